@@ -238,8 +238,7 @@ async fn run_authenticated_checks(
                     HiveError::Other("account response missing active authority".to_string())
                 })
                 .and_then(|value| {
-                    serde_json::from_value::<Authority>(value.clone())
-                        .map_err(|err| HiveError::Serialization(err.to_string()))
+                    serde_json::from_value::<Authority>(value.clone()).map_err(HiveError::from)
                 })?
         }
         Err(err) => {
@@ -270,8 +269,7 @@ async fn run_authenticated_checks(
             let active_value = raw_account.get("active").cloned().ok_or_else(|| {
                 HiveError::Other("raw account payload missing active authority".to_string())
             })?;
-            serde_json::from_value::<Authority>(active_value)
-                .map_err(|parse_err| HiveError::Serialization(parse_err.to_string()))?
+            serde_json::from_value::<Authority>(active_value).map_err(HiveError::from)?
         }
     };
 
@@ -1,9 +1,15 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use serde_json::json;
 
 use crate::client::ClientInner;
 use crate::error::{HiveError, Result};
+use crate::transport::is_missing_api_error;
+
+/// Most keys hived accepts in one `get_key_references` call
+/// (`DATABASE_API_SINGLE_QUERY_LIMIT`).
+pub const KEY_REFERENCES_LIMIT: usize = 1000;
 
 #[derive(Debug, Clone)]
 pub struct AccountByKeyApi {
@@ -34,17 +40,56 @@ impl AccountByKeyApi {
             Err(err) => Err(err),
         }
     }
+
+    /// Accounts referencing each key, keyed by key rather than by position.
+    /// Any number of keys may be passed; they are de-duplicated and sent in
+    /// chunks of [`KEY_REFERENCES_LIMIT`]. Every distinct key gets an entry,
+    /// empty when no account uses it.
+    pub async fn key_reference_map(&self, keys: &[&str]) -> Result<HashMap<String, Vec<String>>> {
+        self.key_reference_map_in_chunks(keys, KEY_REFERENCES_LIMIT)
+            .await
+    }
+
+    async fn key_reference_map_in_chunks(
+        &self,
+        keys: &[&str],
+        chunk_size: usize,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let mut seen = HashSet::new();
+        let distinct = keys
+            .iter()
+            .filter(|key| seen.insert(**key))
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>();
+
+        let mut map = HashMap::with_capacity(distinct.len());
+        for chunk in distinct.chunks(chunk_size) {
+            let references = self.get_key_references(chunk).await?;
+            if references.len() != chunk.len() {
+                return Err(HiveError::json(format!(
+                    "get_key_references returned {} entries for {} keys",
+                    references.len(),
+                    chunk.len()
+                )));
+            }
+            for (key, accounts) in chunk.iter().zip(references) {
+                let merged: &mut Vec<String> = map.entry(key.clone()).or_default();
+                for account in accounts {
+                    if !merged.contains(&account) {
+                        merged.push(account);
+                    }
+                }
+            }
+        }
+        Ok(map)
+    }
 }
 
+/// A missing plugin API, or an older node rejecting the object-style params.
 fn should_fallback_to_condenser(error: &HiveError) -> bool {
-    let HiveError::Rpc { message, .. } = error else {
-        return false;
-    };
-
-    let message = message.to_ascii_lowercase();
-    message.contains("could not find method")
-        || message.contains("could not find api")
-        || message.contains("invalid cast from object_type to array")
+    is_missing_api_error(error)
+        || matches!(error, HiveError::Rpc { message, .. }
+            if message.to_ascii_lowercase().contains("invalid cast from object_type to array"))
 }
 
 #[cfg(test)]
@@ -95,6 +140,49 @@ mod tests {
         assert_eq!(result, vec![vec!["alice".to_string()]]);
     }
 
+    #[tokio::test]
+    async fn key_reference_map_collapses_duplicates_across_chunks() {
+        let server = MockServer::start().await;
+        for (keys, result) in [
+            (json!(["STMa", "STMb"]), json!([["alice", "alice"], []])),
+            (json!(["STMc"]), json!([["bob", "carol"]])),
+        ] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["account_by_key_api", "get_key_references", [{"keys": keys}]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": result
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = AccountByKeyApi::new(inner);
+
+        let map = api
+            .key_reference_map_in_chunks(&["STMa", "STMb", "STMa", "STMc", "STMb"], 2)
+            .await
+            .expect("chunks should resolve");
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["STMa"], vec!["alice".to_string()]);
+        assert!(map["STMb"].is_empty());
+        assert_eq!(map["STMc"], vec!["bob".to_string(), "carol".to_string()]);
+    }
+
     #[tokio::test]
     async fn falls_back_to_condenser_api_when_account_by_key_format_is_rejected() {
         let server = MockServer::start().await;
@@ -1,14 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_stream::try_stream;
-use futures::Stream;
-use serde_json::json;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tokio::sync::Notify;
 
 use crate::client::ClientInner;
 use crate::error::{HiveError, Result};
+use crate::serialization::types::parse_hive_time;
 use crate::types::{AppliedOperation, BlockHeader, DynamicGlobalProperties, SignedBlock};
 
+const BLOCK_INTERVAL_SECS: i64 = 3;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BlockchainMode {
     #[default]
@@ -16,13 +23,66 @@ pub enum BlockchainMode {
     Latest,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct BlockchainStreamOptions {
     pub from: Option<u32>,
     pub to: Option<u32>,
     pub mode: BlockchainMode,
+    /// Ends the stream at its next checkpoint once triggered, including while
+    /// it is waiting for new blocks.
+    pub stop: Option<StopSignal>,
+    /// How many blocks [`Blockchain::get_blocks`] and
+    /// [`Blockchain::get_operations`] fetch ahead concurrently. Output stays
+    /// in block order; 0 and 1 both fetch one block at a time.
+    pub concurrency: usize,
+}
+
+/// A cloneable shutdown flag for block streams. All clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct StopSignal {
+    inner: Arc<StopSignalInner>,
+}
+
+#[derive(Debug, Default)]
+struct StopSignalInner {
+    stopped: AtomicBool,
+    notify: Notify,
+}
+
+impl StopSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stop(&self) {
+        self.inner.stopped.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.inner.stopped.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`StopSignal::stop`] has been called.
+    pub async fn stopped(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_stopped() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl PartialEq for StopSignal {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
 }
 
+impl Eq for StopSignal {}
+
 #[derive(Debug, Clone)]
 pub struct Blockchain {
     client: Arc<ClientInner>,
@@ -69,6 +129,76 @@ impl Blockchain {
         })
     }
 
+    /// Finds the first block produced at or after `target`. Starts from an
+    /// estimate based on the 3s block interval, then brackets and bisects on
+    /// block header timestamps. Returns block 1 for targets before genesis and
+    /// the head block for targets at or after the head time.
+    pub async fn block_num_for_time(&self, target: DateTime<Utc>) -> Result<u32> {
+        let props: DynamicGlobalProperties = self
+            .client
+            .call("condenser_api", "get_dynamic_global_properties", json!([]))
+            .await?;
+        let head = props.head_block_number;
+        let head_time = parse_hive_time(&props.time)?;
+        if head <= 1 || target >= head_time {
+            return Ok(head.max(1));
+        }
+
+        let behind = (head_time - target).num_seconds() / BLOCK_INTERVAL_SECS;
+        let estimate = i64::from(head)
+            .saturating_sub(behind)
+            .clamp(1, i64::from(head)) as u32;
+
+        // Invariant once bracketed: block `lo` is before `target` (0 stands for
+        // "before genesis") and block `hi` is at or after it.
+        let (mut lo, mut hi);
+        let mut step = 1_u32;
+        if self.block_time(estimate).await? >= target {
+            hi = estimate;
+            loop {
+                let candidate = hi.saturating_sub(step);
+                if candidate == 0 || self.block_time(candidate).await? < target {
+                    lo = candidate;
+                    break;
+                }
+                hi = candidate;
+                step = step.saturating_mul(2);
+            }
+        } else {
+            lo = estimate;
+            loop {
+                let candidate = lo.saturating_add(step).min(head);
+                if candidate == head || self.block_time(candidate).await? >= target {
+                    hi = candidate;
+                    break;
+                }
+                lo = candidate;
+                step = step.saturating_mul(2);
+            }
+        }
+
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.block_time(mid).await? >= target {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        Ok(hi)
+    }
+
+    async fn block_time(&self, block_num: u32) -> Result<DateTime<Utc>> {
+        let header: Option<BlockHeader> = self
+            .client
+            .call("condenser_api", "get_block_header", json!([block_num]))
+            .await?;
+        let header = header.ok_or_else(|| {
+            HiveError::Serialization(format!("block header {block_num} not returned by node"))
+        })?;
+        parse_hive_time(&header.timestamp)
+    }
+
     pub fn get_block_numbers(
         &self,
         options: BlockchainStreamOptions,
@@ -84,9 +214,13 @@ impl Blockchain {
                 }
             }
 
+            let stopped = || options.stop.as_ref().is_some_and(StopSignal::is_stopped);
             let mut seen = options.from.unwrap_or(current);
             loop {
                 while current > seen {
+                    if stopped() {
+                        return;
+                    }
                     let next = seen;
                     seen = seen.saturating_add(1);
                     yield next;
@@ -98,7 +232,13 @@ impl Blockchain {
                     }
                 }
 
-                tokio::time::sleep(interval).await;
+                match &options.stop {
+                    Some(stop) => tokio::select! {
+                        _ = tokio::time::sleep(interval) => {}
+                        _ = stop.stopped() => return,
+                    },
+                    None => tokio::time::sleep(interval).await,
+                }
                 current = self.get_current_block_num(options.mode).await?;
             }
         }
@@ -109,15 +249,11 @@ impl Blockchain {
         options: BlockchainStreamOptions,
     ) -> impl Stream<Item = Result<SignedBlock>> + '_ {
         try_stream! {
-            let numbers = self.get_block_numbers(options);
-            futures::pin_mut!(numbers);
-
-            while let Some(number_result) = futures::StreamExt::next(&mut numbers).await {
-                let number = number_result?;
-                let block: Option<SignedBlock> = self
-                    .client
-                    .call("condenser_api", "get_block", json!([number]))
-                    .await?;
+            let blocks = self.prefetch(options, |number| json!([number]), "get_block");
+            futures::pin_mut!(blocks);
+
+            while let Some(block) = blocks.next().await {
+                let block: Option<SignedBlock> = block?;
                 if let Some(block) = block {
                     yield block;
                 }
@@ -130,21 +266,40 @@ impl Blockchain {
         options: BlockchainStreamOptions,
     ) -> impl Stream<Item = Result<AppliedOperation>> + '_ {
         try_stream! {
-            let numbers = self.get_block_numbers(options);
-            futures::pin_mut!(numbers);
-
-            while let Some(number_result) = futures::StreamExt::next(&mut numbers).await {
-                let number = number_result?;
-                let operations: Vec<AppliedOperation> = self
-                    .client
-                    .call("condenser_api", "get_ops_in_block", json!([number, false]))
-                    .await?;
+            let blocks = self.prefetch(
+                options,
+                |number| json!([number, false]),
+                "get_ops_in_block",
+            );
+            futures::pin_mut!(blocks);
+
+            while let Some(operations) = blocks.next().await {
+                let operations: Vec<AppliedOperation> = operations?;
                 for op in operations {
                     yield op;
                 }
             }
         }
     }
+
+    /// Calls `method` for each block number, keeping up to
+    /// `options.concurrency` calls in flight and yielding results in block
+    /// order.
+    fn prefetch<T: DeserializeOwned + 'static>(
+        &self,
+        options: BlockchainStreamOptions,
+        params: fn(u32) -> Value,
+        method: &'static str,
+    ) -> impl Stream<Item = Result<T>> + '_ {
+        let concurrency = options.concurrency.max(1);
+        self.get_block_numbers(options)
+            .map(move |number| async move {
+                self.client
+                    .call("condenser_api", method, params(number?))
+                    .await
+            })
+            .buffered(concurrency)
+    }
 }
 
 #[cfg(test)]
@@ -152,12 +307,14 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration;
 
+    use futures::StreamExt;
     use serde_json::json;
-    use wiremock::matchers::method;
-    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
-    use crate::api::{Blockchain, BlockchainMode};
+    use crate::api::{Blockchain, BlockchainMode, BlockchainStreamOptions, StopSignal};
     use crate::client::{ClientInner, ClientOptions};
+    use crate::serialization::types::{format_hive_time, parse_hive_time};
     use crate::transport::{BackoffStrategy, FailoverTransport};
 
     #[tokio::test]
@@ -203,4 +360,218 @@ mod tests {
         assert_eq!(irreversible, 95);
         assert_eq!(latest, 100);
     }
+
+    #[tokio::test]
+    async fn block_num_for_time_converges_despite_missed_slots() {
+        let server = MockServer::start().await;
+        let genesis = parse_hive_time("2024-01-01T00:00:00").expect("time should parse");
+        // Blocks come every 3s, except for 10 missed slots right after block 500.
+        let block_time = move |block: i64| {
+            let missed = if block > 500 { 10 } else { 0 };
+            genesis + chrono::Duration::seconds(3 * (block - 1 + missed))
+        };
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 1000,
+                    "head_block_id": "000003e800112233445566778899aabbccddeeff00112233445566778899aabb",
+                    "time": format_hive_time(block_time(1000))
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_block_header"]
+            })))
+            .respond_with(move |request: &Request| {
+                let body: serde_json::Value =
+                    serde_json::from_slice(&request.body).expect("request should be json");
+                let block = body["params"][2][0].as_i64().expect("block number");
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": {
+                        "previous": "",
+                        "timestamp": format_hive_time(block_time(block)),
+                        "witness": "initminer",
+                        "transaction_merkle_root": ""
+                    }
+                }))
+            })
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let blockchain = Blockchain::new(inner);
+
+        for (target, expected) in [
+            (block_time(200), 200),
+            (block_time(200) + chrono::Duration::seconds(1), 201),
+            (block_time(700), 700),
+            (block_time(501) - chrono::Duration::seconds(15), 501),
+            (genesis - chrono::Duration::days(1), 1),
+            (block_time(1000) + chrono::Duration::hours(1), 1000),
+        ] {
+            let found = blockchain
+                .block_num_for_time(target)
+                .await
+                .expect("search should succeed");
+            assert_eq!(found, expected, "target {target}");
+        }
+    }
+
+    #[tokio::test]
+    async fn stop_signal_ends_head_following_stream_without_waiting() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 10,
+                    "head_block_id": "0000000a00112233445566778899aabbccddeeff00112233445566778899aabb",
+                    "time": "2024-01-01T00:00:00",
+                    "last_irreversible_block_num": 10
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let blockchain = Blockchain::new(inner);
+
+        let stop = StopSignal::new();
+        let stream = blockchain.get_block_numbers(BlockchainStreamOptions {
+            from: Some(8),
+            stop: Some(stop.clone()),
+            ..Default::default()
+        });
+        futures::pin_mut!(stream);
+
+        assert_eq!(stream.next().await.transpose().unwrap(), Some(8));
+        assert_eq!(stream.next().await.transpose().unwrap(), Some(9));
+
+        // The stream is now caught up and sleeping; stopping must wake it.
+        let stopper = {
+            let stop = stop.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                stop.stop();
+            })
+        };
+        let next = tokio::time::timeout(Duration::from_secs(1), stream.next())
+            .await
+            .expect("stream should end before the next poll interval");
+        assert!(next.is_none());
+        stopper.await.expect("stopper should finish");
+    }
+
+    #[tokio::test]
+    async fn concurrent_operations_stream_keeps_block_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 10,
+                    "head_block_id": "0000000a00112233445566778899aabbccddeeff00112233445566778899aabb",
+                    "time": "2024-01-01T00:00:00",
+                    "last_irreversible_block_num": 10
+                }
+            })))
+            .mount(&server)
+            .await;
+        // Earlier blocks answer more slowly, so responses complete in reverse.
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_ops_in_block"]
+            })))
+            .respond_with(|request: &Request| {
+                let body: serde_json::Value =
+                    serde_json::from_slice(&request.body).expect("request should be json");
+                let block = body["params"][2][0].as_u64().expect("block number");
+                let ops = (0..2)
+                    .map(|op_in_trx| {
+                        json!({
+                            "block": block,
+                            "op_in_trx": op_in_trx,
+                            "op": ["vote", {
+                                "voter": "alice",
+                                "author": "bob",
+                                "permlink": format!("post-{block}"),
+                                "weight": 10000
+                            }]
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                ResponseTemplate::new(200)
+                    .set_delay(Duration::from_millis(50 * (6 - block)))
+                    .set_body_json(json!({
+                        "id": 0,
+                        "jsonrpc": "2.0",
+                        "result": ops
+                    }))
+            })
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let blockchain = Blockchain::new(inner);
+
+        let ops = blockchain
+            .get_operations(BlockchainStreamOptions {
+                from: Some(1),
+                to: Some(5),
+                concurrency: 4,
+                ..Default::default()
+            })
+            .map(|op| op.map(|op| (op.block, op.op_in_trx)))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<crate::error::Result<Vec<_>>>()
+            .expect("stream should succeed");
+        let expected = (1..=5)
+            .flat_map(|block| [(block, 0), (block, 1)])
+            .collect::<Vec<_>>();
+        assert_eq!(ops, expected);
+    }
 }
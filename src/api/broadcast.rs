@@ -1,42 +1,63 @@
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::client::ClientInner;
-use crate::crypto::{sign_transaction, PrivateKey};
+use crate::api::RcApi;
+use crate::client::{ClientInner, WaitOptions};
+use crate::crypto::verify::authority_satisfied;
+use crate::crypto::{sign_transaction, KeyRole, PrivateKey};
 use crate::error::{HiveError, Result};
-use crate::serialization::generate_trx_id;
+use crate::serialization::generate_trx_id_for;
 use crate::serialization::types::{format_hive_time, parse_hive_time};
+use crate::types::rc::MANA_REGEN_SECONDS;
 use crate::types::{
     AccountCreateOperation, AccountCreateWithDelegationOperation, AccountUpdate2Operation,
-    AccountUpdateOperation, AccountWitnessProxyOperation, AccountWitnessVoteOperation,
-    CancelTransferFromSavingsOperation, ChangeRecoveryAccountOperation, ClaimAccountOperation,
-    ClaimRewardBalanceOperation, CollateralizedConvertOperation, CommentOperation,
-    CommentOptionsOperation, ConvertOperation, CreateClaimedAccountOperation,
+    AccountUpdateOperation, AccountWitnessProxyOperation, AccountWitnessVoteOperation, Authority,
+    CancelTransferFromSavingsOperation, ChainId, ChangeRecoveryAccountOperation,
+    ClaimAccountOperation, ClaimRewardBalanceOperation, CollateralizedConvertOperation,
+    CommentOperation, CommentOptionsOperation, ConvertOperation, CreateClaimedAccountOperation,
     CreateProposalOperation, CustomBinaryOperation, CustomJsonOperation, CustomOperation,
     DeclineVotingRightsOperation, DelegateVestingSharesOperation, DeleteCommentOperation,
     DynamicGlobalProperties, EscrowApproveOperation, EscrowDisputeOperation,
-    EscrowReleaseOperation, EscrowTransferOperation, FeedPublishOperation,
-    LimitOrderCancelOperation, LimitOrderCreate2Operation, LimitOrderCreateOperation, Operation,
-    RecoverAccountOperation, RecurrentTransferOperation, RemoveProposalOperation,
-    ReportOverProductionOperation, RequestAccountRecoveryOperation, ResetAccountOperation,
-    SetResetAccountOperation, SetWithdrawVestingRouteOperation, SignedTransaction, Transaction,
-    TransactionConfirmation, TransferFromSavingsOperation, TransferOperation,
-    TransferToSavingsOperation, TransferToVestingOperation, UpdateProposalOperation,
-    UpdateProposalVotesOperation, VoteOperation, WithdrawVestingOperation, WitnessProps,
-    WitnessUpdateOperation,
+    EscrowReleaseOperation, EscrowTransferOperation, ExtendedAccount, FeedPublishOperation,
+    LimitOrderCancelOperation, LimitOrderCreate2Operation, LimitOrderCreateOperation, ManaResult,
+    Operation, RecoverAccountOperation, RecurrentTransferOperation, RemoveProposalOperation,
+    ReportOverProductionOperation, RequestAccountRecoveryOperation, RequiredAuthorities,
+    ResetAccountOperation, SetResetAccountOperation, SetWithdrawVestingRouteOperation,
+    SignedTransaction, Transaction, TransactionConfirmation, TransferFromSavingsOperation,
+    TransferOperation, TransferToSavingsOperation, TransferToVestingOperation,
+    UpdateProposalOperation, UpdateProposalVotesOperation, VoteOperation, WithdrawVestingOperation,
+    WitnessProps, WitnessUpdateOperation,
 };
-use crate::utils::build_witness_update_op;
+use crate::utils::{build_witness_update_op, required_roles};
+
+/// New authorities for [`BroadcastApi::rotate_keys`]; `None` keeps the
+/// account's current authority or memo key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyRotation {
+    pub owner: Option<Authority>,
+    pub active: Option<Authority>,
+    pub posting: Option<Authority>,
+    pub memo_key: Option<String>,
+}
 
 #[derive(Debug, Clone)]
 pub struct BroadcastApi {
     client: Arc<ClientInner>,
+    recent: Arc<Mutex<RecentBroadcasts>>,
 }
 
 impl BroadcastApi {
     pub(crate) fn new(client: Arc<ClientInner>) -> Self {
-        Self { client }
+        let capacity = client.options().broadcast_dedup_capacity;
+        Self {
+            client,
+            recent: Arc::new(Mutex::new(RecentBroadcasts::new(capacity))),
+        }
     }
 
     pub async fn create_transaction(
@@ -48,7 +69,17 @@ impl BroadcastApi {
             .client
             .call("condenser_api", "get_dynamic_global_properties", json!([]))
             .await?;
+        self.create_transaction_with_props(operations, expiration, &props)
+    }
 
+    /// Builds a transaction referencing the head block in an already-fetched
+    /// `props` snapshot, so batches can share a single global-properties fetch.
+    pub fn create_transaction_with_props(
+        &self,
+        operations: Vec<Operation>,
+        expiration: Option<Duration>,
+        props: &DynamicGlobalProperties,
+    ) -> Result<Transaction> {
         let ref_block_num = props.head_block_number & 0xFFFF;
         let block_id = hex::decode(&props.head_block_id).map_err(|err| {
             HiveError::Serialization(format!(
@@ -66,19 +97,68 @@ impl BroadcastApi {
                 HiveError::Serialization("invalid ref block prefix bytes".to_string())
             })?);
 
-        let expiration_time = expiration.unwrap_or(Duration::from_secs(60));
+        let expiration_time =
+            self.clamp_expiration(expiration.unwrap_or(Duration::from_secs(60)))?;
         let expiration_time = parse_hive_time(&props.time)?
             + chrono::Duration::from_std(expiration_time).map_err(|err| {
                 HiveError::Serialization(format!("invalid expiration duration: {err}"))
             })?;
 
-        Ok(Transaction {
-            ref_block_num: ref_block_num as u16,
+        self.create_transaction_deterministic(
+            operations,
+            ref_block_num as u16,
+            ref_block_prefix,
+            expiration_time,
+        )
+    }
+
+    /// Builds a transaction from explicit TaPoS inputs without touching the
+    /// network, so identical inputs always produce an identical transaction.
+    pub fn create_transaction_deterministic(
+        &self,
+        operations: Vec<Operation>,
+        ref_block_num: u16,
+        ref_block_prefix: u32,
+        expiration: DateTime<Utc>,
+    ) -> Result<Transaction> {
+        let transaction = Transaction {
+            ref_block_num,
             ref_block_prefix,
-            expiration: format_hive_time(expiration_time),
+            expiration: format_hive_time(expiration),
             operations,
             extensions: vec![],
-        })
+        };
+
+        let size = transaction.serialized_size()?;
+        let max_size = self.client.options().max_transaction_size;
+        if size > max_size {
+            return Err(HiveError::Other(format!(
+                "transaction exceeds max size: {size} bytes (limit {max_size})"
+            )));
+        }
+        Ok(transaction)
+    }
+
+    fn clamp_expiration(&self, requested: Duration) -> Result<Duration> {
+        let options = self.client.options();
+        if requested <= options.max_expiration {
+            return Ok(requested);
+        }
+        if options.strict_expiration {
+            return Err(HiveError::Other(format!(
+                "requested expiration of {}s exceeds the maximum of {}s",
+                requested.as_secs(),
+                options.max_expiration.as_secs()
+            )));
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            requested_secs = requested.as_secs(),
+            max_secs = options.max_expiration.as_secs(),
+            "clamping transaction expiration to the chain maximum"
+        );
+        Ok(options.max_expiration)
     }
 
     pub fn sign_transaction(
@@ -89,7 +169,85 @@ impl BroadcastApi {
         sign_transaction(transaction, keys, &self.client.options().chain_id)
     }
 
+    /// Like [`BroadcastApi::sign_transaction`], but first checks with
+    /// [`check_key_role`](Self::check_key_role) that `keys` can authorize the
+    /// operations on `account`.
+    pub fn sign_transaction_checked(
+        &self,
+        transaction: &Transaction,
+        keys: &[&PrivateKey],
+        account: &ExtendedAccount,
+    ) -> Result<SignedTransaction> {
+        Self::check_key_role(&transaction.operations, keys, account)?;
+        self.sign_transaction(transaction, keys)
+    }
+
+    /// Fails with "key role insufficient" unless `keys` alone meet the
+    /// threshold of the role [`required_roles`] derives for `operations`, or
+    /// of a stricter role, on `account`, and of every authority the
+    /// operations name directly (see [`RequiredAuthorities::other`]). Account
+    /// references in the authorities are not followed.
+    pub fn check_key_role(
+        operations: &[Operation],
+        keys: &[&PrivateKey],
+        account: &ExtendedAccount,
+    ) -> Result<()> {
+        let signers = keys.iter().map(|key| key.public_key()).collect::<Vec<_>>();
+        let named = RequiredAuthorities::for_operations(operations).other;
+        if !named
+            .iter()
+            .all(|authority| authority_satisfied(authority, &signers, &|_| None, 0))
+        {
+            return Err(HiveError::Other(
+                "key role insufficient: operations need the keys or authorities they name"
+                    .to_string(),
+            ));
+        }
+        let Some(required) = required_roles(operations) else {
+            return Ok(());
+        };
+        let accepted: &[KeyRole] = match required {
+            KeyRole::Owner => &[KeyRole::Owner],
+            KeyRole::Active => &[KeyRole::Active, KeyRole::Owner],
+            _ => &[KeyRole::Posting, KeyRole::Active, KeyRole::Owner],
+        };
+        let satisfied = accepted.iter().any(|role| {
+            account
+                .authority(*role)
+                .is_some_and(|authority| authority_satisfied(authority, &signers, &|_| None, 0))
+        });
+        if satisfied {
+            return Ok(());
+        }
+        Err(HiveError::Other(format!(
+            "key role insufficient: operations need the {} authority of '{}'",
+            required.as_str(),
+            account.name
+        )))
+    }
+
     pub async fn send(&self, transaction: SignedTransaction) -> Result<TransactionConfirmation> {
+        let dedup_id = if self.dedup_enabled() {
+            let tx_id = signed_transaction_id(&transaction, &self.client.options().chain_id)?;
+            if let Some(confirmation) = self.recent_lock().get(&tx_id) {
+                return Ok(confirmation);
+            }
+            Some(tx_id)
+        } else {
+            None
+        };
+
+        let confirmation = self.send_uncached(transaction).await?;
+        if let Some(tx_id) = dedup_id {
+            self.recent_lock().insert(tx_id, confirmation.clone());
+        }
+        Ok(confirmation)
+    }
+
+    async fn send_uncached(
+        &self,
+        transaction: SignedTransaction,
+    ) -> Result<TransactionConfirmation> {
         match self
             .client
             .call(
@@ -107,6 +265,36 @@ impl BroadcastApi {
         }
     }
 
+    /// Broadcasts via `broadcast_transaction_synchronous` only, never falling
+    /// back to async broadcast when the sync endpoint is unavailable.
+    pub async fn send_sync_only(
+        &self,
+        transaction: SignedTransaction,
+    ) -> Result<TransactionConfirmation> {
+        self.client
+            .call(
+                "condenser_api",
+                "broadcast_transaction_synchronous",
+                json!([transaction]),
+            )
+            .await
+    }
+
+    /// Fire-and-forget broadcast: returns the transaction id as soon as the node
+    /// accepts it, without waiting for inclusion in a block.
+    pub async fn send_async(&self, transaction: SignedTransaction) -> Result<String> {
+        let tx_id = signed_transaction_id(&transaction, &self.client.options().chain_id)?;
+        let _: Value = self
+            .client
+            .call(
+                "condenser_api",
+                "broadcast_transaction",
+                json!([transaction]),
+            )
+            .await?;
+        Ok(tx_id)
+    }
+
     pub async fn send_operations(
         &self,
         operations: Vec<Operation>,
@@ -117,6 +305,49 @@ impl BroadcastApi {
         self.send(signed).await
     }
 
+    /// [`send_operations`](Self::send_operations) with a
+    /// [`check_key_role`](Self::check_key_role) against `account` first, so a
+    /// key of the wrong role fails before anything is fetched or broadcast.
+    pub async fn send_operations_checked(
+        &self,
+        operations: Vec<Operation>,
+        key: &PrivateKey,
+        account: &ExtendedAccount,
+    ) -> Result<TransactionConfirmation> {
+        Self::check_key_role(&operations, &[key], account)?;
+        self.send_operations(operations, key).await
+    }
+
+    /// [`send_operations`](Self::send_operations) once `account` has enough
+    /// resource credits for `operations`. While it cannot afford them, sleeps
+    /// for the estimated regeneration time (at most `options.interval`) and
+    /// checks again; gives up with [`HiveError::Timeout`] after
+    /// `options.timeout`.
+    pub async fn send_when_affordable(
+        &self,
+        operations: Vec<Operation>,
+        key: &PrivateKey,
+        account: &str,
+        options: WaitOptions,
+    ) -> Result<TransactionConfirmation> {
+        let rc = RcApi::new(self.client.clone());
+        let cost = rc.calculate_cost(&operations).await?;
+        let wait = async {
+            loop {
+                let mana = rc.get_rc_mana(account).await?;
+                if mana.current >= cost {
+                    return Ok::<(), HiveError>(());
+                }
+                tokio::time::sleep(regeneration_time(&mana, cost).min(options.interval)).await;
+            }
+        };
+        match tokio::time::timeout(options.timeout, wait).await {
+            Ok(result) => result?,
+            Err(_) => return Err(HiveError::Timeout),
+        }
+        self.send_operations(operations, key).await
+    }
+
     pub async fn comment_with_options(
         &self,
         comment: CommentOperation,
@@ -243,6 +474,60 @@ impl BroadcastApi {
             .await
     }
 
+    /// Replaces the roles set in `rotation` with an `account_update` signed by
+    /// `current_owner_key`, carrying the current active, posting and memo keys
+    /// over for the rest. The owner is only sent when it changes, since every
+    /// owner update counts against the chain's owner update limit.
+    ///
+    /// Unless `force` is set, fails before broadcasting if
+    /// `current_owner_key` would not satisfy the resulting owner authority, so
+    /// a typo in the new owner cannot lock the account out.
+    pub async fn rotate_keys(
+        &self,
+        account: &str,
+        rotation: KeyRotation,
+        current_owner_key: &PrivateKey,
+        force: bool,
+    ) -> Result<TransactionConfirmation> {
+        let current = self
+            .client
+            .call::<Vec<ExtendedAccount>>("condenser_api", "get_accounts", json!([[account]]))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| HiveError::Other(format!("account '{account}' not found")))?;
+
+        if !force {
+            let owner = rotation
+                .owner
+                .as_ref()
+                .or(current.owner.as_ref())
+                .ok_or_else(|| HiveError::Other(format!("account '{account}' has no owner")))?;
+            let signer = [current_owner_key.public_key()];
+            if !authority_satisfied(owner, &signer, &|_: &str| None, 0) {
+                return Err(HiveError::Other(format!(
+                    "signing key would not satisfy the new owner authority of '{account}'"
+                )));
+            }
+        }
+
+        let memo_key = rotation
+            .memo_key
+            .or(current.memo_key)
+            .ok_or_else(|| HiveError::Other(format!("account '{account}' has no memo key")))?;
+        let params = AccountUpdateOperation {
+            account: account.to_string(),
+            owner: rotation.owner,
+            active: rotation.active.or(current.active),
+            posting: rotation.posting.or(current.posting),
+            memo_key,
+            json_metadata: String::new(),
+        };
+        let confirmation = self.account_update(params, current_owner_key).await?;
+        self.client.authority_cache().invalidate(account);
+        Ok(confirmation)
+    }
+
     pub async fn witness_update(
         &self,
         params: WitnessUpdateOperation,
@@ -279,6 +564,18 @@ impl BroadcastApi {
             .await
     }
 
+    /// Broadcasts a numeric custom op carrying `payload` as JSON bytes.
+    pub async fn send_custom(
+        &self,
+        id: u16,
+        required_auths: &[&str],
+        payload: &impl Serialize,
+        key: &PrivateKey,
+    ) -> Result<TransactionConfirmation> {
+        let params = CustomOperation::new(id, required_auths, payload)?;
+        self.custom(params, key).await
+    }
+
     pub async fn report_over_production(
         &self,
         params: ReportOverProductionOperation,
@@ -306,6 +603,19 @@ impl BroadcastApi {
             .await
     }
 
+    /// Delegates up to `max_rc` resource credits from `from` to each account in
+    /// `to`, via the `rc` custom_json. A `max_rc` of 0 removes the delegation.
+    pub async fn delegate_rc(
+        &self,
+        from: &str,
+        to: &[&str],
+        max_rc: i64,
+        key: &PrivateKey,
+    ) -> Result<TransactionConfirmation> {
+        let params = delegate_rc_operation(from, to, max_rc)?;
+        self.custom_json(params, key).await
+    }
+
     pub async fn comment_options(
         &self,
         params: CommentOptionsOperation,
@@ -567,28 +877,41 @@ impl BroadcastApi {
             .await
     }
 
+    fn dedup_enabled(&self) -> bool {
+        self.client.options().broadcast_dedup_capacity > 0
+    }
+
+    fn recent_lock(&self) -> std::sync::MutexGuard<'_, RecentBroadcasts> {
+        // The cache holds no invariants a panic could break, so recover it.
+        self.recent
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     async fn send_async_with_confirmation(
         &self,
         transaction: SignedTransaction,
     ) -> Result<TransactionConfirmation> {
-        let tx_id = signed_transaction_id(&transaction)?;
-
-        let _: Value = self
-            .client
-            .call(
-                "condenser_api",
-                "broadcast_transaction",
-                json!([transaction]),
-            )
-            .await?;
+        let tx_id = self.send_async(transaction).await?;
 
         for _ in 0..15 {
             match self
                 .client
-                .call::<Value>("condenser_api", "get_transaction", json!([tx_id.clone()]))
+                .call::<TransactionPosition>(
+                    "condenser_api",
+                    "get_transaction",
+                    json!([tx_id.clone()]),
+                )
                 .await
             {
-                Ok(found) => return Ok(confirmation_from_condenser_transaction(&tx_id, &found)),
+                Ok(found) => {
+                    return Ok(TransactionConfirmation {
+                        id: tx_id,
+                        block_num: found.block_num,
+                        trx_num: found.transaction_num,
+                        expired: false,
+                    })
+                }
                 Err(err) if is_transient_lookup_error(&err) => {
                     tokio::time::sleep(Duration::from_secs(1)).await;
                     continue;
@@ -607,10 +930,71 @@ impl BroadcastApi {
     }
 }
 
+/// Where a looked-up transaction landed. Only these fields are decoded so a
+/// confirmation never hinges on parsing the transaction's operations.
+#[derive(Debug, Deserialize)]
+struct TransactionPosition {
+    block_num: u32,
+    #[serde(alias = "trx_num")]
+    transaction_num: u32,
+}
+
+/// Bounded LRU of confirmations keyed by transaction id.
+#[derive(Debug)]
+struct RecentBroadcasts {
+    capacity: usize,
+    order: VecDeque<String>,
+    confirmations: HashMap<String, TransactionConfirmation>,
+}
+
+impl RecentBroadcasts {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::new(),
+            confirmations: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, tx_id: &str) -> Option<TransactionConfirmation> {
+        let confirmation = self.confirmations.get(tx_id)?.clone();
+        self.touch(tx_id);
+        Some(confirmation)
+    }
+
+    fn insert(&mut self, tx_id: String, confirmation: TransactionConfirmation) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self
+            .confirmations
+            .insert(tx_id.clone(), confirmation)
+            .is_some()
+        {
+            self.touch(&tx_id);
+            return;
+        }
+        self.order.push_back(tx_id);
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.confirmations.remove(&evicted);
+            }
+        }
+    }
+
+    fn touch(&mut self, tx_id: &str) {
+        if let Some(position) = self.order.iter().position(|id| id == tx_id) {
+            if let Some(id) = self.order.remove(position) {
+                self.order.push_back(id);
+            }
+        }
+    }
+}
+
 fn should_fallback_to_async_broadcast(error: &HiveError) -> bool {
     match error {
-        HiveError::Transport(_) | HiveError::Timeout | HiveError::AllNodesFailed => true,
-        HiveError::Serialization(_) => true,
+        HiveError::Transport { .. } | HiveError::Timeout | HiveError::AllNodesFailed => true,
+        HiveError::Serialization(_) | HiveError::Json(_) => true,
         HiveError::Rpc { message, .. } => {
             let message = message.to_ascii_lowercase();
             message.contains("could not find method") || message.contains("could not find api")
@@ -619,45 +1003,51 @@ fn should_fallback_to_async_broadcast(error: &HiveError) -> bool {
     }
 }
 
-fn signed_transaction_id(transaction: &SignedTransaction) -> Result<String> {
-    let unsigned = Transaction {
-        ref_block_num: transaction.ref_block_num,
-        ref_block_prefix: transaction.ref_block_prefix,
-        expiration: transaction.expiration.clone(),
-        operations: transaction.operations.clone(),
-        extensions: transaction.extensions.clone(),
-    };
+fn delegate_rc_operation(from: &str, to: &[&str], max_rc: i64) -> Result<CustomJsonOperation> {
+    if to.is_empty() {
+        return Err(HiveError::Other(
+            "delegate_rc requires at least one delegatee".to_string(),
+        ));
+    }
+    if max_rc < 0 {
+        return Err(HiveError::Other(format!(
+            "delegate_rc max_rc must not be negative, got {max_rc}"
+        )));
+    }
+    let payload = json!([
+        "delegate_rc",
+        {
+            "from": from,
+            "delegatees": to,
+            "max_rc": max_rc,
+        }
+    ]);
+    Ok(CustomJsonOperation {
+        required_auths: vec![],
+        required_posting_auths: vec![from.to_string()],
+        id: "rc".to_string(),
+        json: serde_json::to_string(&payload)?,
+    })
+}
 
-    generate_trx_id(&unsigned)
+fn signed_transaction_id(transaction: &SignedTransaction, chain_id: &ChainId) -> Result<String> {
+    generate_trx_id_for(&transaction.unsigned(), chain_id)
 }
 
-fn confirmation_from_condenser_transaction(
-    tx_id: &str,
-    transaction: &Value,
-) -> TransactionConfirmation {
-    let block_num = transaction
-        .get("block_num")
-        .and_then(Value::as_u64)
-        .and_then(|value| u32::try_from(value).ok())
-        .unwrap_or(0);
-    let trx_num = transaction
-        .get("transaction_num")
-        .or_else(|| transaction.get("trx_num"))
-        .and_then(Value::as_u64)
-        .and_then(|value| u32::try_from(value).ok())
-        .unwrap_or(0);
-
-    TransactionConfirmation {
-        id: tx_id.to_string(),
-        block_num,
-        trx_num,
-        expired: false,
+/// Time for `mana` to regenerate up to `cost`, at the chain's rate of a full
+/// manabar every five days.
+fn regeneration_time(mana: &ManaResult, cost: i64) -> Duration {
+    if mana.max <= 0 {
+        return Duration::MAX;
     }
+    let deficit = (cost - mana.current).max(0) as i128;
+    let seconds = (deficit * MANA_REGEN_SECONDS as i128 + mana.max as i128 - 1) / mana.max as i128;
+    Duration::from_secs(u64::try_from(seconds).unwrap_or(u64::MAX))
 }
 
 fn is_transient_lookup_error(error: &HiveError) -> bool {
     match error {
-        HiveError::Transport(_) | HiveError::Timeout | HiveError::AllNodesFailed => true,
+        HiveError::Transport { .. } | HiveError::Timeout | HiveError::AllNodesFailed => true,
         HiveError::Rpc { message, .. } => {
             let message = message.to_ascii_lowercase();
             message.contains("unknown transaction")
@@ -675,15 +1065,127 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration;
 
-    use serde_json::json;
+    use serde_json::{json, Value};
     use wiremock::matchers::{body_partial_json, method};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
-    use crate::api::BroadcastApi;
-    use crate::client::{ClientInner, ClientOptions};
+    use crate::api::{BroadcastApi, KeyRotation};
+    use crate::client::{ClientInner, ClientOptions, WaitOptions};
     use crate::crypto::PrivateKey;
+    use crate::serialization::types::parse_hive_time;
+    use crate::serialization::{generate_trx_id, serialize_transaction};
+    use crate::test_support::mount_cost_state;
     use crate::transport::{BackoffStrategy, FailoverTransport};
-    use crate::types::{Asset, Operation, SignedTransaction, TransferOperation};
+    use crate::types::{
+        Asset, Authority, CustomJsonOperation, DynamicGlobalProperties, ExtendedAccount, Operation,
+        RecoverAccountOperation, SignedTransaction, Transaction, TransferOperation,
+        WitnessSetPropertiesOperation,
+    };
+
+    #[tokio::test]
+    async fn send_operations_checked_rejects_posting_key_for_transfer() {
+        let server = MockServer::start().await;
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = BroadcastApi::new(inner);
+
+        let posting = PrivateKey::from_seed("alice-posting").expect("seed should derive");
+        let active = PrivateKey::from_seed("alice-active").expect("seed should derive");
+        let account: ExtendedAccount = serde_json::from_value(json!({
+            "name": "alice",
+            "active": Authority::from_key(&active.public_key().to_string()),
+            "posting": Authority::from_key(&posting.public_key().to_string())
+        }))
+        .expect("account should parse");
+        let transfer = Operation::Transfer(TransferOperation {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: Asset::hive(1.0),
+            memo: String::new(),
+        });
+
+        let err = api
+            .send_operations_checked(vec![transfer.clone()], &posting, &account)
+            .await
+            .expect_err("posting key cannot sign a transfer");
+        assert!(
+            err.to_string().starts_with("key role insufficient"),
+            "{err}"
+        );
+        assert!(server.received_requests().await.unwrap().is_empty());
+
+        assert!(BroadcastApi::check_key_role(
+            std::slice::from_ref(&transfer),
+            &[&active],
+            &account
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn check_key_role_accepts_the_signing_key_for_witness_set_properties() {
+        let owner = PrivateKey::from_seed("alice-owner").expect("seed should derive");
+        let active = PrivateKey::from_seed("alice-active").expect("seed should derive");
+        let signing = PrivateKey::from_seed("alice-signing").expect("seed should derive");
+        let account: ExtendedAccount = serde_json::from_value(json!({
+            "name": "alice",
+            "owner": Authority::from_key(&owner.public_key().to_string()),
+            "active": Authority::from_key(&active.public_key().to_string())
+        }))
+        .expect("account should parse");
+        let operation = Operation::WitnessSetProperties(WitnessSetPropertiesOperation {
+            owner: "alice".to_string(),
+            props: vec![(
+                "key".to_string(),
+                signing.public_key().compressed_bytes().to_vec(),
+            )],
+            extensions: vec![],
+        });
+        let operations = std::slice::from_ref(&operation);
+
+        assert!(BroadcastApi::check_key_role(operations, &[&signing], &account).is_ok());
+        for key in [&owner, &active] {
+            let err = BroadcastApi::check_key_role(operations, &[key], &account)
+                .expect_err("only the signing key can set witness properties");
+            assert!(
+                err.to_string().starts_with("key role insufficient"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn check_key_role_uses_the_owner_authorities_recover_account_names() {
+        let current = PrivateKey::from_seed("alice-owner").expect("seed should derive");
+        let new_owner = PrivateKey::from_seed("alice-new-owner").expect("seed should derive");
+        let recent_owner = PrivateKey::from_seed("alice-recent-owner").expect("seed should derive");
+        let account: ExtendedAccount = serde_json::from_value(json!({
+            "name": "alice",
+            "owner": Authority::from_key(&current.public_key().to_string())
+        }))
+        .expect("account should parse");
+        let operation = Operation::RecoverAccount(RecoverAccountOperation::build(
+            "alice",
+            Authority::from_key(&new_owner.public_key().to_string()),
+            Authority::from_key(&recent_owner.public_key().to_string()),
+        ));
+        let operations = std::slice::from_ref(&operation);
+
+        assert!(
+            BroadcastApi::check_key_role(operations, &[&new_owner, &recent_owner], &account)
+                .is_ok()
+        );
+        assert!(BroadcastApi::check_key_role(operations, &[&current], &account).is_err());
+        assert!(BroadcastApi::check_key_role(operations, &[&new_owner], &account).is_err());
+    }
 
     #[tokio::test]
     async fn send_operations_builds_signs_and_broadcasts() {
@@ -758,6 +1260,116 @@ mod tests {
         assert!(!result.expired);
     }
 
+    #[tokio::test]
+    async fn send_when_affordable_waits_for_rc_to_regenerate() {
+        let server = MockServer::start().await;
+        mount_cost_state(&server).await;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock should be after the epoch")
+            .as_secs();
+        let rc_account = |current_mana: i64| {
+            json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": { "rc_accounts": [{
+                    "account": "alice",
+                    "max_rc": "1000000000000000",
+                    "rc_manabar": { "current_mana": current_mana.to_string(), "last_update_time": now }
+                }] }
+            })
+        };
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["rc_api", "find_rc_accounts"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(rc_account(0)))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["rc_api", "find_rc_accounts"]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(rc_account(1_000_000_000_000_000)),
+            )
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 42,
+                    "head_block_id": "0000002a11223344556677889900aabbccddeeff00112233445566778899aabb",
+                    "time": "2024-01-01T00:00:00",
+                    "last_irreversible_block_num": 41
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "broadcast_transaction_synchronous"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": { "id": "abc", "block_num": 43, "trx_num": 0, "expired": false }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+        let key = PrivateKey::from_seed("alice-active").expect("seed should derive");
+        let transfer = Operation::Transfer(TransferOperation {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: Asset::hive(1.0),
+            memo: String::new(),
+        });
+
+        let options = WaitOptions {
+            interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        };
+        let result = broadcast
+            .send_when_affordable(vec![transfer], &key, "alice", options)
+            .await
+            .expect("operation should broadcast once affordable");
+        assert_eq!(result.block_num, 43);
+
+        let polls = server
+            .received_requests()
+            .await
+            .expect("requests should be recorded")
+            .iter()
+            .filter(|request| {
+                let body: Value = serde_json::from_slice(&request.body).expect("json body");
+                body["params"][1] == "find_rc_accounts"
+            })
+            .count();
+        assert_eq!(polls, 2);
+    }
+
     #[tokio::test]
     async fn send_falls_back_to_async_broadcast_when_sync_endpoint_fails() {
         let server = MockServer::start().await;
@@ -826,4 +1438,598 @@ mod tests {
         assert_eq!(result.trx_num, 7);
         assert!(!result.id.is_empty());
     }
+
+    #[tokio::test]
+    async fn async_confirmation_ignores_operations_it_cannot_decode() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "broadcast_transaction_synchronous"]
+            })))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "broadcast_transaction"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {}
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_transaction"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "operations": [["transfer", { "amount": 5 }]],
+                    "block_num": 42,
+                    "transaction_num": 7
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+
+        let tx = SignedTransaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+            signatures: vec!["1f00".to_string()],
+        };
+
+        let result = broadcast.send(tx).await.expect("fallback should succeed");
+        assert_eq!((result.block_num, result.trx_num), (42, 7));
+    }
+
+    #[tokio::test]
+    async fn send_returns_cached_confirmation_for_repeated_transaction() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "broadcast_transaction_synchronous"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "id": "abc",
+                    "block_num": 42,
+                    "trx_num": 1,
+                    "expired": false
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let options = ClientOptions {
+            broadcast_dedup_capacity: 16,
+            ..ClientOptions::default()
+        };
+        let inner = Arc::new(ClientInner::new(transport, options));
+        let broadcast = BroadcastApi::new(inner);
+
+        let tx = SignedTransaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+            signatures: vec!["1f00".to_string()],
+        };
+
+        let first = broadcast
+            .send(tx.clone())
+            .await
+            .expect("first send should succeed");
+        let second = broadcast
+            .send(tx)
+            .await
+            .expect("second send should succeed");
+        assert_eq!(first, second);
+        assert_eq!(second.block_num, 42);
+    }
+
+    #[tokio::test]
+    async fn send_async_broadcasts_once_and_returns_trx_id() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "broadcast_transaction"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+
+        let tx = SignedTransaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+            signatures: vec!["1f00".to_string()],
+        };
+        let expected = generate_trx_id(&Transaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+        })
+        .expect("trx id should compute");
+
+        let tx_id = broadcast
+            .send_async(tx)
+            .await
+            .expect("async broadcast should succeed");
+        assert_eq!(tx_id, expected);
+    }
+
+    #[tokio::test]
+    async fn send_sync_only_does_not_fall_back() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "broadcast_transaction_synchronous"]
+            })))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "broadcast_transaction"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {}
+            })))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+
+        let tx = SignedTransaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+            signatures: vec!["1f00".to_string()],
+        };
+
+        assert!(broadcast.send_sync_only(tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn delegate_rc_broadcasts_rc_custom_json() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 42,
+                    "head_block_id": "0000002a11223344556677889900aabbccddeeff00112233445566778899aabb",
+                    "time": "2024-01-01T00:00:00"
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "broadcast_transaction_synchronous"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"id": "abc", "block_num": 42, "trx_num": 0, "expired": false}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+        let key = PrivateKey::from_wif("5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL")
+            .expect("valid private key");
+
+        assert!(broadcast.delegate_rc("alice", &[], 10, &key).await.is_err());
+        assert!(broadcast
+            .delegate_rc("alice", &["bob"], -1, &key)
+            .await
+            .is_err());
+
+        broadcast
+            .delegate_rc("alice", &["bob", "carol"], 5_000_000_000, &key)
+            .await
+            .expect("delegation should broadcast");
+
+        let requests = server.received_requests().await.expect("requests recorded");
+        let body: Value = requests
+            .iter()
+            .map(|request| serde_json::from_slice::<Value>(&request.body).expect("json body"))
+            .find(|body| body["params"][1] == "broadcast_transaction_synchronous")
+            .expect("broadcast request sent");
+        let op = &body["params"][2][0]["operations"][0];
+        assert_eq!(op[0], "custom_json");
+        assert_eq!(op[1]["id"], "rc");
+        assert_eq!(op[1]["required_auths"], json!([]));
+        assert_eq!(op[1]["required_posting_auths"], json!(["alice"]));
+        let payload: Value =
+            serde_json::from_str(op[1]["json"].as_str().expect("json string")).expect("payload");
+        assert_eq!(
+            payload,
+            json!([
+                "delegate_rc",
+                {"from": "alice", "delegatees": ["bob", "carol"], "max_rc": 5_000_000_000_i64}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_keys_only_replaces_requested_roles() {
+        let public = |seed: &str| {
+            PrivateKey::from_seed(seed)
+                .expect("seed should derive a key")
+                .public_key()
+                .to_string()
+        };
+        let owner_key = PrivateKey::from_seed("alice-owner").expect("seed should derive a key");
+        let owner = Authority::from_key(&owner_key.public_key().to_string());
+        let active = Authority::from_key(&public("alice-active"));
+        let new_posting = Authority::from_key(&public("alice-posting-2"));
+        let memo = public("alice-memo");
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_accounts", [["alice"]]]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": [{
+                    "name": "alice",
+                    "owner": owner,
+                    "active": active,
+                    "posting": Authority::from_key(&public("alice-posting")),
+                    "memo_key": memo
+                }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 42,
+                    "head_block_id": "0000002a11223344556677889900aabbccddeeff00112233445566778899aabb",
+                    "time": "2024-01-01T00:00:00"
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "broadcast_transaction_synchronous"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"id": "abc", "block_num": 42, "trx_num": 0, "expired": false}
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+
+        broadcast
+            .rotate_keys(
+                "alice",
+                KeyRotation {
+                    posting: Some(new_posting.clone()),
+                    ..KeyRotation::default()
+                },
+                &owner_key,
+                false,
+            )
+            .await
+            .expect("rotation should broadcast");
+
+        let requests = server.received_requests().await.expect("requests recorded");
+        let body: Value = requests
+            .iter()
+            .map(|request| serde_json::from_slice::<Value>(&request.body).expect("json body"))
+            .find(|body| body["params"][1] == "broadcast_transaction_synchronous")
+            .expect("broadcast request sent");
+        let op = &body["params"][2][0]["operations"][0];
+        assert_eq!(op[0], "account_update");
+        assert!(op[1]["owner"].is_null());
+        assert_eq!(op[1]["active"], json!(active));
+        assert_eq!(op[1]["posting"], json!(new_posting));
+        assert_eq!(op[1]["memo_key"], memo);
+
+        let lockout = KeyRotation {
+            owner: Some(Authority::from_key(&public("typo"))),
+            ..KeyRotation::default()
+        };
+        let err = broadcast
+            .rotate_keys("alice", lockout.clone(), &owner_key, false)
+            .await
+            .expect_err("lockout should be refused");
+        assert!(err.to_string().contains("new owner authority"), "{err}");
+        broadcast
+            .rotate_keys("alice", lockout, &owner_key, true)
+            .await
+            .expect("forced rotation should broadcast");
+    }
+
+    #[tokio::test]
+    async fn create_transaction_with_props_reuses_snapshot_without_rpc() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+
+        let props = DynamicGlobalProperties {
+            head_block_number: 0x0001_002a,
+            head_block_id: "0001002a11223344556677889900aabbccddeeff00112233445566778899aabb"
+                .to_string(),
+            time: "2024-01-01T00:00:00".to_string(),
+            ..Default::default()
+        };
+
+        let first = broadcast
+            .create_transaction_with_props(vec![], None, &props)
+            .expect("first transaction should build");
+        let second = broadcast
+            .create_transaction_with_props(vec![], Some(Duration::from_secs(120)), &props)
+            .expect("second transaction should build");
+
+        assert_eq!(first.ref_block_num, 0x002a);
+        assert_eq!(first.ref_block_prefix, 0x4433_2211);
+        assert_eq!(first.ref_block_num, second.ref_block_num);
+        assert_eq!(first.ref_block_prefix, second.ref_block_prefix);
+        assert_eq!(first.expiration, "2024-01-01T00:01:00");
+        assert_eq!(second.expiration, "2024-01-01T00:02:00");
+    }
+
+    #[tokio::test]
+    async fn create_transaction_clamps_or_rejects_long_expirations() {
+        let server = MockServer::start().await;
+        let props = DynamicGlobalProperties {
+            head_block_number: 1,
+            head_block_id: "0000000111223344556677889900aabbccddeeff00112233445566778899aabb"
+                .to_string(),
+            time: "2024-01-01T00:00:00".to_string(),
+            ..Default::default()
+        };
+        let broadcast_with = |options: ClientOptions| {
+            let transport = Arc::new(
+                FailoverTransport::new(
+                    &[server.uri()],
+                    Duration::from_secs(2),
+                    1,
+                    BackoffStrategy::default(),
+                )
+                .expect("transport should initialize"),
+            );
+            BroadcastApi::new(Arc::new(ClientInner::new(transport, options)))
+        };
+        let two_hours = Some(Duration::from_secs(2 * 60 * 60));
+
+        let lenient = broadcast_with(ClientOptions::default());
+        let clamped = lenient
+            .create_transaction_with_props(vec![], two_hours, &props)
+            .expect("long expiration should clamp");
+        assert_eq!(clamped.expiration, "2024-01-01T01:00:00");
+        let normal = lenient
+            .create_transaction_with_props(vec![], Some(Duration::from_secs(60)), &props)
+            .expect("normal expiration should pass");
+        assert_eq!(normal.expiration, "2024-01-01T00:01:00");
+
+        let strict = broadcast_with(ClientOptions {
+            strict_expiration: true,
+            ..ClientOptions::default()
+        });
+        assert!(strict
+            .create_transaction_with_props(vec![], two_hours, &props)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn create_transaction_rejects_oversized_transactions() {
+        let server = MockServer::start().await;
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let broadcast = BroadcastApi::new(inner);
+        let props = DynamicGlobalProperties {
+            head_block_number: 1,
+            head_block_id: "0000000111223344556677889900aabbccddeeff00112233445566778899aabb"
+                .to_string(),
+            time: "2024-01-01T00:00:00".to_string(),
+            ..Default::default()
+        };
+        let custom_json = |len: usize| {
+            Operation::CustomJson(CustomJsonOperation {
+                required_auths: vec![],
+                required_posting_auths: vec!["alice".to_string()],
+                id: "app".to_string(),
+                json: format!("\"{}\"", "x".repeat(len)),
+            })
+        };
+
+        let err = broadcast
+            .create_transaction_with_props(vec![custom_json(70_000)], None, &props)
+            .expect_err("oversized transaction should be rejected");
+        assert!(err.to_string().contains("exceeds max size"), "{err}");
+
+        assert!(broadcast
+            .create_transaction_with_props(vec![custom_json(1_000)], None, &props)
+            .is_ok());
+    }
+
+    #[test]
+    fn deterministic_transactions_are_byte_identical() {
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &["http://127.0.0.1:1".to_string()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let broadcast = BroadcastApi::new(Arc::new(ClientInner::new(
+            transport,
+            ClientOptions::default(),
+        )));
+        let expiration = parse_hive_time("2024-01-01T00:01:00").expect("time should parse");
+        let build = || {
+            broadcast
+                .create_transaction_deterministic(
+                    vec![Operation::Transfer(TransferOperation {
+                        from: "alice".to_string(),
+                        to: "bob".to_string(),
+                        amount: Asset::from_string("1.000 HIVE").unwrap(),
+                        memo: "memo".to_string(),
+                    })],
+                    0x1234,
+                    0xdeadbeef,
+                    expiration,
+                )
+                .expect("transaction should build")
+        };
+
+        let (first, second) = (build(), build());
+        assert_eq!(first.expiration, "2024-01-01T00:01:00");
+        assert_eq!(
+            serialize_transaction(&first).unwrap(),
+            serialize_transaction(&second).unwrap()
+        );
+        assert_eq!(
+            generate_trx_id(&first).unwrap(),
+            generate_trx_id(&second).unwrap()
+        );
+    }
 }
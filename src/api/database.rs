@@ -1,19 +1,29 @@
 use std::sync::Arc;
 
+use async_stream::try_stream;
+use futures::Stream;
 use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use serde_json::{json, Value};
 
+use crate::api::transaction_status::is_unknown_transaction_error;
 use crate::client::ClientInner;
-use crate::error::Result;
+use crate::error::{HiveError, Result};
+use crate::transport::is_missing_api_error;
 use crate::types::{
-    AccountHistoryEntry, AccountReputation, ActiveVote, AppliedOperation, BlockHeader,
-    CollateralizedConversionRequest, Comment, Discussion, DiscussionQuery, DiscussionQueryCategory,
-    DynamicGlobalProperties, Escrow, ExpiringVestingDelegation, ExtendedAccount, FeedHistory,
-    FollowCount, FollowEntry, MarketBucket, MarketTrade, OpenOrder, OrderBook, OwnerHistory, Price,
-    Proposal, RecoveryRequest, RecurrentTransfer, RewardFund, SavingsWithdraw, ScheduledHardfork,
+    AccountHistoryEntry, AccountReputation, ActiveVote, AppliedOperation, AppliedTransaction,
+    BlockHeader, ChainProperties, CollateralizedConversionRequest, Comment, ConversionRequest,
+    Discussion, DiscussionQuery, DiscussionQueryCategory, DynamicGlobalProperties, Escrow,
+    ExpiringVestingDelegation, ExtendedAccount, FeedHistory, FollowCount, FollowEntry,
+    MarketBucket, MarketTrade, OpenOrder, OrderBook, OwnerHistory, Price, Proposal,
+    RecoveryRequest, RecurrentTransfer, RewardFund, SavingsWithdraw, ScheduledHardfork,
     SignedBlock, SignedTransaction, Version, VestingDelegation, Witness,
 };
 
+const VESTING_DELEGATION_PAGE_SIZE: u32 = 1000;
+const FOLLOW_PAGE_SIZE: u32 = 1000;
+const FIND_COMMENTS_LIMIT: usize = 1000;
+
 #[derive(Debug, Clone)]
 pub struct DatabaseApi {
     client: Arc<ClientInner>,
@@ -28,6 +38,26 @@ impl DatabaseApi {
         self.client.call("condenser_api", method, params).await
     }
 
+    /// Calls a lookup that may legitimately find nothing. Nodes signal that
+    /// with `null`, an empty object, or a "not found" style RPC error; all of
+    /// those map to `Ok(None)`.
+    async fn call_optional<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>> {
+        let value: Value = match self.call(method, params).await {
+            Ok(value) => value,
+            Err(HiveError::Rpc { message, .. }) if is_not_found_error(&message) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        match value {
+            Value::Null => Ok(None),
+            Value::Object(ref fields) if fields.is_empty() => Ok(None),
+            value => Ok(Some(serde_json::from_value(value)?)),
+        }
+    }
+
     pub async fn get_accounts(&self, accounts: &[&str]) -> Result<Vec<ExtendedAccount>> {
         self.call("get_accounts", json!([accounts])).await
     }
@@ -51,9 +81,12 @@ impl DatabaseApi {
         account_lower_bound: &str,
         limit: u32,
     ) -> Result<Vec<AccountReputation>> {
-        self.call(
+        self.call_with_plugin_fallback(
             "get_account_reputations",
             json!([account_lower_bound, limit]),
+            "reputation_api",
+            json!({ "account_lower_bound": account_lower_bound, "limit": limit }),
+            Some("reputations"),
         )
         .await
     }
@@ -63,13 +96,59 @@ impl DatabaseApi {
     }
 
     pub async fn get_recovery_request(&self, account: &str) -> Result<Option<RecoveryRequest>> {
-        self.call("get_recovery_request", json!([account])).await
+        self.call_optional("get_recovery_request", json!([account]))
+            .await
     }
 
     pub async fn get_content(&self, author: &str, permlink: &str) -> Result<Comment> {
         self.call("get_content", json!([author, permlink])).await
     }
 
+    /// Fetches many posts at once through `database_api.find_comments`, in
+    /// chunks of the node's query limit. Posts that do not exist are left
+    /// out. Nodes without `database_api` are served by concurrent
+    /// `get_content` calls instead.
+    pub async fn find_comments(&self, posts: &[(&str, &str)]) -> Result<Vec<Comment>> {
+        #[derive(Deserialize)]
+        struct Comments {
+            comments: Vec<Comment>,
+        }
+
+        let mut comments = Vec::with_capacity(posts.len());
+        for chunk in posts.chunks(FIND_COMMENTS_LIMIT) {
+            let found: Result<Comments> = self
+                .client
+                .call(
+                    "database_api",
+                    "find_comments",
+                    json!({ "comments": chunk }),
+                )
+                .await;
+            match found {
+                Ok(found) => comments.extend(found.comments),
+                Err(err) if is_missing_api_error(&err) => {
+                    return self.find_comments_with_get_content(posts).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(comments)
+    }
+
+    async fn find_comments_with_get_content(&self, posts: &[(&str, &str)]) -> Result<Vec<Comment>> {
+        let fetched = futures::future::try_join_all(
+            posts
+                .iter()
+                .map(|(author, permlink)| self.get_content(author, permlink)),
+        )
+        .await?;
+        // condenser answers a missing post with an empty comment.
+        Ok(fetched
+            .into_iter()
+            .filter(|comment| !comment.author.is_empty())
+            .collect())
+    }
+
     pub async fn get_content_replies(&self, author: &str, permlink: &str) -> Result<Vec<Comment>> {
         self.call("get_content_replies", json!([author, permlink]))
             .await
@@ -126,6 +205,11 @@ impl DatabaseApi {
         self.call("get_chain_properties", json!([])).await
     }
 
+    /// [`DatabaseApi::get_chain_properties`] as the typed witness median.
+    pub async fn get_chain_properties_typed(&self) -> Result<ChainProperties> {
+        self.call("get_chain_properties", json!([])).await
+    }
+
     pub async fn get_feed_history(&self) -> Result<FeedHistory> {
         self.call("get_feed_history", json!([])).await
     }
@@ -135,6 +219,18 @@ impl DatabaseApi {
             .await
     }
 
+    /// Like [`DatabaseApi::get_current_median_history_price`], but rejects a
+    /// zero or non HBD/HIVE feed and normalizes an inverted one to HBD per HIVE.
+    pub async fn get_current_median_history_price_validated(&self) -> Result<Price> {
+        let price = self.get_current_median_history_price().await?;
+        price.hbd_per_hive().ok_or_else(|| {
+            HiveError::Other(format!(
+                "node returned an invalid median history price: {} / {}",
+                price.base, price.quote
+            ))
+        })
+    }
+
     pub async fn get_hardfork_version(&self) -> Result<String> {
         self.call("get_hardfork_version", json!([])).await
     }
@@ -160,7 +256,8 @@ impl DatabaseApi {
     }
 
     pub async fn get_witness_by_account(&self, account: &str) -> Result<Option<Witness>> {
-        self.call("get_witness_by_account", json!([account])).await
+        self.call_optional("get_witness_by_account", json!([account]))
+            .await
     }
 
     pub async fn get_vesting_delegations(
@@ -173,6 +270,45 @@ impl DatabaseApi {
             .await
     }
 
+    /// Streams every outgoing delegation of `account`, paging by delegatee.
+    pub fn stream_vesting_delegations<'a>(
+        &'a self,
+        account: &'a str,
+    ) -> impl Stream<Item = Result<VestingDelegation>> + 'a {
+        self.stream_vesting_delegations_with_limit(account, VESTING_DELEGATION_PAGE_SIZE)
+    }
+
+    pub fn stream_vesting_delegations_with_limit<'a>(
+        &'a self,
+        account: &'a str,
+        limit: u32,
+    ) -> impl Stream<Item = Result<VestingDelegation>> + 'a {
+        try_stream! {
+            let limit = limit.max(1);
+            let mut from = String::new();
+            loop {
+                let page = self.get_vesting_delegations(account, &from, limit).await?;
+                let full_page = page.len() >= limit as usize;
+                let Some(last) = page.last().map(|delegation| delegation.delegatee.clone()) else {
+                    break;
+                };
+
+                // Each page after the first starts at the previous page's last delegatee.
+                for delegation in page {
+                    if !from.is_empty() && delegation.delegatee == from {
+                        continue;
+                    }
+                    yield delegation;
+                }
+
+                if !full_page || last == from {
+                    break;
+                }
+                from = last;
+            }
+        }
+    }
+
     pub async fn get_expiring_vesting_delegations(
         &self,
         account: &str,
@@ -221,16 +357,81 @@ impl DatabaseApi {
         self.call("get_savings_withdraw_to", json!([account])).await
     }
 
-    pub async fn get_conversion_requests(&self, account: &str) -> Result<Vec<Value>> {
-        self.call("get_conversion_requests", json!([account])).await
+    pub async fn get_conversion_requests(&self, account: &str) -> Result<Vec<ConversionRequest>> {
+        self.call_with_database_fallback(
+            "get_conversion_requests",
+            "find_hbd_conversion_requests",
+            account,
+        )
+        .await
     }
 
     pub async fn get_collateralized_conversion_requests(
         &self,
         account: &str,
     ) -> Result<Vec<CollateralizedConversionRequest>> {
-        self.call("get_collateralized_conversion_requests", json!([account]))
-            .await
+        self.call_with_database_fallback(
+            "get_collateralized_conversion_requests",
+            "find_collateralized_conversion_requests",
+            account,
+        )
+        .await
+    }
+
+    /// Calls a per-account condenser method, falling back to the equivalent
+    /// `database_api` finder on nodes that do not expose it.
+    async fn call_with_database_fallback<T: DeserializeOwned>(
+        &self,
+        condenser_method: &str,
+        database_method: &str,
+        account: &str,
+    ) -> Result<Vec<T>> {
+        #[derive(Deserialize)]
+        struct Requests<T> {
+            requests: Vec<T>,
+        }
+
+        match self.call(condenser_method, json!([account])).await {
+            Ok(requests) => Ok(requests),
+            Err(err) if is_missing_api_error(&err) => {
+                let found: Requests<T> = self
+                    .client
+                    .call(
+                        "database_api",
+                        database_method,
+                        json!({ "account": account }),
+                    )
+                    .await?;
+                Ok(found.requests)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Calls a condenser method, retrying it on the plugin `api` with named
+    /// `plugin_params` when the node does not serve it through condenser. The
+    /// plugin response is unwrapped from `field` when given.
+    async fn call_with_plugin_fallback<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+        api: &str,
+        plugin_params: Value,
+        field: Option<&str>,
+    ) -> Result<T> {
+        match self.call(method, params).await {
+            Ok(result) => Ok(result),
+            Err(err) if is_missing_api_error(&err) => {
+                let mut result: Value = self.client.call(api, method, plugin_params).await?;
+                if let Some(field) = field {
+                    result = result.get_mut(field).map(Value::take).ok_or_else(|| {
+                        HiveError::json(format!("{api}.{method} response is missing '{field}'"))
+                    })?;
+                }
+                serde_json::from_value(result).map_err(Into::into)
+            }
+            Err(err) => Err(err),
+        }
     }
 
     pub async fn get_followers(
@@ -240,11 +441,8 @@ impl DatabaseApi {
         follow_type: &str,
         limit: u32,
     ) -> Result<Vec<FollowEntry>> {
-        self.call(
-            "get_followers",
-            json!([account, start_follower, follow_type, limit]),
-        )
-        .await
+        self.fetch_follows("get_followers", account, start_follower, follow_type, limit)
+            .await
     }
 
     pub async fn get_following(
@@ -254,15 +452,113 @@ impl DatabaseApi {
         follow_type: &str,
         limit: u32,
     ) -> Result<Vec<FollowEntry>> {
-        self.call(
+        self.fetch_follows(
             "get_following",
-            json!([account, start_following, follow_type, limit]),
+            account,
+            start_following,
+            follow_type,
+            limit,
+        )
+        .await
+    }
+
+    /// One page of `get_followers`/`get_following`, from `condenser_api` or,
+    /// on nodes without it, `follow_api`.
+    async fn fetch_follows(
+        &self,
+        method: &str,
+        account: &str,
+        start: &str,
+        follow_type: &str,
+        limit: u32,
+    ) -> Result<Vec<FollowEntry>> {
+        self.call_with_plugin_fallback(
+            method,
+            json!([account, start, follow_type, limit]),
+            "follow_api",
+            json!({ "account": account, "start": start, "type": follow_type, "limit": limit }),
+            Some(method.trim_start_matches("get_")),
         )
         .await
     }
 
+    /// Streams every follower of `account` with the given follow type
+    /// (`"blog"` or `"ignore"`), paging by `start_follower`.
+    pub fn stream_followers<'a>(
+        &'a self,
+        account: &'a str,
+        follow_type: &'a str,
+    ) -> impl Stream<Item = Result<FollowEntry>> + 'a {
+        self.stream_follows(
+            "get_followers",
+            account,
+            follow_type,
+            FOLLOW_PAGE_SIZE,
+            |entry| &entry.follower,
+        )
+    }
+
+    /// Streams every account `account` follows with the given follow type,
+    /// paging by `start_following`.
+    pub fn stream_following<'a>(
+        &'a self,
+        account: &'a str,
+        follow_type: &'a str,
+    ) -> impl Stream<Item = Result<FollowEntry>> + 'a {
+        self.stream_follows(
+            "get_following",
+            account,
+            follow_type,
+            FOLLOW_PAGE_SIZE,
+            |entry| &entry.following,
+        )
+    }
+
+    fn stream_follows<'a>(
+        &'a self,
+        method: &'a str,
+        account: &'a str,
+        follow_type: &'a str,
+        limit: u32,
+        cursor: fn(&FollowEntry) -> &String,
+    ) -> impl Stream<Item = Result<FollowEntry>> + 'a {
+        try_stream! {
+            let limit = limit.max(1);
+            let mut start = String::new();
+            loop {
+                let page = self
+                    .fetch_follows(method, account, &start, follow_type, limit)
+                    .await?;
+                let full_page = page.len() >= limit as usize;
+                let Some(last) = page.last().map(|entry| cursor(entry).clone()) else {
+                    break;
+                };
+
+                // The start cursor is inclusive, so skip the repeated boundary entry.
+                for entry in page {
+                    if !start.is_empty() && *cursor(&entry) == start {
+                        continue;
+                    }
+                    yield entry;
+                }
+
+                if !full_page || last == start {
+                    break;
+                }
+                start = last;
+            }
+        }
+    }
+
     pub async fn get_follow_count(&self, account: &str) -> Result<FollowCount> {
-        self.call("get_follow_count", json!([account])).await
+        self.call_with_plugin_fallback(
+            "get_follow_count",
+            json!([account]),
+            "follow_api",
+            json!({ "account": account }),
+            None,
+        )
+        .await
     }
 
     pub async fn get_reblogged_by(&self, author: &str, permlink: &str) -> Result<Vec<String>> {
@@ -359,13 +655,31 @@ impl DatabaseApi {
         self.get_ops_in_block(block_num, false).await
     }
 
+    /// Looks up an irreversible-or-pending transaction by id. Returns `None`
+    /// when the node does not know the transaction.
+    pub async fn get_transaction(&self, trx_id: &str) -> Result<Option<AppliedTransaction>> {
+        self.call_optional("get_transaction", json!([trx_id])).await
+    }
+
     pub async fn get_block(&self, block_num: u32) -> Result<Option<SignedBlock>> {
-        self.call("get_block", json!([block_num])).await
+        self.call_optional("get_block", json!([block_num])).await
     }
 
     pub async fn get_block_header(&self, block_num: u32) -> Result<Option<BlockHeader>> {
-        self.call("get_block_header", json!([block_num])).await
+        self.call_optional("get_block_header", json!([block_num]))
+            .await
+    }
+}
+
+fn is_not_found_error(message: &str) -> bool {
+    if is_unknown_transaction_error(message) {
+        return true;
     }
+    let message = message.to_ascii_lowercase();
+    message.contains("not found")
+        || message.contains("does not exist")
+        || message.contains("unknown block")
+        || message.contains("unknown key")
 }
 
 #[cfg(test)]
@@ -373,6 +687,7 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration;
 
+    use futures::TryStreamExt;
     use serde_json::json;
     use wiremock::matchers::{body_partial_json, method};
     use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -380,7 +695,7 @@ mod tests {
     use crate::api::DatabaseApi;
     use crate::client::{ClientInner, ClientOptions};
     use crate::transport::{BackoffStrategy, FailoverTransport};
-    use crate::types::{DiscussionQuery, DiscussionQueryCategory};
+    use crate::types::{Asset, ChainProperties, DiscussionQuery, DiscussionQueryCategory};
 
     #[tokio::test]
     async fn get_accounts_calls_condenser_api() {
@@ -415,6 +730,148 @@ mod tests {
         assert_eq!(accounts[0].name, "alice");
     }
 
+    #[tokio::test]
+    async fn find_comments_sends_author_permlink_pairs() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["database_api", "find_comments", {
+                    "comments": [["alice", "hello"], ["bob", "world"]]
+                }]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"comments": [
+                    {"author": "alice", "permlink": "hello", "body": "hi"},
+                    {"author": "bob", "permlink": "world", "body": "there"}
+                ]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let comments = api
+            .find_comments(&[("alice", "hello"), ("bob", "world")])
+            .await
+            .expect("rpc should pass");
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[1].author, "bob");
+        assert_eq!(comments[1].body.as_deref(), Some("there"));
+    }
+
+    #[tokio::test]
+    async fn find_comments_falls_back_to_get_content() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["database_api", "find_comments"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {
+                    "code": -32002,
+                    "message": "Could not find API database_api"
+                }
+            })))
+            .mount(&server)
+            .await;
+        for (author, permlink) in [("alice", "hello"), ("bob", "world")] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["condenser_api", "get_content", [author, permlink]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": {"author": author, "permlink": permlink}
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let comments = api
+            .find_comments(&[("alice", "hello"), ("bob", "world")])
+            .await
+            .expect("fallback should pass");
+        let posts = comments
+            .iter()
+            .map(|comment| (comment.author.as_str(), comment.permlink.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(posts, vec![("alice", "hello"), ("bob", "world")]);
+    }
+
+    #[tokio::test]
+    async fn get_chain_properties_typed_parses_witness_median() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_chain_properties", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "account_creation_fee": "3.000 HIVE",
+                    "maximum_block_size": 65536,
+                    "hbd_interest_rate": "1500"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let props = api
+            .get_chain_properties_typed()
+            .await
+            .expect("chain properties should parse");
+        assert_eq!(
+            props,
+            ChainProperties {
+                account_creation_fee: Asset::hive(3.0),
+                maximum_block_size: 65536,
+                hbd_interest_rate: 1500,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn get_discussions_maps_category_to_method_name() {
         let server = MockServer::start().await;
@@ -450,4 +907,397 @@ mod tests {
             .expect("rpc should pass");
         assert!(posts.is_empty());
     }
+
+    #[tokio::test]
+    async fn stream_vesting_delegations_pages_and_deduplicates() {
+        let server = MockServer::start().await;
+        let delegation = |delegatee: &str| {
+            json!({
+                "id": 1,
+                "delegator": "alice",
+                "delegatee": delegatee,
+                "vesting_shares": "1000.000000 VESTS",
+                "min_delegation_time": "2024-01-01T00:00:00"
+            })
+        };
+        for (from, page) in [
+            ("", vec![delegation("bob"), delegation("carol")]),
+            ("carol", vec![delegation("carol"), delegation("dave")]),
+            ("dave", vec![delegation("dave")]),
+        ] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "method": "call",
+                    "params": ["condenser_api", "get_vesting_delegations", ["alice", from, 2]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": page
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let delegations: Vec<_> = api
+            .stream_vesting_delegations_with_limit("alice", 2)
+            .try_collect()
+            .await
+            .expect("stream should complete");
+        let delegatees: Vec<_> = delegations
+            .iter()
+            .map(|delegation| delegation.delegatee.as_str())
+            .collect();
+        assert_eq!(delegatees, ["bob", "carol", "dave"]);
+    }
+
+    #[tokio::test]
+    async fn stream_follows_pages_by_follower_cursor() {
+        let server = MockServer::start().await;
+        let entry =
+            |follower: &str| json!({"follower": follower, "following": "alice", "what": ["blog"]});
+        for (start, page) in [
+            ("", vec![entry("bob"), entry("carol")]),
+            ("carol", vec![entry("carol")]),
+        ] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "method": "call",
+                    "params": ["condenser_api", "get_followers", ["alice", start, "blog", 2]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": page
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let followers: Vec<_> = api
+            .stream_follows("get_followers", "alice", "blog", 2, |entry| &entry.follower)
+            .try_collect()
+            .await
+            .expect("stream should complete");
+        let names: Vec<_> = followers
+            .iter()
+            .map(|entry| entry.follower.as_str())
+            .collect();
+        assert_eq!(names, ["bob", "carol"]);
+    }
+
+    #[tokio::test]
+    async fn get_transaction_decodes_and_maps_unknown_to_none() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_transaction", ["abc"]]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "ref_block_num": 1,
+                    "ref_block_prefix": 2,
+                    "expiration": "2024-01-01T00:00:00",
+                    "operations": [["vote", {"voter": "alice", "author": "bob", "permlink": "p", "weight": 100}]],
+                    "signatures": [],
+                    "transaction_id": "abc",
+                    "block_num": 99,
+                    "transaction_num": 4
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_transaction", ["missing"]]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {"code": -32003, "message": "Unknown Transaction missing"}
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let found = api
+            .get_transaction("abc")
+            .await
+            .expect("rpc should pass")
+            .expect("transaction should be found");
+        assert_eq!(found.block_num, 99);
+        assert_eq!(found.transaction_num, 4);
+        assert_eq!(found.operations.len(), 1);
+
+        let missing = api
+            .get_transaction("missing")
+            .await
+            .expect("rpc should pass");
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn conversion_requests_fall_back_to_database_api() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_conversion_requests", ["alice"]]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {"code": -32601, "message": "Could not find method get_conversion_requests"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["database_api", "find_hbd_conversion_requests", {"account": "alice"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"requests": [{
+                    "id": 289745,
+                    "owner": "alice",
+                    "requestid": 1234,
+                    "amount": {"amount": "10000", "precision": 3, "nai": "@@000000013"},
+                    "conversion_date": "2024-03-08T00:00:00"
+                }]}
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let requests = api
+            .get_conversion_requests("alice")
+            .await
+            .expect("fallback should succeed");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].requestid, 1234);
+        assert_eq!(requests[0].amount, Asset::hbd(10.0));
+    }
+
+    #[tokio::test]
+    async fn follow_reads_fall_back_to_follow_api() {
+        let server = MockServer::start().await;
+        for method_name in ["get_followers", "get_follow_count"] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["condenser_api", method_name]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "error": {"code": -32601, "message": "Could not find method"}
+                })))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["follow_api", "get_followers", {
+                    "account": "alice", "start": "", "type": "blog", "limit": 10
+                }]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"followers": [
+                    {"follower": "bob", "following": "alice", "what": ["blog"]}
+                ]}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["follow_api", "get_follow_count", {"account": "alice"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"account": "alice", "follower_count": 1, "following_count": 0}
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        let followers = api
+            .get_followers("alice", "", "blog", 10)
+            .await
+            .expect("fallback should succeed");
+        assert_eq!(followers.len(), 1);
+        assert_eq!(followers[0].follower, "bob");
+        let count = api
+            .get_follow_count("alice")
+            .await
+            .expect("fallback should succeed");
+        assert_eq!(count.follower_count, 1);
+    }
+
+    #[tokio::test]
+    async fn validated_median_price_rejects_bad_feeds() {
+        async fn median_price(
+            base: &str,
+            quote: &str,
+        ) -> crate::error::Result<crate::types::Price> {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["condenser_api", "get_current_median_history_price", []]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": {"base": base, "quote": quote}
+                })))
+                .mount(&server)
+                .await;
+            let transport = Arc::new(
+                FailoverTransport::new(
+                    &[server.uri()],
+                    Duration::from_secs(2),
+                    1,
+                    BackoffStrategy::default(),
+                )
+                .expect("transport should initialize"),
+            );
+            let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+            DatabaseApi::new(inner)
+                .get_current_median_history_price_validated()
+                .await
+        }
+
+        let price = median_price("0.250 HBD", "1.000 HIVE")
+            .await
+            .expect("valid feed should pass");
+        assert_eq!(price.base, Asset::hbd(0.25));
+        let inverted = median_price("1.000 HIVE", "0.250 HBD")
+            .await
+            .expect("inverted feed should normalize");
+        assert_eq!(inverted, price);
+
+        assert!(median_price("0.000 HBD", "1.000 HIVE").await.is_err());
+        assert!(median_price("1.000 HIVE", "2.000000 VESTS").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_block_maps_null_and_not_found_to_none() {
+        let server = MockServer::start().await;
+        let respond = |block: u32, body: serde_json::Value| {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "method": "call",
+                    "params": ["condenser_api", "get_block", [block]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        };
+        respond(1, json!({"id": 0, "jsonrpc": "2.0", "result": null}))
+            .mount(&server)
+            .await;
+        respond(2, json!({"id": 0, "jsonrpc": "2.0", "result": {}}))
+            .mount(&server)
+            .await;
+        respond(
+            3,
+            json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {"code": -32000, "message": "Block 3 not found"}
+            }),
+        )
+        .mount(&server)
+        .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_block", [4]]
+            })))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::Fixed { ms: 0 },
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = DatabaseApi::new(inner);
+
+        for block in 1..=3 {
+            let found = api.get_block(block).await.expect("lookup should succeed");
+            assert!(found.is_none(), "block {block}");
+        }
+        assert!(api.get_block(4).await.is_err());
+    }
 }
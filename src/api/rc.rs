@@ -7,7 +7,6 @@ use serde_json::{json, Value};
 
 use crate::client::ClientInner;
 use crate::error::{HiveError, Result};
-use crate::serialization::serialize_transaction;
 use crate::serialization::types::parse_hive_time;
 use crate::types::{
     Authority, DynamicGlobalProperties, ExtendedAccount, ManaResult, Operation, RCAccount, RCParams,
@@ -30,8 +29,6 @@ const KNOWN_RESOURCE_NAMES: [&str; 5] = [
 const RC_SHARE_BASIS_POINTS: i64 = 10_000;
 const RC_REGEN_DIVISOR: i64 = 144_000;
 const DEFAULT_SIGNATURE_COUNT: i64 = 1;
-const SIGNATURE_SIZE_BYTES: i64 = 65;
-const SIGNATURE_VECTOR_OVERHEAD_BYTES: i64 = 1;
 const DEFAULT_EXPIRATION_HOURS: i64 = 1;
 
 #[derive(Debug, Clone)]
@@ -170,6 +167,11 @@ impl RcApi {
         Self::calculate_rc_mana(rc_account)
     }
 
+    /// Whether `account` currently has at least `cost` resource credits.
+    pub async fn can_afford(&self, account: &str, cost: i64) -> Result<bool> {
+        Ok(self.get_rc_mana(account).await?.current >= cost)
+    }
+
     pub async fn get_vp_mana(&self, username: &str) -> Result<ManaResult> {
         let accounts: Vec<ExtendedAccount> = self
             .client
@@ -511,7 +513,8 @@ fn estimate_resource_usage(operations: &[Operation], params: &RCParams) -> Resul
             | Operation::ResetAccount(_)
             | Operation::SetResetAccount(_)
             | Operation::ReportOverProduction(_)
-            | Operation::Virtual { .. } => {}
+            | Operation::Virtual { .. }
+            | Operation::Unknown(_) => {}
         }
     }
 
@@ -541,11 +544,9 @@ fn estimate_signed_transaction_size(operations: &[Operation]) -> Result<i64> {
         extensions: Vec::new(),
     };
 
-    let serialized = serialize_transaction(&tx)?;
-    let tx_size = i64::try_from(serialized.len()).map_err(|_| {
-        HiveError::Other("serialized transaction size exceeds i64 range".to_string())
-    })?;
-    Ok(tx_size + SIGNATURE_VECTOR_OVERHEAD_BYTES + SIGNATURE_SIZE_BYTES * DEFAULT_SIGNATURE_COUNT)
+    let size = tx.estimated_signed_size(DEFAULT_SIGNATURE_COUNT as usize)?;
+    i64::try_from(size)
+        .map_err(|_| HiveError::Other("serialized transaction size exceeds i64 range".to_string()))
 }
 
 fn compute_resource_cost(
@@ -865,6 +866,55 @@ mod tests {
         assert_eq!(pool.resource_pool["resource_history_bytes"].pool, 1);
     }
 
+    #[tokio::test]
+    async fn fallback_regen_reads_nai_encoded_vesting_totals() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": 1,
+                    "head_block_id": "0000000000000000000000000000000000000000",
+                    "time": "2024-01-01T00:00:00",
+                    "total_vesting_shares": {
+                        "amount": "310000000000000000",
+                        "precision": 6,
+                        "nai": "@@000000037"
+                    },
+                    "total_vesting_fund_hive": {
+                        "amount": 180000000000_i64,
+                        "precision": 3,
+                        "nai": "@@000000021"
+                    },
+                    "current_supply": "400000000.000 HIVE"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let transport = Arc::new(
+            FailoverTransport::new(
+                &[server.uri()],
+                Duration::from_secs(2),
+                1,
+                BackoffStrategy::default(),
+            )
+            .expect("transport should initialize"),
+        );
+        let inner = Arc::new(ClientInner::new(transport, ClientOptions::default()));
+        let api = RcApi::new(inner);
+
+        let regen = api
+            .get_fallback_regen()
+            .await
+            .expect("regen should compute from NAI totals");
+        assert_eq!(regen, 310_000_000_000_000_000 / super::RC_REGEN_DIVISOR);
+    }
+
     #[tokio::test]
     async fn calculate_cost_uses_live_formula_with_stats_share() {
         let server = MockServer::start().await;
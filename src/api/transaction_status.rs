@@ -4,6 +4,7 @@ use serde_json::json;
 
 use crate::client::ClientInner;
 use crate::error::{HiveError, Result};
+use crate::transport::is_missing_api_error;
 use crate::types::TransactionStatus;
 
 #[derive(Debug, Clone)]
@@ -27,7 +28,7 @@ impl TransactionStatusApi {
             .await
         {
             Ok(status) => Ok(status),
-            Err(err) if should_fallback_to_condenser(&err) => {
+            Err(err) if is_missing_api_error(&err) => {
                 self.find_transaction_with_condenser(transaction_id).await
             }
             Err(err) => Err(err),
@@ -43,12 +44,17 @@ impl TransactionStatusApi {
             .call::<serde_json::Value>("condenser_api", "get_transaction", json!([transaction_id]))
             .await
         {
-            Ok(_) => Ok(TransactionStatus {
+            Ok(transaction) => Ok(TransactionStatus {
                 status: "found_in_block".to_string(),
+                block_num: transaction["block_num"]
+                    .as_u64()
+                    .and_then(|block_num| u32::try_from(block_num).ok()),
+                expiration: transaction["expiration"].as_str().map(str::to_string),
             }),
             Err(HiveError::Rpc { message, .. }) if is_unknown_transaction_error(&message) => {
                 Ok(TransactionStatus {
                     status: "unknown".to_string(),
+                    ..TransactionStatus::default()
                 })
             }
             Err(err) => Err(err),
@@ -56,16 +62,7 @@ impl TransactionStatusApi {
     }
 }
 
-fn should_fallback_to_condenser(error: &HiveError) -> bool {
-    let HiveError::Rpc { message, .. } = error else {
-        return false;
-    };
-
-    let message = message.to_ascii_lowercase();
-    message.contains("could not find method") || message.contains("could not find api")
-}
-
-fn is_unknown_transaction_error(message: &str) -> bool {
+pub(crate) fn is_unknown_transaction_error(message: &str) -> bool {
     let message = message.to_ascii_lowercase();
     message.contains("unknown transaction")
         || message.contains("unable to find transaction")
@@ -118,6 +115,7 @@ mod tests {
             .await
             .expect("rpc should succeed");
         assert_eq!(response.status, "within_mempool");
+        assert_eq!(response.block_num, None);
     }
 
     #[tokio::test]
@@ -149,6 +147,7 @@ mod tests {
                 "jsonrpc": "2.0",
                 "result": {
                     "transaction_id": "deadbeef",
+                    "expiration": "2024-01-01T00:01:00",
                     "block_num": 99
                 }
             })))
@@ -172,6 +171,8 @@ mod tests {
             .await
             .expect("fallback should succeed");
         assert_eq!(response.status, "found_in_block");
+        assert_eq!(response.block_num, Some(99));
+        assert_eq!(response.expiration.as_deref(), Some("2024-01-01T00:01:00"));
     }
 
     #[tokio::test]
@@ -226,5 +227,6 @@ mod tests {
             .await
             .expect("fallback should return unknown status");
         assert_eq!(response.status, "unknown");
+        assert_eq!(response.block_num, None);
     }
 }
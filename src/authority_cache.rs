@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::crypto::KeyRole;
+use crate::types::{Authority, ExtendedAccount};
+
+/// Account authorities keyed by account and role, each entry valid for a
+/// fixed TTL. Shared by every clone of a [`Client`](crate::Client); call
+/// [`invalidate`](Self::invalidate) after broadcasting an `account_update` so
+/// the next check sees the new keys.
+///
+/// The memo key is cached under [`KeyRole::Memo`] as a threshold-1 authority.
+#[derive(Debug)]
+pub struct AuthorityCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, KeyRole), (Instant, Authority)>>,
+}
+
+impl AuthorityCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The cached authority, or `None` if it is missing or older than the TTL.
+    pub fn get(&self, account: &str, role: KeyRole) -> Option<Authority> {
+        let entries = self.lock();
+        entries
+            .get(&(account.to_string(), role))
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, authority)| authority.clone())
+    }
+
+    pub fn insert(&self, account: &str, role: KeyRole, authority: Authority) {
+        self.lock()
+            .insert((account.to_string(), role), (Instant::now(), authority));
+    }
+
+    /// Caches every role (and the memo key) present on `account`.
+    pub fn insert_account(&self, account: &ExtendedAccount) {
+        for role in [KeyRole::Owner, KeyRole::Active, KeyRole::Posting] {
+            if let Some(authority) = account.authority(role) {
+                self.insert(&account.name, role, authority.clone());
+            }
+        }
+        if let Some(memo_key) = &account.memo_key {
+            self.insert(&account.name, KeyRole::Memo, Authority::from_key(memo_key));
+        }
+    }
+
+    /// Drops every role cached for `account`.
+    pub fn invalidate(&self, account: &str) {
+        self.lock().retain(|(name, _), _| name != account);
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(String, KeyRole), (Instant, Authority)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,112 @@
+use std::future::Future;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::{Client, ClientOptions};
+use crate::crypto::PrivateKey;
+use crate::error::{HiveError, Result};
+use crate::types::{DynamicGlobalProperties, ExtendedAccount, Operation, TransactionConfirmation};
+
+/// A synchronous facade over [`Client`].
+///
+/// Every call is driven to completion on a single current-thread runtime owned by
+/// the facade, so it must not be used from inside another async runtime.
+#[derive(Debug)]
+pub struct BlockingClient {
+    client: Client,
+    runtime: Runtime,
+}
+
+impl BlockingClient {
+    pub fn new(nodes: Vec<&str>, options: ClientOptions) -> Result<Self> {
+        Self::from_client(Client::new(nodes, options))
+    }
+
+    pub fn new_default() -> Result<Self> {
+        Self::from_client(Client::new_default())
+    }
+
+    pub fn from_client(client: Client) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| HiveError::Other(format!("failed to build runtime: {err}")))?;
+        Ok(Self { client, runtime })
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn call<T: DeserializeOwned>(&self, api: &str, method: &str, params: Value) -> Result<T> {
+        self.block_on(self.client.call(api, method, params))
+    }
+
+    pub fn get_accounts(&self, accounts: &[&str]) -> Result<Vec<ExtendedAccount>> {
+        self.block_on(self.client.database.get_accounts(accounts))
+    }
+
+    pub fn get_account_count(&self) -> Result<u64> {
+        self.block_on(self.client.database.get_account_count())
+    }
+
+    pub fn get_dynamic_global_properties(&self) -> Result<DynamicGlobalProperties> {
+        self.block_on(self.client.database.get_dynamic_global_properties())
+    }
+
+    pub fn send_operations(
+        &self,
+        operations: Vec<Operation>,
+        key: &PrivateKey,
+    ) -> Result<TransactionConfirmation> {
+        self.block_on(self.client.broadcast.send_operations(operations, key))
+    }
+
+    pub fn calculate_cost(&self, operations: &[Operation]) -> Result<i64> {
+        self.block_on(self.client.rc.calculate_cost(operations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::blocking::BlockingClient;
+    use crate::client::ClientOptions;
+
+    #[test]
+    fn get_account_count_runs_without_await() {
+        let server_runtime = tokio::runtime::Runtime::new().expect("runtime should build");
+        let server = server_runtime.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "method": "call",
+                    "params": ["condenser_api", "get_account_count", []]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": 4242
+                })))
+                .mount(&server)
+                .await;
+            server
+        });
+
+        let client = BlockingClient::new(vec![&server.uri()], ClientOptions::default())
+            .expect("blocking client should build");
+        let count = client
+            .get_account_count()
+            .expect("blocking call should succeed");
+        assert_eq!(count, 4242);
+    }
+}
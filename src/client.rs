@@ -1,24 +1,158 @@
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::Utc;
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::api::{
     AccountByKeyApi, Blockchain, BroadcastApi, DatabaseApi, HivemindApi, RcApi,
     TransactionStatusApi,
 };
-use crate::error::Result;
-use crate::transport::{BackoffStrategy, FailoverTransport};
-use crate::types::ChainId;
+use crate::authority_cache::AuthorityCache;
+use crate::crypto::verify::{authority_satisfied, recover_signers};
+use crate::crypto::{memo, KeyRole, PrivateKey, PublicKey};
+use crate::error::{HiveError, Result};
+use crate::serialization::transaction_digest;
+use crate::transport::{
+    BackoffStrategy, FailoverTransport, JitterPolicy, NodeProbe, NodeSyncStatus, RpcEnvelope,
+    Transport, DEFAULT_RETRYABLE_RPC_ERRORS,
+};
+use crate::types::{
+    AccountCreateOperation, Asset, Authority, ChainId, ClaimAccountOperation,
+    DynamicGlobalProperties, Network, Operation, RecoverAccountOperation, RequiredAuthorities,
+    SignedTransaction, TransactionConfirmation, Version,
+};
+use crate::utils::{estimate_payout, parse_hardfork_version, vote_rshares};
+
+/// The furthest in the future hived accepts a transaction expiration
+/// (`HIVE_MAX_TIME_UNTIL_EXPIRATION`).
+pub const MAX_TRANSACTION_EXPIRATION: Duration = Duration::from_secs(60 * 60);
+
+/// Largest serialized transaction hived accepts (`HIVE_MAX_TRANSACTION_SIZE`).
+pub const MAX_TRANSACTION_SIZE: usize = 64 * 1024;
+
+/// Aggregated pre-broadcast checks from [`Client::preflight`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preflight {
+    /// Whether the node accepts the transaction's current signatures.
+    pub authority_ok: bool,
+    /// Keys the node says are required that have not signed yet, or `None`
+    /// when `available_keys` cannot satisfy the required authorities.
+    pub missing_keys: Option<Vec<String>>,
+    pub rc_cost: i64,
+    /// Whether the paying account (the first account whose authority the
+    /// operations require) has enough RC for `rc_cost`.
+    pub rc_affordable: bool,
+}
+
+/// Result of [`Client::ping`]: the chain head as seen by the answering node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ping {
+    pub head_block_number: u32,
+    pub time: String,
+    pub node: String,
+}
+
+/// How deeply a block is buried, from [`Client::confirmation_depth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationDepth {
+    pub block_num: u32,
+    pub head: u32,
+    pub last_irreversible: u32,
+    /// Blocks produced on top of `block_num`; 0 while it is the head block.
+    pub confirmations: u32,
+    pub is_irreversible: bool,
+}
+
+impl ConfirmationDepth {
+    fn new(block_num: u32, head: u32, last_irreversible: u32) -> Self {
+        Self {
+            block_num,
+            head,
+            last_irreversible,
+            confirmations: head.saturating_sub(block_num),
+            is_irreversible: block_num <= last_irreversible,
+        }
+    }
+}
+
+/// How often [`Client::wait_for_irreversible`] rechecks the chain.
+const IRREVERSIBLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Polling settings for [`Client::await_confirmation`] and
+/// [`BroadcastApi::send_when_affordable`](crate::api::BroadcastApi::send_when_affordable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitOptions {
+    pub interval: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ClientOptions {
     pub timeout: Duration,
     pub failover_threshold: u32,
     pub address_prefix: String,
+    /// Signs for this chain. The testnet and mirrornet chain ids also switch
+    /// HIVE and HBD to their testnet wire symbols (`TESTS`/`TBD`).
     pub chain_id: ChainId,
     pub backoff: BackoffStrategy,
+    /// Randomization applied to each backoff delay; `JitterPolicy::None`
+    /// makes the schedule deterministic.
+    pub jitter: JitterPolicy,
+    /// RPC error message fragments that are retried on the next node.
+    pub retryable_rpc_errors: Vec<String>,
+    /// How many recently broadcast transaction ids to remember so an identical
+    /// re-send returns the earlier confirmation. `0` disables the guard.
+    pub broadcast_dedup_capacity: usize,
+    /// Longest expiration `create_transaction` will set; longer requests are
+    /// clamped to it.
+    pub max_expiration: Duration,
+    /// Return an error instead of clamping when a requested expiration exceeds
+    /// `max_expiration`.
+    pub strict_expiration: bool,
+    /// Serialized size above which `create_transaction` fails locally instead
+    /// of letting the node reject the transaction.
+    pub max_transaction_size: usize,
+    /// Method name to API namespace, consulted before every call so a method
+    /// can be served from e.g. `database_api` or a proxy's own namespace. The
+    /// params are sent unchanged, so the target must accept the same shape.
+    pub api_overrides: HashMap<String, String>,
+    /// How long [`Client::authority_cache`] entries stay fresh.
+    pub authority_cache_ttl: Duration,
+}
+
+impl ClientOptions {
+    /// Default options with the chain id and key prefix of `network`.
+    pub fn for_network(network: Network) -> Self {
+        Self {
+            chain_id: network.chain_id(),
+            address_prefix: network.address_prefix().to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Default options for the public testnet: its chain id, `TST` keys and
+    /// `TESTS`/`TBD` asset symbols.
+    pub fn testnet() -> Self {
+        Self::for_network(Network::Testnet)
+    }
+
+    /// Default options for the mirrornet: its chain id, `TST` keys and
+    /// `TESTS`/`TBD` asset symbols.
+    pub fn mirrornet() -> Self {
+        Self::for_network(Network::Mirrornet)
+    }
 }
 
 impl Default for ClientOptions {
@@ -35,19 +169,36 @@ impl Default for ClientOptions {
             address_prefix: "STM".to_string(),
             chain_id,
             backoff: BackoffStrategy::default(),
+            jitter: JitterPolicy::default(),
+            retryable_rpc_errors: DEFAULT_RETRYABLE_RPC_ERRORS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            broadcast_dedup_capacity: 0,
+            max_expiration: MAX_TRANSACTION_EXPIRATION,
+            strict_expiration: false,
+            max_transaction_size: MAX_TRANSACTION_SIZE,
+            api_overrides: HashMap::new(),
+            authority_cache_ttl: Duration::from_secs(60),
         }
     }
 }
 
 #[derive(Debug)]
 pub(crate) struct ClientInner {
-    transport: Arc<FailoverTransport>,
+    transport: Arc<dyn Transport>,
     options: ClientOptions,
+    authority_cache: AuthorityCache,
 }
 
 impl ClientInner {
-    pub(crate) fn new(transport: Arc<FailoverTransport>, options: ClientOptions) -> Self {
-        Self { transport, options }
+    pub(crate) fn new(transport: Arc<dyn Transport>, options: ClientOptions) -> Self {
+        let authority_cache = AuthorityCache::new(options.authority_cache_ttl);
+        Self {
+            transport,
+            options,
+            authority_cache,
+        }
     }
 
     pub(crate) async fn call<T: DeserializeOwned>(
@@ -56,14 +207,79 @@ impl ClientInner {
         method: &str,
         params: Value,
     ) -> Result<T> {
-        self.transport.call(api, method, params).await
+        let api = self.resolve_api(api, method);
+        let value = self.transport.call(api, method, params, None).await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub(crate) async fn call_with_timeout<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<T> {
+        let api = self.resolve_api(api, method);
+        let value = self
+            .transport
+            .call(api, method, params, Some(timeout))
+            .await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub(crate) async fn call_envelope<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<RpcEnvelope<T>> {
+        let api = self.resolve_api(api, method);
+        let envelope = self.transport.call_envelope(api, method, params).await?;
+        Ok(RpcEnvelope {
+            id: envelope.id,
+            jsonrpc: envelope.jsonrpc,
+            result: envelope.result.map(serde_json::from_value).transpose()?,
+            error: envelope.error,
+        })
+    }
+
+    /// Like [`ClientInner::call`], also returning the node that answered.
+    pub(crate) async fn call_with_node<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<(T, Option<String>)> {
+        let api = self.resolve_api(api, method);
+        let (value, node) = self.transport.call_with_node(api, method, params).await?;
+        Ok((serde_json::from_value(value)?, node))
+    }
+
+    fn resolve_api<'a>(&'a self, api: &'a str, method: &str) -> &'a str {
+        self.options
+            .api_overrides
+            .get(method)
+            .map_or(api, String::as_str)
     }
 
     pub(crate) fn options(&self) -> &ClientOptions {
         &self.options
     }
+
+    pub(crate) fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    pub(crate) fn authority_cache(&self) -> &AuthorityCache {
+        &self.authority_cache
+    }
 }
 
+/// Entry point to a set of Hive nodes.
+///
+/// Cloning is cheap: every clone, and every API handle on it, shares the same
+/// transport, failover state and options behind an `Arc`. Clone a `Client`
+/// into each task rather than building a new one.
 #[derive(Debug, Clone)]
 pub struct Client {
     inner: Arc<ClientInner>,
@@ -89,11 +305,22 @@ impl Client {
                 options.failover_threshold,
                 options.backoff.clone(),
             )
-            .expect("failed to initialize transport"),
+            .expect("failed to initialize transport")
+            .with_retryable_rpc_errors(options.retryable_rpc_errors.clone())
+            .with_jitter(options.jitter),
         );
 
-        let inner = Arc::new(ClientInner::new(transport, options));
+        Self::from_inner(Arc::new(ClientInner::new(transport, options)))
+    }
+
+    /// A client that sends every call through `transport`, e.g. a custom
+    /// provider or, with the `test-util` feature, a
+    /// [`MockTransport`](crate::transport::MockTransport).
+    pub fn from_transport(transport: Arc<dyn Transport>, options: ClientOptions) -> Self {
+        Self::from_inner(Arc::new(ClientInner::new(transport, options)))
+    }
 
+    fn from_inner(inner: Arc<ClientInner>) -> Self {
         Self {
             database: DatabaseApi::new(inner.clone()),
             broadcast: BroadcastApi::new(inner.clone()),
@@ -108,30 +335,38 @@ impl Client {
 
     pub fn new_default() -> Self {
         Self::new(
-            vec!["https://api.hive.blog", "https://api.openhive.network"],
+            Network::Mainnet.default_nodes().to_vec(),
             ClientOptions::default(),
         )
     }
 
     pub fn testnet(nodes: Vec<&str>) -> Self {
-        Self::new(
-            nodes,
-            ClientOptions {
-                chain_id: ChainId::testnet(),
-                address_prefix: "TST".to_string(),
-                ..ClientOptions::default()
-            },
-        )
+        Self::new(nodes, ClientOptions::testnet())
     }
 
     pub fn testnet_default() -> Self {
-        Self::testnet(vec!["https://testnet.openhive.network"])
+        Self::testnet(Network::Testnet.default_nodes().to_vec())
+    }
+
+    pub fn mirrornet(nodes: Vec<&str>) -> Self {
+        Self::new(nodes, ClientOptions::mirrornet())
+    }
+
+    pub fn mirrornet_default() -> Self {
+        Self::mirrornet(Network::Mirrornet.default_nodes().to_vec())
     }
 
     pub fn options(&self) -> &ClientOptions {
         self.inner.options()
     }
 
+    /// The public key of `key`, rendered with this client's address prefix.
+    pub fn public_key(&self, key: &PrivateKey) -> PublicKey {
+        let mut public_key = key.public_key();
+        public_key.prefix = self.options().address_prefix.clone();
+        public_key
+    }
+
     pub async fn call<T: DeserializeOwned>(
         &self,
         api: &str,
@@ -140,15 +375,555 @@ impl Client {
     ) -> Result<T> {
         self.inner.call(api, method, params).await
     }
+
+    /// Like [`Client::call`], but waits up to `timeout` for each node instead
+    /// of [`ClientOptions::timeout`], e.g. for a heavy history or block range
+    /// query.
+    pub async fn call_with_timeout<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<T> {
+        self.inner
+            .call_with_timeout(api, method, params, timeout)
+            .await
+    }
+
+    /// Like [`Client::call`], but returns the full JSON-RPC response (id,
+    /// version, and the result or error) for inspection.
+    pub async fn call_envelope<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<RpcEnvelope<T>> {
+        self.inner.call_envelope(api, method, params).await
+    }
+
+    /// URL of the node that served the most recent successful call, if any.
+    pub fn last_node(&self) -> Option<String> {
+        self.inner.transport().last_node()
+    }
+
+    /// Cheap liveness check: fetches the head block and chain time, and
+    /// reports which node answered.
+    pub async fn ping(&self) -> Result<Ping> {
+        let (props, node) = self
+            .inner
+            .call_with_node::<DynamicGlobalProperties>(
+                "condenser_api",
+                "get_dynamic_global_properties",
+                json!([]),
+            )
+            .await?;
+        Ok(Ping {
+            head_block_number: props.head_block_number,
+            time: props.time,
+            node: node.unwrap_or_default(),
+        })
+    }
+
+    /// The answering node's software version.
+    pub async fn node_version(&self) -> Result<Version> {
+        self.database.get_version().await
+    }
+
+    /// Estimated HBD value of a vote by `voter` at `weight_percent` (-100 to
+    /// 100) right now, from their voting mana, the `post` reward fund and the
+    /// median price feed.
+    pub async fn vote_value(&self, voter: &str, weight_percent: f64) -> Result<Asset> {
+        let account = self
+            .database
+            .get_accounts(&[voter])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| HiveError::Other(format!("account '{voter}' not found")))?;
+        let mana = RcApi::calculate_vp_mana(&account)?;
+        let fund = self.database.get_reward_fund("post").await?;
+        let price = self
+            .database
+            .get_current_median_history_price_validated()
+            .await?;
+
+        let weight = (weight_percent * 100.0).round().clamp(-10_000.0, 10_000.0) as i16;
+        estimate_payout(vote_rshares(mana.current, weight), &fund, &price)
+    }
+
+    /// Authorities cached for [`Client::account_authority`],
+    /// [`Client::effective_keys`] and [`Client::verify_signatures`]. Shared by
+    /// every clone; invalidate an account after changing its keys.
+    pub fn authority_cache(&self) -> &AuthorityCache {
+        self.inner.authority_cache()
+    }
+
+    /// `account`'s authority for `role`, from the cache while fresh and
+    /// otherwise refetched with `get_accounts`.
+    pub async fn account_authority(
+        &self,
+        account: &str,
+        role: KeyRole,
+    ) -> Result<Option<Authority>> {
+        self.load_authorities(&[account]).await?;
+        Ok(self.authority_cache().get(account, role))
+    }
+
+    /// Cached counterpart of [`ExtendedAccount::effective_keys`](crate::types::ExtendedAccount::effective_keys):
+    /// referenced accounts are fetched (and cached) as needed.
+    pub async fn effective_keys(&self, account: &str, role: KeyRole) -> Result<Vec<(String, u16)>> {
+        let Some(authority) = self.account_authority(account, role).await? else {
+            return Ok(Vec::new());
+        };
+        let referenced = authority
+            .account_auths
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        self.load_authorities(&referenced).await?;
+        let cache = self.authority_cache();
+        Ok(authority.weighted_keys(|name| cache.get(name, role)))
+    }
+
+    /// Checks locally, against cached authorities, whether the signatures on
+    /// `signed` satisfy every authority its operations require. As on the
+    /// node, a higher role may stand in for a lower one and account
+    /// references are followed one level.
+    pub async fn verify_signatures(&self, signed: &SignedTransaction) -> Result<bool> {
+        let digest = transaction_digest(&signed.unsigned(), &self.options().chain_id)?;
+        let Some(signers) = recover_signers(&signed.signatures, &digest) else {
+            return Ok(false);
+        };
+
+        let required = RequiredAuthorities::for_operations(&signed.operations);
+        let requirements: [(&[String], &[KeyRole]); 3] = [
+            (&required.owner, &[KeyRole::Owner]),
+            (&required.active, &[KeyRole::Active, KeyRole::Owner]),
+            (
+                &required.posting,
+                &[KeyRole::Posting, KeyRole::Active, KeyRole::Owner],
+            ),
+        ];
+        let accounts = requirements
+            .iter()
+            .flat_map(|(accounts, _)| accounts.iter().map(String::as_str))
+            .collect::<Vec<_>>();
+        self.load_authorities(&accounts).await?;
+
+        let cache = self.authority_cache();
+        let mut referenced = Vec::new();
+        for account in &accounts {
+            for role in [KeyRole::Owner, KeyRole::Active, KeyRole::Posting] {
+                if let Some(authority) = cache.get(account, role) {
+                    referenced.extend(authority.account_auths.into_iter().map(|(name, _)| name));
+                }
+            }
+        }
+        for authority in &required.other {
+            referenced.extend(authority.account_auths.iter().map(|(name, _)| name.clone()));
+        }
+        let referenced = referenced.iter().map(String::as_str).collect::<Vec<_>>();
+        self.load_authorities(&referenced).await?;
+
+        let satisfied = |account: &str, role: KeyRole| {
+            cache.get(account, role).is_some_and(|authority| {
+                authority_satisfied(&authority, &signers, &|name: &str| cache.get(name, role), 1)
+            })
+        };
+        let accounts_satisfied = requirements.iter().all(|(accounts, roles)| {
+            accounts
+                .iter()
+                .all(|account| roles.iter().any(|role| satisfied(account, *role)))
+        });
+        let others_satisfied = required.other.iter().all(|authority| {
+            authority_satisfied(
+                authority,
+                &signers,
+                &|name: &str| cache.get(name, KeyRole::Active),
+                1,
+            )
+        });
+        Ok(accounts_satisfied && others_satisfied)
+    }
+
+    /// Fetches, in one `get_accounts` call, every account in `accounts` that
+    /// has no fresh cache entry.
+    async fn load_authorities(&self, accounts: &[&str]) -> Result<()> {
+        let cache = self.authority_cache();
+        let mut stale = accounts
+            .iter()
+            .copied()
+            .filter(|account| cache.get(account, KeyRole::Owner).is_none())
+            .collect::<Vec<_>>();
+        stale.sort_unstable();
+        stale.dedup();
+        if stale.is_empty() {
+            return Ok(());
+        }
+        for account in self.database.get_accounts(&stale).await? {
+            cache.insert_account(&account);
+        }
+        Ok(())
+    }
+
+    /// Offline counterpart of `get_potential_signatures`: every key that could
+    /// contribute a signature to `transaction`, sorted. As on the node, a
+    /// posting requirement also admits active and owner keys and an active
+    /// requirement admits owner keys. `authorities` supplies an account's
+    /// authority for a role and is followed one level into `account_auths`.
+    pub fn potential_signatures_local(
+        transaction: &SignedTransaction,
+        authorities: impl Fn(&str, KeyRole) -> Option<Authority>,
+    ) -> Vec<String> {
+        let required = RequiredAuthorities::for_operations(&transaction.operations);
+        let mut keys = BTreeSet::new();
+        let mut collect = |authority: &Authority, role: KeyRole| {
+            keys.extend(authority.key_auths.iter().map(|(key, _)| key.clone()));
+            for (account, _) in &authority.account_auths {
+                if let Some(nested) = authorities(account, role) {
+                    keys.extend(nested.key_auths.into_iter().map(|(key, _)| key));
+                }
+            }
+        };
+
+        let requirements: [(&[String], &[KeyRole]); 3] = [
+            (&required.owner, &[KeyRole::Owner]),
+            (&required.active, &[KeyRole::Active, KeyRole::Owner]),
+            (
+                &required.posting,
+                &[KeyRole::Posting, KeyRole::Active, KeyRole::Owner],
+            ),
+        ];
+        for (accounts, roles) in requirements {
+            for account in accounts {
+                for role in roles {
+                    if let Some(authority) = authorities(account, *role) {
+                        collect(&authority, *role);
+                    }
+                }
+            }
+        }
+        for authority in &required.other {
+            collect(authority, KeyRole::Active);
+        }
+
+        keys.into_iter().collect()
+    }
+
+    /// Checks whether `signed` is likely to be accepted: whether its
+    /// signatures satisfy the required authorities, which of
+    /// `available_keys` still need to sign, and whether the payer can afford
+    /// the RC cost.
+    pub async fn preflight(
+        &self,
+        signed: &SignedTransaction,
+        available_keys: &[String],
+    ) -> Result<Preflight> {
+        // A missing-authority assertion is an answer here, not a failure of
+        // the preflight itself; any other error is.
+        let authority_ok = match self.database.verify_authority(signed).await {
+            Ok(ok) => ok,
+            Err(err) if is_missing_authority_error(&err) => false,
+            Err(err) => return Err(err),
+        };
+        let required = match self
+            .database
+            .get_required_signatures(signed, available_keys)
+            .await
+        {
+            Ok(required) => Some(required),
+            Err(err) if is_missing_authority_error(&err) => None,
+            Err(err) => return Err(err),
+        };
+
+        let digest = transaction_digest(&signed.unsigned(), &self.options().chain_id)?;
+        let signers = recover_signers(&signed.signatures, &digest).unwrap_or_default();
+        let missing_keys = required.map(|required| {
+            required
+                .into_iter()
+                .filter(|key| {
+                    // Compare the curve points so address prefixes don't matter.
+                    PublicKey::from_string(key)
+                        .map(|key| !signers.iter().any(|signer| signer.key == key.key))
+                        .unwrap_or(true)
+                })
+                .collect()
+        });
+
+        let rc_cost = self.rc.calculate_cost(&signed.operations).await?;
+        let authorities = RequiredAuthorities::for_operations(&signed.operations);
+        let payer = authorities
+            .active
+            .first()
+            .or_else(|| authorities.owner.first())
+            .or_else(|| authorities.posting.first());
+        let rc_affordable = match payer {
+            Some(payer) => self.rc.can_afford(payer, rc_cost).await?,
+            None => true,
+        };
+
+        Ok(Preflight {
+            authority_ok,
+            missing_keys,
+            rc_cost,
+            rc_affordable,
+        })
+    }
+
+    /// Builds the user's `recover_account` from the recovery partner's
+    /// outstanding request for `account`, which supplies
+    /// `new_owner_authority`. Fails if there is no request or it has expired.
+    pub async fn prepare_recover_account(
+        &self,
+        account: &str,
+        recent_owner_authority: Authority,
+    ) -> Result<RecoverAccountOperation> {
+        let request = self
+            .database
+            .get_recovery_request(account)
+            .await?
+            .ok_or_else(|| {
+                HiveError::Other(format!("no outstanding recovery request for '{account}'"))
+            })?;
+        if request.is_expired(Utc::now())? {
+            return Err(HiveError::Other(format!(
+                "recovery request for '{account}' expired at {}",
+                request.expires
+            )));
+        }
+        Ok(RecoverAccountOperation::from_request(
+            &request,
+            recent_owner_authority,
+        ))
+    }
+
+    /// Completes a recovery: builds the op with
+    /// [`Client::prepare_recover_account`] and broadcasts it signed with
+    /// `keys`, which must cover both the new and the recent owner authority.
+    pub async fn recover_account_flow(
+        &self,
+        account: &str,
+        recent_owner_authority: Authority,
+        keys: &[&PrivateKey],
+    ) -> Result<TransactionConfirmation> {
+        let operation = self
+            .prepare_recover_account(account, recent_owner_authority)
+            .await?;
+        let transaction = self
+            .broadcast
+            .create_transaction(vec![Operation::RecoverAccount(operation)], None)
+            .await?;
+        let signed = self.broadcast.sign_transaction(&transaction, keys)?;
+        let confirmation = self.broadcast.send(signed).await?;
+        self.authority_cache().invalidate(account);
+        Ok(confirmation)
+    }
+
+    /// Where `block_num` stands relative to the current head and last
+    /// irreversible block.
+    pub async fn confirmation_depth(&self, block_num: u32) -> Result<ConfirmationDepth> {
+        let props = self.database.get_dynamic_global_properties().await?;
+        Ok(ConfirmationDepth::new(
+            block_num,
+            props.head_block_number,
+            props.last_irreversible_block_num,
+        ))
+    }
+
+    /// Polls until `block_num` is irreversible, failing with
+    /// [`HiveError::Timeout`] if that takes longer than `timeout`.
+    pub async fn wait_for_irreversible(
+        &self,
+        block_num: u32,
+        timeout: Duration,
+    ) -> Result<ConfirmationDepth> {
+        let poll = async {
+            loop {
+                let depth = self.confirmation_depth(block_num).await?;
+                if depth.is_irreversible {
+                    return Ok(depth);
+                }
+                tokio::time::sleep(IRREVERSIBLE_POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(timeout, poll)
+            .await
+            .unwrap_or(Err(HiveError::Timeout))
+    }
+
+    /// Polls `find_transaction` until `trx_id` lands in a block, returning
+    /// its block number. A transaction that expired out of the mempool
+    /// resolves with `expired` set and `block_num` 0; [`HiveError::Timeout`]
+    /// if neither happens within `options.timeout`. `trx_num` is not reported
+    /// by the status API and is left at 0.
+    pub async fn await_confirmation(
+        &self,
+        trx_id: &str,
+        options: WaitOptions,
+    ) -> Result<TransactionConfirmation> {
+        let poll = async {
+            loop {
+                let status = self.transaction.find_transaction(trx_id).await?;
+                if let Some(block_num) = status.block_num {
+                    return Ok(TransactionConfirmation {
+                        id: trx_id.to_string(),
+                        block_num,
+                        trx_num: 0,
+                        expired: false,
+                    });
+                }
+                if status.status.starts_with("expired") || status.status == "too_old" {
+                    return Ok(TransactionConfirmation {
+                        id: trx_id.to_string(),
+                        expired: true,
+                        ..TransactionConfirmation::default()
+                    });
+                }
+                tokio::time::sleep(options.interval).await;
+            }
+        };
+        tokio::time::timeout(options.timeout, poll)
+            .await
+            .unwrap_or(Err(HiveError::Timeout))
+    }
+
+    /// The witness-median `account_creation_fee`, which the `fee` of an
+    /// `account_create` must match exactly.
+    pub async fn account_creation_fee(&self) -> Result<Asset> {
+        let props = self.database.get_chain_properties_typed().await?;
+        Ok(props.account_creation_fee)
+    }
+
+    /// RC cost of claiming an account ticket with a zero-fee
+    /// `claim_account`, the alternative to paying the HIVE creation fee;
+    /// the ticket is later spent by `create_claimed_account`.
+    pub async fn claim_account_rc_cost(&self, creator: &str) -> Result<i64> {
+        let claim = ClaimAccountOperation {
+            creator: creator.to_string(),
+            fee: Asset::hive(0.0),
+            extensions: Vec::new(),
+        };
+        self.rc
+            .calculate_cost(&[Operation::ClaimAccount(claim)])
+            .await
+    }
+
+    /// `operation` with its `fee` set to the current account creation fee.
+    pub async fn prepare_account_create(
+        &self,
+        operation: AccountCreateOperation,
+    ) -> Result<AccountCreateOperation> {
+        Ok(AccountCreateOperation {
+            fee: self.account_creation_fee().await?,
+            ..operation
+        })
+    }
+
+    /// Broadcasts `account_create`, paying whatever fee the chain currently
+    /// requires instead of the one in `operation`.
+    pub async fn create_account(
+        &self,
+        operation: AccountCreateOperation,
+        key: &PrivateKey,
+    ) -> Result<TransactionConfirmation> {
+        let operation = self.prepare_account_create(operation).await?;
+        self.broadcast.account_create(operation, key).await
+    }
+
+    /// Encrypts `message` to `recipient`'s memo key, adding the leading `#`
+    /// if it is missing. Fails if the recipient has memos disabled (a null
+    /// memo key) rather than producing a memo nobody can read.
+    pub async fn encrypt_memo(
+        &self,
+        message: &str,
+        sender_memo_key: &PrivateKey,
+        recipient: &str,
+    ) -> Result<String> {
+        let account = self
+            .database
+            .get_accounts(&[recipient])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| HiveError::Other(format!("account '{recipient}' not found")))?;
+        let memo_key = account.memo_public_key()?.ok_or_else(|| {
+            HiveError::Other(format!("recipient '{recipient}' has memos disabled"))
+        })?;
+        let message = if message.starts_with('#') {
+            message.to_string()
+        } else {
+            format!("#{message}")
+        };
+        memo::encode(&message, sender_memo_key, &memo_key)
+    }
+
+    /// Calls every configured node directly (bypassing failover order) and
+    /// reports reachability, latency, and head block, fastest first.
+    pub async fn probe_nodes(&self) -> Vec<NodeProbe> {
+        self.inner.transport().probe_nodes().await
+    }
+
+    /// Probes every node and flags those lagging more than `max_lag_blocks`
+    /// behind the highest head block; lagging nodes stop being preferred.
+    pub async fn check_sync(&self, max_lag_blocks: u32) -> Vec<NodeSyncStatus> {
+        self.inner.transport().check_sync(max_lag_blocks).await
+    }
+
+    pub async fn validate_operations(&self, operations: &[Operation]) -> Result<()> {
+        let version = self.database.get_hardfork_version().await?;
+        let current = hardfork_number(&version)?;
+
+        for operation in operations {
+            let required = operation.min_hardfork();
+            if required > current {
+                return Err(HiveError::Other(format!(
+                    "operation '{}' requires hardfork {required}, but node is on hardfork {current} ({version})",
+                    operation.name()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn hardfork_number(version: &str) -> Result<u32> {
+    parse_hardfork_version(version).map(|(_, minor, _)| minor)
+}
+
+/// hived's `tx_missing_*_auth` assertions, reported as "missing required
+/// active authority" or "Missing Active Authority alice".
+fn is_missing_authority_error(error: &HiveError) -> bool {
+    let HiveError::Rpc { message, .. } = error else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    ["active", "owner", "posting"].iter().any(|role| {
+        message.contains(&format!("missing {role} authority"))
+            || message.contains(&format!("missing required {role} authority"))
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use serde_json::json;
     use wiremock::matchers::{body_partial_json, method};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
-    use crate::client::{Client, ClientOptions};
+    use crate::client::{Client, ClientOptions, ConfirmationDepth, WaitOptions};
+    use crate::crypto::utils::sha256;
+    use crate::crypto::{sign_transaction, KeyRole, PrivateKey, Signature};
+    use crate::error::HiveError;
+    use crate::serialization::serialize_transaction_for;
+    use crate::test_support::mount_cost_state;
+    use crate::transport::BackoffStrategy;
+    use crate::types::{
+        AccountCreateOperation, Asset, Authority, ChainId, DynamicGlobalProperties, Operation,
+        SignedTransaction, Transaction, TransferOperation, UpdateProposalOperation,
+    };
 
     #[tokio::test]
     async fn raw_call_routes_through_transport() {
@@ -176,28 +951,995 @@ mod tests {
         assert_eq!(value["ok"], json!(true));
     }
 
+    #[test]
+    fn testnet_presets_set_chain_id_and_key_prefix() {
+        let options = ClientOptions::testnet();
+        assert_eq!(options.chain_id.to_bytes()[..4], [0x18, 0xdc, 0xf0, 0xa2]);
+        assert_eq!(options.address_prefix, "TST");
+        assert_eq!(ClientOptions::mirrornet().chain_id, ChainId::mirrornet());
+        assert_eq!(ClientOptions::mirrornet().address_prefix, "TST");
+
+        let key = PrivateKey::from_seed("alice-posting").expect("seed should derive a key");
+        let testnet = Client::testnet_default();
+        let public_key = testnet.public_key(&key).to_string();
+        assert!(public_key.starts_with("TST"));
+        assert_eq!(
+            public_key[3..],
+            Client::new_default().public_key(&key).to_string()[3..]
+        );
+    }
+
+    #[test]
+    fn testnet_preset_signs_transfers_with_testnet_symbols() {
+        let client = Client::testnet_default();
+        let key = PrivateKey::from_seed("alice-active").expect("seed should derive a key");
+        let transaction = Transaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2026-01-01T00:00:00".to_string(),
+            operations: vec![Operation::Transfer(TransferOperation {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: Asset::hive(1.0),
+                memo: String::new(),
+            })],
+            extensions: vec![],
+        };
+
+        let bytes = serialize_transaction_for(&transaction, &client.options().chain_id)
+            .expect("transaction should serialize");
+        assert!(bytes.windows(7).any(|window| window == b"TESTS\0\0"));
+        assert!(!bytes.windows(5).any(|window| window == b"STEEM"));
+
+        let signed = client
+            .broadcast
+            .sign_transaction(&transaction, &[&key])
+            .expect("transaction should sign");
+        let mut to_hash = ChainId::testnet().bytes.to_vec();
+        to_hash.extend_from_slice(&bytes);
+        let signature = Signature::from_hex(&signed.signatures[0]).expect("signature should parse");
+        assert_eq!(
+            signature
+                .recover(&sha256(&to_hash))
+                .expect("key should recover"),
+            key.public_key()
+        );
+    }
+
     #[tokio::test]
-    async fn database_api_is_wired_to_client() {
+    async fn api_overrides_remap_method_namespace() {
         let server = MockServer::start().await;
         Mock::given(method("POST"))
             .and(body_partial_json(json!({
                 "method": "call",
-                "params": ["condenser_api", "get_account_count", []]
+                "params": ["proxy_api", "get_account_count", []]
             })))
             .respond_with(ResponseTemplate::new(200).set_body_json(json!({
                 "id": 0,
                 "jsonrpc": "2.0",
-                "result": 1337
+                "result": 7
             })))
+            .expect(1)
             .mount(&server)
             .await;
 
-        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let mut options = ClientOptions::default();
+        options
+            .api_overrides
+            .insert("get_account_count".to_string(), "proxy_api".to_string());
+        let client = Client::new(vec![&server.uri()], options);
         let count = client
             .database
             .get_account_count()
             .await
-            .expect("database call should succeed");
-        assert_eq!(count, 1337);
+            .expect("overridden call should succeed");
+        assert_eq!(count, 7);
+    }
+
+    #[tokio::test]
+    async fn call_envelope_exposes_id_and_result_or_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_config", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"HIVE_BLOCK_INTERVAL": 3}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_account_count", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {"code": -32002, "message": "Assert Exception"}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let envelope = client
+            .call_envelope::<serde_json::Value>("condenser_api", "get_config", json!([]))
+            .await
+            .expect("envelope call should succeed");
+        assert_eq!(envelope.id, json!(0));
+        assert_eq!(envelope.jsonrpc, "2.0");
+        assert_eq!(envelope.error, None);
+        assert_eq!(envelope.result, Some(json!({"HIVE_BLOCK_INTERVAL": 3})));
+
+        let envelope = client
+            .call_envelope::<u64>("condenser_api", "get_account_count", json!([]))
+            .await
+            .expect("rpc errors should stay in the envelope");
+        assert_eq!(envelope.result, None);
+        assert_eq!(envelope.error.as_ref().map(|err| err.code), Some(-32002));
+        assert!(matches!(
+            envelope.into_result(),
+            Err(HiveError::Rpc { code: -32002, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn node_version_parses_blockchain_semver() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_version", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "blockchain_version": "1.27.5",
+                    "hive_revision": "a2f1a9b0e2b3e0c9a9e4d45c93d2b4d0e5d4c0a1",
+                    "fc_revision": "a2f1a9b0e2b3e0c9a9e4d45c93d2b4d0e5d4c0a1",
+                    "chain_id": "beeab0de00000000000000000000000000000000000000000000000000000000",
+                    "node_type": "mainnet"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let version = client.node_version().await.expect("version should load");
+        assert_eq!(version.fc_revision.len(), 40);
+        let semver = version.blockchain_semver().expect("version should parse");
+        assert_eq!(semver, (1, 27, 5));
+        assert!(semver > (1, 26, 1));
+        assert!(semver < (1, 28, 0));
+    }
+
+    #[tokio::test]
+    async fn vote_value_estimates_hbd_from_mana_fund_and_price() {
+        let server = MockServer::start().await;
+        let respond = |method_name: &str, result: serde_json::Value| {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["condenser_api", method_name]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": result
+                })))
+        };
+        respond(
+            "get_accounts",
+            json!([{
+                "name": "alice",
+                "vesting_shares": "1000000.000000 VESTS",
+                "delegated_vesting_shares": "0.000000 VESTS",
+                "received_vesting_shares": "0.000000 VESTS",
+                "voting_manabar": {"current_mana": "1000000000000", "last_update_time": 0}
+            }]),
+        )
+        .mount(&server)
+        .await;
+        respond(
+            "get_reward_fund",
+            json!({
+                "id": 0,
+                "name": "post",
+                "reward_balance": "800000.000 HIVE",
+                "recent_claims": "5000000000000000"
+            }),
+        )
+        .mount(&server)
+        .await;
+        respond(
+            "get_current_median_history_price",
+            json!({"base": "0.250 HBD", "quote": "1.000 HIVE"}),
+        )
+        .mount(&server)
+        .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let full = client
+            .vote_value("alice", 100.0)
+            .await
+            .expect("vote value should compute");
+        // (1e12 / 50 - dust) rshares of 5e15 claims on 800k HIVE, at $0.25.
+        assert_eq!(full, Asset::hbd(0.798));
+        let half = client
+            .vote_value("alice", 50.0)
+            .await
+            .expect("vote value should compute");
+        assert!(half.amount > 0 && half.amount < full.amount);
+    }
+
+    #[test]
+    fn potential_signatures_local_follows_role_hierarchy() {
+        const ALICE_ACTIVE: &str = "STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA";
+        const ALICE_OWNER: &str = "STM6LLegbAgLAy28EHrffBVuANFWcFgmqRMW13wBmTExqFE9SCkg4";
+        const ALICE_POSTING: &str = "STM7RbCDXHMuVMk7yk9GhXMk7bCSTrqhdfrGMDQaQPbZTBmj3GnDA";
+        const BOB_ACTIVE: &str = "STM8m5UgaFAAYQRuaNejYdS8FVLVp9Ss3K1qAVk5de6F8s3HnVbvA";
+
+        let transaction = SignedTransaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![Operation::Transfer(TransferOperation {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: Asset::hive(1.0),
+                memo: String::new(),
+            })],
+            extensions: vec![],
+            signatures: vec![],
+        };
+        let local = Client::potential_signatures_local(&transaction, |account, role| {
+            match (account, role) {
+                ("alice", KeyRole::Active) => Some(Authority {
+                    weight_threshold: 1,
+                    account_auths: vec![("bob".to_string(), 1)],
+                    key_auths: vec![(ALICE_ACTIVE.to_string(), 1)],
+                }),
+                ("alice", KeyRole::Owner) => Some(Authority::from_key(ALICE_OWNER)),
+                ("alice", KeyRole::Posting) => Some(Authority::from_key(ALICE_POSTING)),
+                ("bob", KeyRole::Active) => Some(Authority::from_key(BOB_ACTIVE)),
+                _ => None,
+            }
+        });
+
+        // A transfer needs alice's active authority: her active and owner
+        // keys plus bob's active key through her account_auths, but not her
+        // posting key.
+        assert_eq!(
+            local,
+            vec![
+                "STM6LLegbAgLAy28EHrffBVuANFWcFgmqRMW13wBmTExqFE9SCkg4",
+                "STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA",
+                "STM8m5UgaFAAYQRuaNejYdS8FVLVp9Ss3K1qAVk5de6F8s3HnVbvA",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn authority_cache_reuses_accounts_until_invalidated() {
+        let alice_active = PrivateKey::from_seed("alice-active").expect("seed should derive a key");
+        let alice_active_pub = alice_active.public_key().to_string();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_accounts", [["alice"]]]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": [{
+                    "name": "alice",
+                    "owner": {"weight_threshold": 1, "account_auths": [], "key_auths": [[alice_active_pub, 1]]},
+                    "active": {"weight_threshold": 1, "account_auths": [], "key_auths": [[alice_active_pub, 1]]},
+                    "posting": {"weight_threshold": 1, "account_auths": [], "key_auths": [[alice_active_pub, 1]]},
+                    "memo_key": alice_active_pub
+                }]
+            })))
+            .mount(&server)
+            .await;
+        let get_accounts_calls = || async {
+            server
+                .received_requests()
+                .await
+                .expect("requests recorded")
+                .len()
+        };
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let transaction = Transaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: vec![Operation::Transfer(TransferOperation {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: Asset::hive(1.0),
+                memo: String::new(),
+            })],
+            extensions: vec![],
+        };
+        let signed = sign_transaction(&transaction, &[&alice_active], &client.options().chain_id)
+            .expect("transaction should sign");
+
+        assert!(client.verify_signatures(&signed).await.expect("verify"));
+        assert!(client.verify_signatures(&signed).await.expect("verify"));
+        assert_eq!(
+            client
+                .effective_keys("alice", KeyRole::Active)
+                .await
+                .expect("effective keys"),
+            vec![(alice_active_pub.clone(), 1)]
+        );
+        assert_eq!(get_accounts_calls().await, 1);
+
+        let other = PrivateKey::from_seed("mallory").expect("seed should derive a key");
+        let forged = sign_transaction(&transaction, &[&other], &client.options().chain_id)
+            .expect("transaction should sign");
+        assert!(!client.verify_signatures(&forged).await.expect("verify"));
+        assert_eq!(get_accounts_calls().await, 1);
+
+        client.authority_cache().invalidate("alice");
+        assert!(client.verify_signatures(&signed).await.expect("verify"));
+        assert_eq!(get_accounts_calls().await, 2);
+    }
+
+    #[tokio::test]
+    async fn prepare_recover_account_uses_outstanding_request() {
+        let public = |seed: &str| {
+            PrivateKey::from_seed(seed)
+                .expect("seed should derive a key")
+                .public_key()
+                .to_string()
+        };
+        let new_owner = Authority::from_key(&public("alice-new-owner"));
+        let recent_owner = Authority::from_key(&public("alice-old-owner"));
+
+        let server = MockServer::start().await;
+        for (account, expires) in [
+            ("alice", "2099-01-01T00:00:00"),
+            ("bob", "2020-01-01T00:00:00"),
+        ] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["condenser_api", "get_recovery_request", [account]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": {
+                        "id": 3,
+                        "account_to_recover": account,
+                        "new_owner_authority": new_owner,
+                        "expires": expires
+                    }
+                })))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_recovery_request", ["carol"]]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": null
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let operation = client
+            .prepare_recover_account("alice", recent_owner.clone())
+            .await
+            .expect("request should be found");
+        assert_eq!(operation.account_to_recover, "alice");
+        assert_eq!(operation.new_owner_authority, new_owner);
+        assert_eq!(operation.recent_owner_authority, recent_owner);
+
+        let expired = client
+            .prepare_recover_account("bob", recent_owner.clone())
+            .await
+            .expect_err("expired request should be refused");
+        assert!(expired.to_string().contains("expired"), "{expired}");
+        assert!(client
+            .prepare_recover_account("carol", recent_owner)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn await_confirmation_polls_until_transaction_is_in_a_block() {
+        let status = |result: serde_json::Value| {
+            ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": result
+            }))
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["transaction_status_api", "find_transaction"]
+            })))
+            .respond_with(status(json!({"status": "within_mempool"})))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["transaction_status_api", "find_transaction"]
+            })))
+            .respond_with(status(
+                json!({"status": "found_in_block", "block_num": 4321}),
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let options = WaitOptions {
+            interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        };
+        let confirmation = client
+            .await_confirmation("deadbeef", options)
+            .await
+            .expect("transaction should be confirmed");
+        assert_eq!(confirmation.id, "deadbeef");
+        assert_eq!(confirmation.block_num, 4321);
+        assert!(!confirmation.expired);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn wait_for_irreversible_resolves_once_lib_passes_block() {
+        let props = |head: u32, lib: u32| {
+            ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "head_block_number": head,
+                    "head_block_id": "0000000000000000000000000000000000000000",
+                    "time": "2024-01-01T00:00:00",
+                    "last_irreversible_block_num": lib
+                }
+            }))
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties"]
+            })))
+            .respond_with(props(105, 90))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_dynamic_global_properties"]
+            })))
+            .respond_with(props(120, 101))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let depth = client
+            .wait_for_irreversible(100, Duration::from_secs(5))
+            .await
+            .expect("block should become irreversible");
+        assert_eq!(
+            depth,
+            ConfirmationDepth {
+                block_num: 100,
+                head: 120,
+                last_irreversible: 101,
+                confirmations: 20,
+                is_irreversible: true,
+            }
+        );
+
+        let pending = client
+            .confirmation_depth(110)
+            .await
+            .expect("props should load");
+        assert!(!pending.is_irreversible);
+        assert!(matches!(
+            client
+                .wait_for_irreversible(110, Duration::from_millis(200))
+                .await,
+            Err(HiveError::Timeout)
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_account_pays_current_creation_fee() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_chain_properties"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {
+                    "account_creation_fee": "3.000 HIVE",
+                    "maximum_block_size": 65536,
+                    "hbd_interest_rate": 2000
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        assert_eq!(
+            client
+                .account_creation_fee()
+                .await
+                .expect("fee should load"),
+            Asset::hive(3.0)
+        );
+
+        let key = PrivateKey::from_seed("new-account").expect("seed should derive a key");
+        let public = key.public_key().to_string();
+        let operation = client
+            .prepare_account_create(AccountCreateOperation {
+                fee: Asset::hive(0.1),
+                creator: "alice".to_string(),
+                new_account_name: "newbie".to_string(),
+                owner: Authority::from_key(&public),
+                active: Authority::from_key(&public),
+                posting: Authority::from_key(&public),
+                memo_key: public.clone(),
+                json_metadata: String::new(),
+            })
+            .await
+            .expect("operation should be prepared");
+        assert_eq!(operation.fee, Asset::hive(3.0));
+        assert_eq!(operation.new_account_name, "newbie");
+    }
+
+    #[tokio::test]
+    async fn encrypt_memo_refuses_recipients_with_memos_disabled() {
+        let recipient_key = PrivateKey::from_seed("bob-memo").expect("seed should derive a key");
+        let server = MockServer::start().await;
+        for (account, memo_key) in [
+            ("bob", recipient_key.public_key().to_string()),
+            (
+                "carol",
+                "STM1111111111111111111111111111111114T1Anm".to_string(),
+            ),
+        ] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({
+                    "params": ["condenser_api", "get_accounts", [[account]]]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": 0,
+                    "jsonrpc": "2.0",
+                    "result": [{"name": account, "memo_key": memo_key}]
+                })))
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let sender = PrivateKey::from_seed("alice-memo").expect("seed should derive a key");
+        let encrypted = client
+            .encrypt_memo("hello bob", &sender, "bob")
+            .await
+            .expect("memo should encrypt");
+        assert_eq!(
+            crate::crypto::memo::decode(&encrypted, &recipient_key).expect("memo should decode"),
+            "#hello bob"
+        );
+
+        let err = client
+            .encrypt_memo("#hello carol", &sender, "carol")
+            .await
+            .expect_err("null memo key should be refused");
+        assert!(err.to_string().contains("memos disabled"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn call_with_timeout_overrides_client_default() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"id": 0, "jsonrpc": "2.0", "result": 42}))
+                    .set_delay(Duration::from_millis(600)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new(
+            vec![&server.uri()],
+            ClientOptions {
+                timeout: Duration::from_millis(200),
+                backoff: BackoffStrategy::Fixed { ms: 0 },
+                ..ClientOptions::default()
+            },
+        );
+        assert!(client
+            .call::<u64>("condenser_api", "get_account_count", json!([]))
+            .await
+            .is_err());
+
+        let count: u64 = client
+            .call_with_timeout(
+                "condenser_api",
+                "get_account_count",
+                json!([]),
+                Duration::from_secs(5),
+            )
+            .await
+            .expect("generous timeout should succeed");
+        assert_eq!(count, 42);
+    }
+
+    #[tokio::test]
+    async fn cloned_clients_share_one_transport_across_tasks() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Client>();
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_account_count", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": 7
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let clone = client.clone();
+        assert!(std::sync::Arc::ptr_eq(&client.inner, &clone.inner));
+        assert!(std::sync::Arc::ptr_eq(
+            client.inner.transport(),
+            clone.inner.transport()
+        ));
+
+        let tasks = [client.clone(), clone]
+            .map(|client| tokio::spawn(async move { client.database.get_account_count().await }));
+        for task in tasks {
+            let count = task
+                .await
+                .expect("task should not panic")
+                .expect("call should succeed");
+            assert_eq!(count, 7);
+        }
+        assert_eq!(client.last_node(), Some(server.uri()));
+    }
+
+    #[tokio::test]
+    async fn database_api_is_wired_to_client() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_account_count", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": 1337
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let count = client
+            .database
+            .get_account_count()
+            .await
+            .expect("database call should succeed");
+        assert_eq!(count, 1337);
+    }
+
+    #[tokio::test]
+    async fn validate_operations_rejects_ops_newer_than_node_hardfork() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_hardfork_version", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": "0.20.0"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let op = Operation::UpdateProposal(UpdateProposalOperation {
+            proposal_id: 7,
+            creator: "alice".to_string(),
+            daily_pay: Asset::hbd(10.0),
+            subject: "subject".to_string(),
+            permlink: "permlink".to_string(),
+            extensions: vec![],
+        });
+
+        let err = client
+            .validate_operations(&[op])
+            .await
+            .expect_err("update_proposal should be rejected on HF20");
+        match err {
+            HiveError::Other(message) => {
+                assert!(message.contains("update_proposal"));
+                assert!(message.contains("hardfork 24"));
+                assert!(message.contains("hardfork 20"));
+            }
+            other => panic!("expected HiveError::Other, got {other:?}"),
+        }
+    }
+
+    async fn mount_head_block(server: &MockServer, head_block_number: u32, delay: Duration) {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_dynamic_global_properties", []]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_delay(delay)
+                    .set_body_json(json!({
+                        "id": 0,
+                        "jsonrpc": "2.0",
+                        "result": { "head_block_number": head_block_number }
+                    })),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn ping_reports_head_block_and_serving_node() {
+        let down = MockServer::start().await;
+        let up = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&down)
+            .await;
+        let props = DynamicGlobalProperties {
+            head_block_number: 4242,
+            time: "2024-01-01T00:00:00".to_string(),
+            ..Default::default()
+        };
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": props
+            })))
+            .mount(&up)
+            .await;
+
+        let options = ClientOptions {
+            backoff: BackoffStrategy::Fixed { ms: 0 },
+            ..ClientOptions::default()
+        };
+        let client = Client::new(vec![&down.uri(), &up.uri()], options);
+        let ping = client.ping().await.expect("ping should succeed");
+        assert_eq!(ping.head_block_number, 4242);
+        assert_eq!(ping.time, "2024-01-01T00:00:00");
+        assert_eq!(ping.node, up.uri());
+    }
+
+    #[tokio::test]
+    async fn probe_nodes_reports_every_node_fastest_first() {
+        let slow = MockServer::start().await;
+        let fast = MockServer::start().await;
+        mount_head_block(&slow, 100, Duration::from_millis(200)).await;
+        mount_head_block(&fast, 101, Duration::ZERO).await;
+
+        let client = Client::new(vec![&slow.uri(), &fast.uri()], ClientOptions::default());
+        let probes = client.probe_nodes().await;
+
+        assert_eq!(probes.len(), 2);
+        assert!(probes.iter().all(|probe| probe.ok));
+        assert_eq!(probes[0].node, fast.uri());
+        assert_eq!(probes[0].head_block_number, Some(101));
+        assert!(probes[0].latency < probes[1].latency);
+    }
+
+    #[tokio::test]
+    async fn check_sync_flags_lagging_node_and_prefers_synced_one() {
+        let lagging = MockServer::start().await;
+        let synced = MockServer::start().await;
+        mount_head_block(&lagging, 50, Duration::ZERO).await;
+        mount_head_block(&synced, 100, Duration::from_millis(50)).await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "call",
+                "params": ["condenser_api", "get_account_count", []]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": 7
+            })))
+            .expect(1)
+            .mount(&synced)
+            .await;
+
+        let client = Client::new(
+            vec![&lagging.uri(), &synced.uri()],
+            ClientOptions::default(),
+        );
+        let statuses = client.check_sync(10).await;
+
+        let lagging_status = statuses
+            .iter()
+            .find(|status| status.probe.node == lagging.uri())
+            .expect("lagging node should be reported");
+        assert!(lagging_status.behind);
+        assert_eq!(lagging_status.lag_blocks, Some(50));
+        let synced_status = statuses
+            .iter()
+            .find(|status| status.probe.node == synced.uri())
+            .expect("synced node should be reported");
+        assert!(!synced_status.behind);
+
+        let count = client
+            .database
+            .get_account_count()
+            .await
+            .expect("synced node should serve the call");
+        assert_eq!(count, 7);
+    }
+
+    #[tokio::test]
+    async fn last_node_reports_node_that_served_call() {
+        let failing = MockServer::start().await;
+        let healthy = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&failing)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": 3
+            })))
+            .mount(&healthy)
+            .await;
+
+        let client = Client::new(
+            vec![&failing.uri(), &healthy.uri()],
+            ClientOptions {
+                backoff: BackoffStrategy::Fixed { ms: 0 },
+                ..ClientOptions::default()
+            },
+        );
+        assert_eq!(client.last_node(), None);
+
+        client
+            .database
+            .get_account_count()
+            .await
+            .expect("second node should serve the call");
+        assert_eq!(client.last_node(), Some(healthy.uri()));
+    }
+
+    #[tokio::test]
+    async fn preflight_aggregates_authority_and_rc_checks() {
+        let server = MockServer::start().await;
+        let signer = PrivateKey::from_seed("alice-active").expect("key");
+        let cosigner = PrivateKey::from_seed("alice-cosigner").expect("key");
+        let available = vec![
+            signer.public_key().to_string(),
+            cosigner.public_key().to_string(),
+        ];
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "verify_authority"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {"code": -32000, "message": "Missing Active Authority alice"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "get_required_signatures"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": available
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["rc_api", "find_rc_accounts", {"accounts": ["alice"]}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": {"rc_accounts": [{
+                    "account": "alice",
+                    "max_rc": "100000000000000",
+                    "rc_manabar": {"current_mana": "100000000000000", "last_update_time": 0}
+                }]}
+            })))
+            .mount(&server)
+            .await;
+        mount_cost_state(&server).await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let tx = Transaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:01:00".to_string(),
+            operations: vec![Operation::Transfer(TransferOperation {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: Asset::hive(1.0),
+                memo: String::new(),
+            })],
+            extensions: vec![],
+        };
+        let signed = sign_transaction(&tx, &[&signer], &client.options().chain_id)
+            .expect("transaction should sign");
+
+        let preflight = client
+            .preflight(&signed, &available)
+            .await
+            .expect("preflight should succeed");
+        assert!(!preflight.authority_ok);
+        assert_eq!(preflight.missing_keys, Some(vec![available[1].clone()]));
+        assert!(preflight.rc_cost > 0);
+        assert!(preflight.rc_affordable);
+    }
+
+    #[tokio::test]
+    async fn preflight_propagates_rpc_errors_other_than_missing_authority() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "params": ["condenser_api", "verify_authority"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {"code": -32000, "message": "Bad Cast:Invalid cast from string to object"}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(vec![&server.uri()], ClientOptions::default());
+        let signed = SignedTransaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:01:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+            signatures: vec![],
+        };
+
+        let err = client
+            .preflight(&signed, &[])
+            .await
+            .expect_err("a malformed request should fail the preflight");
+        assert!(matches!(err, HiveError::Rpc { .. }));
     }
 }
@@ -4,16 +4,26 @@ use std::str::FromStr;
 use secp256k1::ecdh;
 use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
 use secp256k1::rand::thread_rng;
-use secp256k1::{Message, PublicKey as SecpPublicKey, Secp256k1, SecretKey};
+use secp256k1::{Message, PublicKey as SecpPublicKey, SecretKey};
 
 use crate::crypto::signature::Signature;
-use crate::crypto::utils::{double_sha256, ripemd160, sha256, sha512};
+use crate::crypto::utils::{double_sha256, ripemd160, secp_context, sha256, sha512};
 use crate::error::{HiveError, Result};
 use crate::serialization::serializer::transaction_digest;
 use crate::types::{ChainId, SignedTransaction, Transaction};
 
 const NETWORK_ID: u8 = 0x80;
 
+/// Default cap on nonces tried by [`PrivateKey::sign`] before giving up.
+pub const DEFAULT_MAX_SIGN_ATTEMPTS: u32 = u16::MAX as u32;
+
+/// A canonical signature together with the number of nonces it took to find.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignOutcome {
+    pub signature: Signature,
+    pub attempts: u32,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum KeyRole {
     Owner,
@@ -125,6 +135,32 @@ impl PublicKey {
         }
     }
 
+    /// SEC1 uncompressed form (`0x04 || x || y`); all zeros for the null key.
+    pub fn uncompressed_bytes(&self) -> [u8; 65] {
+        match self.key {
+            Some(key) => key.serialize_uncompressed(),
+            None => [0_u8; 65],
+        }
+    }
+
+    /// Hex of the 33-byte compressed key.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.compressed_bytes())
+    }
+
+    /// Parses a hex-encoded compressed key, using the default `STM` prefix.
+    pub fn from_hex(value: &str) -> Result<Self> {
+        let bytes = hex::decode(value)
+            .map_err(|err| HiveError::InvalidKey(format!("invalid public key hex: {err}")))?;
+        let bytes: [u8; 33] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            HiveError::InvalidKey(format!(
+                "invalid public key length: expected 33 bytes, got {}",
+                bytes.len()
+            ))
+        })?;
+        Self::from_bytes(bytes, "STM")
+    }
+
     pub fn is_null(&self) -> bool {
         self.key.is_none()
     }
@@ -141,13 +177,16 @@ impl PublicKey {
         let msg = Message::from_digest_slice(digest);
         let sig = secp256k1::ecdsa::Signature::from_compact(&signature.compact_bytes());
         match (msg, sig) {
-            (Ok(msg), Ok(sig)) => {
-                let secp = Secp256k1::verification_only();
-                secp.verify_ecdsa(&msg, &sig, public_key).is_ok()
-            }
+            (Ok(msg), Ok(sig)) => secp_context().verify_ecdsa(&msg, &sig, public_key).is_ok(),
             _ => false,
         }
     }
+
+    /// Checks a [`PrivateKey::sign_message`] signature over `message`. Not
+    /// for transactions, whose digest also covers the chain id.
+    pub fn verify_message(&self, message: &[u8], signature: &Signature) -> bool {
+        self.verify(&sha256(message), signature)
+    }
 }
 
 impl Display for PublicKey {
@@ -236,33 +275,58 @@ impl PrivateKey {
     }
 
     pub fn public_key(&self) -> PublicKey {
-        let secp = Secp256k1::new();
-        let key = SecpPublicKey::from_secret_key(&secp, &self.secret);
+        let key = SecpPublicKey::from_secret_key(secp_context(), &self.secret);
         PublicKey::from_secp256k1(key, "STM")
     }
 
     pub fn sign(&self, digest: &[u8; 32]) -> Result<Signature> {
-        let secp = Secp256k1::new();
+        self.sign_with_limit(digest, DEFAULT_MAX_SIGN_ATTEMPTS)
+            .map(|signed| signed.signature)
+    }
+
+    /// Signs the sha256 of arbitrary bytes, e.g. a login challenge proving
+    /// ownership of this key. This is NOT transaction signing: no chain id
+    /// is mixed in, so the signature can never authorize an operation. Use
+    /// [`sign_transaction`] for transactions.
+    pub fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign(&sha256(message))
+    }
+
+    /// Signs `digest`, giving up after `max_attempts` non-canonical signatures.
+    /// The returned attempt count is 1 when the first nonce was canonical.
+    pub fn sign_with_limit(&self, digest: &[u8; 32], max_attempts: u32) -> Result<SignOutcome> {
+        if max_attempts == 0 {
+            return Err(HiveError::signing(
+                "max signing attempts must be at least 1".to_string(),
+            ));
+        }
+        if digest.iter().all(|byte| *byte == 0) {
+            return Err(HiveError::signing(
+                "refusing to sign a zero digest".to_string(),
+            ));
+        }
+
+        let secp = secp_context();
         let msg = Message::from_digest_slice(digest)
-            .map_err(|err| HiveError::Signing(format!("invalid digest: {err}")))?;
+            .map_err(|err| HiveError::secp("invalid digest", err))?;
 
-        let mut attempts = 0_u16;
-        loop {
-            attempts = attempts.saturating_add(1);
-            let nonce_seed = sha256(&[digest.as_slice(), &[(attempts as u8)]].concat());
+        for attempts in 1..=max_attempts {
+            let nonce_seed = sign_nonce_seed(digest, attempts);
             let recoverable =
                 secp.sign_ecdsa_recoverable_with_noncedata(&msg, &self.secret, &nonce_seed);
             let (recovery_id, compact) = recoverable.serialize_compact();
             if Signature::is_canonical_compact(&compact) {
-                return Signature::from_compact(compact, recovery_id.to_i32() as u8);
-            }
-
-            if attempts == u16::MAX {
-                return Err(HiveError::Signing(
-                    "unable to produce canonical signature".to_string(),
-                ));
+                let signature = Signature::from_compact(compact, recovery_id.to_i32() as u8)?;
+                return Ok(SignOutcome {
+                    signature,
+                    attempts,
+                });
             }
         }
+
+        Err(HiveError::signing(format!(
+            "unable to produce canonical signature in {max_attempts} attempts"
+        )))
     }
 
     pub fn get_shared_secret(&self, public_key: &PublicKey) -> [u8; 64] {
@@ -310,11 +374,21 @@ impl TryFrom<String> for PrivateKey {
     }
 }
 
+// The first 255 attempts keep the single-byte counter used by dhive so
+// signatures stay byte-for-byte compatible; later attempts widen it instead of
+// wrapping around and re-trying nonces that were already rejected.
+fn sign_nonce_seed(digest: &[u8; 32], attempts: u32) -> [u8; 32] {
+    match u8::try_from(attempts) {
+        Ok(counter) => sha256(&[digest.as_slice(), &[counter]].concat()),
+        Err(_) => sha256(&[digest.as_slice(), &attempts.to_le_bytes()].concat()),
+    }
+}
+
 pub(crate) fn recoverable_from_signature(signature: &Signature) -> Result<RecoverableSignature> {
     let rec_id = RecoveryId::from_i32(signature.recovery_id() as i32)
-        .map_err(|err| HiveError::Signing(format!("invalid recovery id: {err}")))?;
+        .map_err(|err| HiveError::secp("invalid recovery id", err))?;
     RecoverableSignature::from_compact(&signature.compact_bytes(), rec_id)
-        .map_err(|err| HiveError::Signing(format!("invalid compact signature: {err}")))
+        .map_err(|err| HiveError::secp("invalid compact signature", err))
 }
 
 pub fn sign_transaction(
@@ -341,6 +415,8 @@ pub fn sign_transaction(
 #[cfg(test)]
 mod tests {
     use crate::crypto::keys::{sign_transaction, KeyRole, PrivateKey, PublicKey};
+    use crate::crypto::utils::sha256;
+    use crate::serialization::serializer::serialize_transaction;
     use crate::types::{ChainId, Operation, Transaction, VoteOperation};
 
     #[test]
@@ -388,6 +464,26 @@ mod tests {
         assert_eq!(key.compressed_bytes(), [0_u8; 33]);
     }
 
+    #[test]
+    fn public_key_hex_round_trip() {
+        let key = PublicKey::from_string("STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA")
+            .expect("public key should parse");
+        let hex = key.to_hex();
+        assert_eq!(hex.len(), 66);
+        let parsed = PublicKey::from_hex(&hex).expect("hex should parse");
+        assert_eq!(parsed, key);
+
+        let uncompressed = key.uncompressed_bytes();
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(uncompressed[1..33], key.compressed_bytes()[1..]);
+
+        let null = PublicKey::from_hex(&"00".repeat(33)).expect("null key hex should parse");
+        assert!(null.is_null());
+        assert_eq!(null.uncompressed_bytes(), [0_u8; 65]);
+        assert!(PublicKey::from_hex("02abcd").is_err());
+        assert!(PublicKey::from_hex("zz").is_err());
+    }
+
     #[test]
     fn sign_transaction_matches_dhive_vector() {
         let key = PrivateKey::from_wif("5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL")
@@ -412,4 +508,92 @@ mod tests {
             "1f037a09c1110a8bd8757ad3081a11456d241feedd4366723bb9f9046cc6a1b21b26bf4b8372546bc2446c7498ff5742dce0143ff1fe13591eb8dd88b9a7fef2f2"
         );
     }
+
+    #[test]
+    fn sign_message_verifies_and_is_not_a_transaction_signature() {
+        let key = PrivateKey::from_wif("5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL")
+            .expect("wif should parse");
+        let challenge = b"login:alice:1700000000:3f9a";
+
+        let signature = key.sign_message(challenge).expect("challenge should sign");
+        assert!(signature.is_canonical());
+        assert!(key.public_key().verify_message(challenge, &signature));
+        assert!(!key
+            .public_key()
+            .verify_message(b"login:mallory", &signature));
+        assert!(!PrivateKey::generate()
+            .public_key()
+            .verify_message(challenge, &signature));
+
+        let tx = Transaction {
+            ref_block_num: 1234,
+            ref_block_prefix: 1122334455,
+            expiration: "2017-07-15T16:51:19".to_string(),
+            operations: Vec::new(),
+            extensions: Vec::new(),
+        };
+        let chain_id = ChainId { bytes: [0_u8; 32] };
+        let signed = sign_transaction(&tx, &[&key], &chain_id).expect("transaction should sign");
+        let tx_bytes = serialize_transaction(&tx).expect("transaction should serialize");
+        let over_tx_bytes = key.sign_message(&tx_bytes).expect("bytes should sign");
+        assert_ne!(signed.signatures[0], over_tx_bytes.to_hex());
+    }
+
+    #[test]
+    fn signed_hello_recovers_to_the_signing_key() {
+        let key = PrivateKey::from_login("foo", "barman", KeyRole::Posting).expect("valid key");
+        let public_key = key.public_key();
+
+        let signature = key.sign_message(b"hello").expect("message should sign");
+        assert!(public_key.verify_message(b"hello", &signature));
+        assert!(!public_key.verify_message(b"hello!", &signature));
+
+        let recovered = signature
+            .recover(&sha256(b"hello"))
+            .expect("signer should recover");
+        assert_eq!(recovered, public_key);
+    }
+
+    #[test]
+    fn sign_with_limit_reports_attempts() {
+        let key = PrivateKey::from_wif("5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL")
+            .expect("wif should parse");
+        let digest = sha256(b"hive-rs");
+
+        let outcome = key
+            .sign_with_limit(&digest, 64)
+            .expect("digest should sign");
+        assert!((1..=64).contains(&outcome.attempts));
+        assert_eq!(
+            outcome.signature,
+            key.sign(&digest).expect("digest should sign")
+        );
+    }
+
+    #[test]
+    fn sign_with_limit_rejects_zero_digest_and_zero_limit() {
+        let key = PrivateKey::generate();
+        assert!(key.sign_with_limit(&[0_u8; 32], 10).is_err());
+        assert!(key.sign_with_limit(&sha256(b"hive-rs"), 0).is_err());
+    }
+
+    #[test]
+    fn shared_context_matches_fresh_context() {
+        let key = PrivateKey::from_wif("5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL")
+            .expect("wif should parse");
+        let fresh = secp256k1::Secp256k1::new();
+        let expected = secp256k1::PublicKey::from_secret_key(&fresh, &key.secret);
+        assert_eq!(key.public_key().key, Some(expected));
+
+        let public = key.public_key();
+        let first = key.sign(&sha256(b"digest 0")).expect("digest should sign");
+        for round in 0..1000_u32 {
+            let digest = sha256(format!("digest {}", round % 10).as_bytes());
+            let signature = key.sign(&digest).expect("digest should sign");
+            assert!(public.verify(&digest, &signature));
+            if round % 10 == 0 {
+                assert_eq!(signature, first);
+            }
+        }
+    }
 }
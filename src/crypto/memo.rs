@@ -1,6 +1,7 @@
 use aes::Aes256;
 use cbc::cipher::block_padding::Pkcs7;
 use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use rand::RngCore;
 
 use crate::crypto::keys::{PrivateKey, PublicKey};
 use crate::crypto::utils::{sha256, sha512};
@@ -31,6 +32,19 @@ pub fn encode(
     encode_with_nonce(message, sender_private, receiver_public, nonce)
 }
 
+/// Encodes a memo using a random 64-bit nonce drawn from `rng`.
+///
+/// The nonce must never repeat for the same sender/receiver pair, so `rng`
+/// should be a cryptographically secure generator.
+pub fn encode_with_rng<R: RngCore + ?Sized>(
+    message: &str,
+    sender_private: &PrivateKey,
+    receiver_public: &PublicKey,
+    rng: &mut R,
+) -> Result<String> {
+    encode_with_nonce(message, sender_private, receiver_public, rng.next_u64())
+}
+
 pub fn encode_with_nonce(
     message: &str,
     sender_private: &PrivateKey,
@@ -54,7 +68,7 @@ pub fn encode_with_nonce(
     encrypt_buf.resize(msg_len + pad_len, 0);
     let encrypted = Aes256CbcEnc::new(&key.into(), &iv.into())
         .encrypt_padded_mut::<Pkcs7>(&mut encrypt_buf, msg_len)
-        .map_err(|err| HiveError::Signing(format!("memo encrypt failed: {err}")))?
+        .map_err(|err| HiveError::signing(format!("memo encrypt failed: {err}")))?
         .to_vec();
 
     let payload = EncryptedMemoPayload {
@@ -76,7 +90,7 @@ pub fn decode(encoded: &str, receiver_private: &PrivateKey) -> Result<String> {
 
     let raw = bs58::decode(&encoded[1..])
         .into_vec()
-        .map_err(|err| HiveError::Signing(format!("invalid base58 memo: {err}")))?;
+        .map_err(|err| HiveError::signing(format!("invalid base58 memo: {err}")))?;
     let payload = deserialize_encrypted_memo(&raw)?;
 
     let my_public = receiver_private.public_key().to_string();
@@ -93,13 +107,13 @@ pub fn decode(encoded: &str, receiver_private: &PrivateKey) -> Result<String> {
 
     let (key, iv, check) = derive_aes_params(receiver_private, &other_public, payload.nonce);
     if check != payload.check {
-        return Err(HiveError::Signing("Invalid key".to_string()));
+        return Err(HiveError::signing("Invalid key".to_string()));
     }
 
     let mut decrypt_buf = payload.encrypted.clone();
     let decrypted = Aes256CbcDec::new(&key.into(), &iv.into())
         .decrypt_padded_mut::<Pkcs7>(&mut decrypt_buf)
-        .map_err(|err| HiveError::Signing(format!("memo decrypt failed: {err}")))?
+        .map_err(|err| HiveError::signing(format!("memo decrypt failed: {err}")))?
         .to_vec();
 
     // dhive first tries VString, then raw UTF-8 fallback.
@@ -111,7 +125,7 @@ pub fn decode(encoded: &str, receiver_private: &PrivateKey) -> Result<String> {
     }
 
     let text = String::from_utf8(decrypted)
-        .map_err(|err| HiveError::Signing(format!("memo plaintext is not valid UTF-8: {err}")))?;
+        .map_err(|err| HiveError::signing(format!("memo plaintext is not valid UTF-8: {err}")))?;
     Ok(format!("#{text}"))
 }
 
@@ -247,7 +261,10 @@ fn read_variable_binary(cursor: &mut &[u8]) -> Result<Vec<u8>> {
 #[cfg(test)]
 mod tests {
     use crate::crypto::keys::{PrivateKey, PublicKey};
-    use crate::crypto::memo::{decode, encode_with_nonce};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::crypto::memo::{decode, encode_with_nonce, encode_with_rng};
 
     #[test]
     fn encrypt_and_decrypt_round_trip() {
@@ -263,6 +280,24 @@ mod tests {
         assert_eq!(decoded, "#memo爱");
     }
 
+    #[test]
+    fn encode_with_rng_round_trips() {
+        let sender = PrivateKey::from_wif("5JdeC9P7Pbd1uGdFVEsJ41EkEnADbbHGq6p1BwFxm6txNBsQnsw")
+            .expect("valid sender key");
+        let recipient = sender.public_key();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let first = encode_with_rng("#hello", &sender, &recipient, &mut rng)
+            .expect("memo encode should succeed");
+        let second = encode_with_rng("#hello", &sender, &recipient, &mut rng)
+            .expect("memo encode should succeed");
+        assert_ne!(first, second);
+        assert_eq!(
+            decode(&first, &sender).expect("memo decode should succeed"),
+            "#hello"
+        );
+    }
+
     #[test]
     fn matches_dhive_encryption_vector() {
         let sender = PrivateKey::from_wif("5JdeC9P7Pbd1uGdFVEsJ41EkEnADbbHGq6p1BwFxm6txNBsQnsw")
@@ -0,0 +1,94 @@
+use serde::Deserialize;
+
+use crate::crypto::keys::{PrivateKey, PublicKey};
+use crate::crypto::signature::Signature;
+use crate::crypto::utils::sha256;
+use crate::error::Result;
+
+/// Signs `message` the way Hive Keychain's `requestSignBuffer` and dhive's
+/// `PrivateKey.sign(cryptoUtils.sha256(message))` do: sha256 of the message
+/// bytes, signed canonically, as 130 hex characters (recovery byte first).
+///
+/// A message that is a JSON-encoded Node `Buffer`
+/// (`{"type":"Buffer","data":[...]}`) is signed as those raw bytes, as
+/// Keychain does. Like [`PrivateKey::sign_message`], this never covers a
+/// chain id and cannot authorize a transaction.
+pub fn sign_buffer(message: &str, key: &PrivateKey) -> Result<String> {
+    let signature = key.sign_message(&message_bytes(message))?;
+    Ok(signature.to_hex())
+}
+
+/// Checks a [`sign_buffer`] (or Keychain) signature by recovering its signer
+/// and comparing it to `public_key`. Fails only when `signature_hex` is not a
+/// 65-byte hex signature.
+pub fn verify_buffer(message: &str, signature_hex: &str, public_key: &PublicKey) -> Result<bool> {
+    let signature = Signature::from_hex(signature_hex)?;
+    let digest = sha256(&message_bytes(message));
+    Ok(signature
+        .recover(&digest)
+        .is_ok_and(|signer| signer.compressed_bytes() == public_key.compressed_bytes()))
+}
+
+fn message_bytes(message: &str) -> Vec<u8> {
+    #[derive(Deserialize)]
+    struct NodeBuffer {
+        #[serde(rename = "type")]
+        kind: String,
+        data: Vec<u8>,
+    }
+
+    match serde_json::from_str::<NodeBuffer>(message) {
+        Ok(buffer) if buffer.kind == "Buffer" => buffer.data,
+        _ => message.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::keys::{PrivateKey, PublicKey};
+    use crate::crypto::message::{sign_buffer, verify_buffer};
+
+    const WIF: &str = "5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL";
+
+    #[test]
+    fn sign_buffer_is_deterministic_and_verifies() {
+        let key = PrivateKey::from_wif(WIF).expect("wif should parse");
+        let public_key = key.public_key();
+
+        let signature = sign_buffer("hive-rs login 1700000000", &key).expect("message should sign");
+        // Pinned output of this crate's signer; the nonce is deterministic.
+        assert_eq!(
+            signature,
+            "1f0c399d20c4980c1760470166a6d86a32df4a1cc7421fd5c2b451be0ece3e0b9e44a22422316eaa257593f7f17853ea4b46391ace2604811a5a857c716c5db0a5"
+        );
+        assert!(verify_buffer("hive-rs login 1700000000", &signature, &public_key).unwrap());
+        assert!(!verify_buffer("hive-rs login 1700000001", &signature, &public_key).unwrap());
+        assert!(verify_buffer("hive-rs", "zz", &public_key).is_err());
+    }
+
+    #[test]
+    fn verify_buffer_accepts_a_signature_made_outside_this_crate() {
+        // Produced by OpenSSL through Node's `crypto.sign("sha256", ...)` with a
+        // random nonce, normalized to low-s, recovery byte computed separately.
+        const SIGNATURE: &str = "1f6153cdda52f35b093f74c986400314af18fd912170f1f683cf5203c23de5b5ba4c1c6749e93c074011a80b3e3cf990eb0ee9efaa9c5b56c5fb8e594cf43edc16";
+        let public_key =
+            PublicKey::from_string("STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA")
+                .expect("public key should parse");
+
+        let message = "Sign in to hive-rs as alice at 2026-10-17T12:00:00Z";
+        assert!(verify_buffer(message, SIGNATURE, &public_key).unwrap());
+        assert!(!verify_buffer("Sign in to hive-rs as mallory", SIGNATURE, &public_key).unwrap());
+    }
+
+    #[test]
+    fn json_buffer_messages_sign_their_bytes() {
+        let key = PrivateKey::from_wif(WIF).expect("wif should parse");
+        let as_buffer = sign_buffer(r#"{"type":"Buffer","data":[104,105,118,101]}"#, &key)
+            .expect("buffer should sign");
+        assert_eq!(
+            as_buffer,
+            sign_buffer("hive", &key).expect("text should sign")
+        );
+        assert!(verify_buffer("hive", &as_buffer, &key.public_key()).unwrap());
+    }
+}
@@ -1,8 +1,11 @@
 pub mod keys;
 pub mod memo;
+pub mod message;
 pub mod signature;
 pub mod utils;
+pub mod verify;
 
 pub use keys::*;
 pub use memo::*;
 pub use signature::*;
+pub use verify::verify_block_signatures;
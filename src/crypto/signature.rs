@@ -1,6 +1,7 @@
-use secp256k1::{Message, Secp256k1};
+use secp256k1::Message;
 
 use crate::crypto::keys::{recoverable_from_signature, PublicKey};
+use crate::crypto::utils::secp_context;
 use crate::error::{HiveError, Result};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,7 +16,7 @@ impl Signature {
 
     pub fn from_compact(compact: [u8; 64], recovery_id: u8) -> Result<Self> {
         if recovery_id > 3 {
-            return Err(HiveError::Signing(format!(
+            return Err(HiveError::signing(format!(
                 "invalid recovery id {recovery_id}"
             )));
         }
@@ -27,10 +28,10 @@ impl Signature {
 
     pub fn from_hex(value: &str) -> Result<Self> {
         let bytes = hex::decode(value)
-            .map_err(|err| HiveError::Signing(format!("invalid signature hex: {err}")))?;
+            .map_err(|err| HiveError::signing(format!("invalid signature hex: {err}")))?;
         let data: [u8; 65] = bytes
             .try_into()
-            .map_err(|_| HiveError::Signing("signature must be 65 bytes".to_string()))?;
+            .map_err(|_| HiveError::signing("signature must be 65 bytes".to_string()))?;
         Ok(Self { data })
     }
 
@@ -61,14 +62,17 @@ impl Signature {
             && !(signature[32] == 0 && signature[33] & 0x80 == 0)
     }
 
+    /// The `STM` public key that produced this signature over `digest`, e.g.
+    /// to match a transaction signature against an account's authority keys.
+    /// Fails with [`HiveError::Signing`] when no key can be recovered, as for
+    /// an all-zero (null) signature.
     pub fn recover(&self, digest: &[u8; 32]) -> Result<PublicKey> {
         let recoverable = recoverable_from_signature(self)?;
         let message = Message::from_digest_slice(digest)
-            .map_err(|err| HiveError::Signing(format!("invalid digest: {err}")))?;
-        let secp = Secp256k1::verification_only();
-        let key = secp
+            .map_err(|err| HiveError::secp("invalid digest", err))?;
+        let key = secp_context()
             .recover_ecdsa(&message, &recoverable)
-            .map_err(|err| HiveError::Signing(format!("recover failed: {err}")))?;
+            .map_err(|err| HiveError::secp("recover failed", err))?;
         Ok(PublicKey::from_secp256k1(key, "STM"))
     }
 }
@@ -77,6 +81,7 @@ impl Signature {
 mod tests {
     use crate::crypto::keys::{KeyRole, PrivateKey};
     use crate::crypto::signature::Signature;
+    use crate::error::HiveError;
 
     #[test]
     fn sign_and_recover_matches_known_vector() {
@@ -101,6 +106,24 @@ mod tests {
         assert!(recovered.verify(&digest, &signature));
     }
 
+    #[test]
+    fn recover_finds_signer_and_rejects_null_signature() {
+        let key = PrivateKey::from_login("foo", "barman", KeyRole::Active).expect("valid key");
+        let digest = [7_u8; 32];
+        let signature = key.sign(&digest).expect("signing should succeed");
+
+        let recovered = signature.recover(&digest).expect("recovery should succeed");
+        assert_eq!(recovered, key.public_key());
+        assert_eq!(recovered.prefix(), "STM");
+        assert_ne!(signature.recover(&[8_u8; 32]).ok(), Some(key.public_key()));
+
+        let null = Signature::from_bytes([0_u8; 65]);
+        assert!(matches!(
+            null.recover(&digest),
+            Err(HiveError::Signing { .. })
+        ));
+    }
+
     #[test]
     fn signature_hex_round_trip() {
         let hex = "20173e52773241c69a8870c796634a537cb543e088c8aa13b89d46e33c0227c62e4afda5266272bd53c4e3e7f417af4d811b3fae5bd069c94447f1fdc48a525b8d";
@@ -1,6 +1,16 @@
+use std::sync::OnceLock;
+
 use ripemd::{Digest as RipemdDigest, Ripemd160};
+use secp256k1::{All, Secp256k1};
 use sha2::{Sha256, Sha512};
 
+static SECP256K1: OnceLock<Secp256k1<All>> = OnceLock::new();
+
+/// Process-wide secp256k1 context; building one per call is comparatively slow.
+pub(crate) fn secp_context() -> &'static Secp256k1<All> {
+    SECP256K1.get_or_init(Secp256k1::new)
+}
+
 pub fn sha256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(data);
@@ -0,0 +1,161 @@
+use crate::crypto::keys::PublicKey;
+use crate::crypto::signature::Signature;
+use crate::error::Result;
+use crate::serialization::serializer::transaction_digest;
+use crate::types::{Authority, ChainId, RequiredAuthorities, SignedBlock, SignedTransaction};
+
+/// Checks every transaction in `block` against the authorities its operations
+/// require, returning one flag per transaction in block order.
+///
+/// `resolver` maps an account name to the authority that should be checked for
+/// it (typically cached account data) and is also used to expand
+/// `account_auths` one level deep. Unknown accounts, unreadable signatures and
+/// unmet thresholds all yield `false`; only serialization failures are errors.
+pub fn verify_block_signatures(
+    block: &SignedBlock,
+    chain_id: &ChainId,
+    resolver: impl Fn(&str) -> Option<Authority>,
+) -> Result<Vec<bool>> {
+    block
+        .transactions
+        .iter()
+        .map(|transaction| verify_transaction_signatures(transaction, chain_id, &resolver))
+        .collect()
+}
+
+pub(crate) fn verify_transaction_signatures(
+    transaction: &SignedTransaction,
+    chain_id: &ChainId,
+    resolver: &impl Fn(&str) -> Option<Authority>,
+) -> Result<bool> {
+    let digest = transaction_digest(&transaction.unsigned(), chain_id)?;
+    let Some(signers) = recover_signers(&transaction.signatures, &digest) else {
+        return Ok(false);
+    };
+
+    let required = RequiredAuthorities::for_operations(&transaction.operations);
+    let accounts_satisfied = required
+        .owner
+        .iter()
+        .chain(&required.active)
+        .chain(&required.posting)
+        .all(|account| {
+            resolver(account)
+                .is_some_and(|authority| authority_satisfied(&authority, &signers, resolver, 1))
+        });
+    let others_satisfied = required
+        .other
+        .iter()
+        .all(|authority| authority_satisfied(authority, &signers, resolver, 1));
+
+    Ok(accounts_satisfied && others_satisfied)
+}
+
+/// Recovers the signing keys, or `None` if any signature is malformed.
+pub(crate) fn recover_signers(signatures: &[String], digest: &[u8; 32]) -> Option<Vec<PublicKey>> {
+    signatures
+        .iter()
+        .map(|signature| {
+            Signature::from_hex(signature)
+                .and_then(|signature| signature.recover(digest))
+                .ok()
+        })
+        .collect()
+}
+
+/// Sums the weights of `authority` entries backed by `signers`, following
+/// account references up to `depth` levels through `resolver`.
+pub(crate) fn authority_satisfied(
+    authority: &Authority,
+    signers: &[PublicKey],
+    resolver: &impl Fn(&str) -> Option<Authority>,
+    depth: u32,
+) -> bool {
+    let mut weight = 0_u64;
+    for (key, key_weight) in &authority.key_auths {
+        let Ok(key) = PublicKey::from_string(key) else {
+            continue;
+        };
+        if signers.iter().any(|signer| signer.key == key.key) {
+            weight += u64::from(*key_weight);
+        }
+    }
+
+    if depth > 0 {
+        for (account, account_weight) in &authority.account_auths {
+            let nested = resolver(account)
+                .is_some_and(|nested| authority_satisfied(&nested, signers, resolver, depth - 1));
+            if nested {
+                weight += u64::from(*account_weight);
+            }
+        }
+    }
+
+    weight >= u64::from(authority.weight_threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::keys::{sign_transaction, PrivateKey};
+    use crate::crypto::verify::verify_block_signatures;
+    use crate::types::{
+        Asset, Authority, ChainId, Operation, SignedBlock, Transaction, TransferOperation,
+        VoteOperation,
+    };
+
+    #[test]
+    fn flags_transaction_with_corrupted_signature() {
+        let key = PrivateKey::from_wif("5KG4sr3rMH1QuduYj79p36h7PrEeZakHEPjB9NkLWqgw19DDieL")
+            .expect("wif should parse");
+        let chain_id = ChainId::mainnet();
+        let transaction = |operation: Operation| Transaction {
+            ref_block_num: 1234,
+            ref_block_prefix: 1122334455,
+            expiration: "2024-01-01T00:01:00".to_string(),
+            operations: vec![operation],
+            extensions: vec![],
+        };
+
+        let valid = sign_transaction(
+            &transaction(Operation::Vote(VoteOperation {
+                voter: "alice".to_string(),
+                author: "bob".to_string(),
+                permlink: "post".to_string(),
+                weight: 10000,
+            })),
+            &[&key],
+            &chain_id,
+        )
+        .expect("vote should sign");
+        let mut corrupted = sign_transaction(
+            &transaction(Operation::Transfer(TransferOperation {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: Asset::hive(1.0),
+                memo: String::new(),
+            })),
+            &[&key],
+            &chain_id,
+        )
+        .expect("transfer should sign");
+        let mut signature = corrupted.signatures[0].clone().into_bytes();
+        signature[10] = if signature[10] == b'0' { b'1' } else { b'0' };
+        corrupted.signatures[0] = String::from_utf8(signature).expect("hex stays utf8");
+
+        let block = SignedBlock {
+            transactions: vec![valid, corrupted],
+            ..Default::default()
+        };
+        let authority = Authority {
+            weight_threshold: 1,
+            account_auths: vec![],
+            key_auths: vec![(key.public_key().to_string(), 1)],
+        };
+
+        let results = verify_block_signatures(&block, &chain_id, |account| {
+            (account == "alice").then(|| authority.clone())
+        })
+        .expect("verification should run");
+        assert_eq!(results, [true, false]);
+    }
+}
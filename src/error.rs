@@ -10,17 +10,28 @@ pub enum HiveError {
         data: Option<Value>,
     },
 
-    #[error("Transport error: {0}")]
-    Transport(String),
+    #[error("Transport error: {message}")]
+    Transport {
+        message: String,
+        #[source]
+        source: Option<reqwest::Error>,
+    },
 
     #[error("Serialization error: {0}")]
     Serialization(String),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Invalid key: {0}")]
     InvalidKey(String),
 
-    #[error("Signing error: {0}")]
-    Signing(String),
+    #[error("Signing error: {message}")]
+    Signing {
+        message: String,
+        #[source]
+        source: Option<secp256k1::Error>,
+    },
 
     #[error("All nodes failed")]
     AllNodesFailed,
@@ -37,22 +48,49 @@ pub enum HiveError {
 
 pub type Result<T> = std::result::Result<T, HiveError>;
 
+impl HiveError {
+    pub fn transport(message: impl Into<String>) -> Self {
+        Self::Transport {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn signing(message: impl Into<String>) -> Self {
+        Self::Signing {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// A JSON payload error that did not come from serde itself, e.g. a
+    /// structurally valid document missing an expected member.
+    pub fn json(message: impl std::fmt::Display) -> Self {
+        Self::Json(serde::de::Error::custom(message))
+    }
+
+    /// A signing error caused by `source`, described as `"{context}: {source}"`.
+    pub(crate) fn secp(context: &str, source: secp256k1::Error) -> Self {
+        Self::Signing {
+            message: format!("{context}: {source}"),
+            source: Some(source),
+        }
+    }
+}
+
 impl From<reqwest::Error> for HiveError {
     fn from(value: reqwest::Error) -> Self {
         if value.is_timeout() {
             Self::Timeout
         } else {
-            Self::Transport(value.to_string())
+            Self::Transport {
+                message: value.to_string(),
+                source: Some(value),
+            }
         }
     }
 }
 
-impl From<serde_json::Error> for HiveError {
-    fn from(value: serde_json::Error) -> Self {
-        Self::Serialization(value.to_string())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::HiveError;
@@ -65,10 +103,11 @@ mod tests {
                 message: "boom".to_string(),
                 data: None,
             },
-            HiveError::Transport("io".to_string()),
-            HiveError::Serialization("bad json".to_string()),
+            HiveError::transport("io"),
+            HiveError::Serialization("bad bytes".to_string()),
+            HiveError::Json(serde_json::from_str::<u8>("x").unwrap_err()),
             HiveError::InvalidKey("bad key".to_string()),
-            HiveError::Signing("failed".to_string()),
+            HiveError::signing("failed"),
             HiveError::AllNodesFailed,
             HiveError::Timeout,
             HiveError::InvalidAsset("bad amount".to_string()),
@@ -79,4 +118,44 @@ mod tests {
             assert!(!err.to_string().is_empty());
         }
     }
+
+    #[tokio::test]
+    async fn transport_error_keeps_reqwest_source() {
+        use std::error::Error as _;
+
+        let reqwest_err = reqwest::Client::new()
+            .get("http://127.0.0.1:1")
+            .send()
+            .await
+            .expect_err("nothing listens on port 1");
+        let expected = reqwest_err.to_string();
+        let err = HiveError::from(reqwest_err);
+
+        assert!(matches!(err, HiveError::Transport { .. }));
+        let source = err.source().expect("transport error should have a source");
+        assert!(source.is::<reqwest::Error>());
+        assert_eq!(source.to_string(), expected);
+        assert!(HiveError::transport("io").source().is_none());
+    }
+
+    #[test]
+    fn json_and_binary_failures_use_distinct_variants() {
+        use crate::serialization::types::write_asset;
+        use crate::types::{Asset, AssetSymbol, Operation};
+
+        let err = Operation::from_op_array(&serde_json::json!(["transfer", {"from": 1}]))
+            .expect_err("malformed body should fail");
+        assert!(matches!(err, HiveError::Json(_)), "{err:?}");
+        let err = HiveError::from(serde_json::from_str::<Asset>("{").unwrap_err());
+        assert!(matches!(err, HiveError::Json(_)));
+
+        let asset = Asset {
+            amount: 1,
+            precision: 3,
+            symbol: AssetSymbol::Custom("TOOLONGSYM".to_string()),
+            display_symbol: None,
+        };
+        let err = write_asset(&mut Vec::new(), &asset).expect_err("symbol should be too long");
+        assert!(matches!(err, HiveError::Serialization(_)), "{err:?}");
+    }
 }
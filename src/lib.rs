@@ -1,21 +1,32 @@
 pub mod api;
+pub mod authority_cache;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 pub mod crypto;
 pub mod error;
 pub mod serialization;
+#[cfg(test)]
+mod test_support;
 pub mod transport;
 pub mod types;
 pub mod utils;
 
-pub use client::{Client, ClientOptions};
+pub use authority_cache::AuthorityCache;
+pub use client::{Client, ClientOptions, ConfirmationDepth, Ping, Preflight, WaitOptions};
 pub use crypto::keys::{sign_transaction, KeyRole, PrivateKey, PublicKey};
 pub use crypto::memo;
 pub use crypto::signature::Signature;
 pub use error::{HiveError, Result};
+pub use serialization::deserializer::{deserialize_transaction, HiveDeserialize};
 pub use serialization::serializer::{
-    generate_trx_id, serialize_transaction, transaction_digest, HiveSerialize,
+    generate_trx_id, generate_trx_id_for, serialize_transaction, serialize_transaction_for,
+    serialize_transaction_into, transaction_digest, HiveSerialize,
 };
 pub use types::*;
 pub use utils::{
-    build_witness_update_op, get_vesting_share_price, get_vests, make_bit_mask_filter, unique_nonce,
+    apply_operation_to_balances, build_witness_update_op, get_vesting_share_price, get_vests,
+    make_bit_mask_filter, make_bit_mask_filter_from_names, power_down_schedule, required_roles,
+    unique_nonce, vests_to_hp, BalanceState, NonceGenerator, PowerDownTranche,
+    WitnessSetPropsBuilder,
 };
@@ -1,10 +1,134 @@
+use chrono::DateTime;
+
+use crate::crypto::keys::PublicKey;
 use crate::error::{HiveError, Result};
-use crate::serialization::types::read_varint32;
+use crate::serialization::types::{format_hive_time, read_string, read_varint32};
+use crate::types::{
+    AccountCreateOperation, AccountCreateWithDelegationOperation, AccountUpdate2Operation,
+    AccountUpdateOperation, AccountWitnessProxyOperation, AccountWitnessVoteOperation, Asset,
+    AssetSymbol, Authority, BeneficiaryRoute, BlockHeader, CancelTransferFromSavingsOperation,
+    ChainProperties, ChangeRecoveryAccountOperation, ClaimAccountOperation,
+    ClaimRewardBalanceOperation, CollateralizedConvertOperation, CommentOperation,
+    CommentOptionsExtension, CommentOptionsOperation, ConvertOperation,
+    CreateClaimedAccountOperation, CreateProposalOperation, CustomBinaryOperation,
+    CustomJsonOperation, CustomOperation, DeclineVotingRightsOperation,
+    DelegateVestingSharesOperation, DeleteCommentOperation, EscrowApproveOperation,
+    EscrowDisputeOperation, EscrowReleaseOperation, EscrowTransferOperation, FeedPublishOperation,
+    LimitOrderCancelOperation, LimitOrderCreate2Operation, LimitOrderCreateOperation, Operation,
+    Price, RecoverAccountOperation, RecurrentTransferOperation, RemoveProposalOperation,
+    ReportOverProductionOperation, RequestAccountRecoveryOperation, ResetAccountOperation,
+    SetResetAccountOperation, SetWithdrawVestingRouteOperation, SignedBlockHeader, Transaction,
+    TransferFromSavingsOperation, TransferOperation, TransferToSavingsOperation,
+    TransferToVestingOperation, UpdateProposalExtension, UpdateProposalOperation,
+    UpdateProposalVotesOperation, VoteOperation, WithdrawVestingOperation,
+    WitnessSetPropertiesOperation, WitnessUpdateOperation,
+};
 
 pub trait HiveDeserialize: Sized {
     fn hive_deserialize(cursor: &mut &[u8]) -> Result<Self>;
 }
 
+impl HiveDeserialize for Operation {
+    fn hive_deserialize(cursor: &mut &[u8]) -> Result<Self> {
+        let id = read_varint32(cursor)?;
+        let operation = match id {
+            0 => Operation::Vote(deserialize_vote(cursor)?),
+            1 => Operation::Comment(deserialize_comment(cursor)?),
+            2 => Operation::Transfer(deserialize_transfer(cursor)?),
+            3 => Operation::TransferToVesting(deserialize_transfer_to_vesting(cursor)?),
+            4 => Operation::WithdrawVesting(deserialize_withdraw_vesting(cursor)?),
+            5 => Operation::LimitOrderCreate(deserialize_limit_order_create(cursor)?),
+            6 => Operation::LimitOrderCancel(deserialize_limit_order_cancel(cursor)?),
+            7 => Operation::FeedPublish(deserialize_feed_publish(cursor)?),
+            8 => Operation::Convert(deserialize_convert(cursor)?),
+            9 => Operation::AccountCreate(deserialize_account_create(cursor)?),
+            10 => Operation::AccountUpdate(deserialize_account_update(cursor)?),
+            11 => Operation::WitnessUpdate(deserialize_witness_update(cursor)?),
+            12 => Operation::AccountWitnessVote(deserialize_account_witness_vote(cursor)?),
+            13 => Operation::AccountWitnessProxy(deserialize_account_witness_proxy(cursor)?),
+            15 => Operation::Custom(deserialize_custom(cursor)?),
+            16 => Operation::ReportOverProduction(deserialize_report_over_production(cursor)?),
+            17 => Operation::DeleteComment(deserialize_delete_comment(cursor)?),
+            18 => Operation::CustomJson(deserialize_custom_json(cursor)?),
+            19 => Operation::CommentOptions(deserialize_comment_options(cursor)?),
+            20 => {
+                Operation::SetWithdrawVestingRoute(deserialize_set_withdraw_vesting_route(cursor)?)
+            }
+            21 => Operation::LimitOrderCreate2(deserialize_limit_order_create2(cursor)?),
+            22 => Operation::ClaimAccount(deserialize_claim_account(cursor)?),
+            23 => Operation::CreateClaimedAccount(deserialize_create_claimed_account(cursor)?),
+            24 => Operation::RequestAccountRecovery(deserialize_request_account_recovery(cursor)?),
+            25 => Operation::RecoverAccount(deserialize_recover_account(cursor)?),
+            26 => Operation::ChangeRecoveryAccount(deserialize_change_recovery_account(cursor)?),
+            27 => Operation::EscrowTransfer(deserialize_escrow_transfer(cursor)?),
+            28 => Operation::EscrowDispute(deserialize_escrow_dispute(cursor)?),
+            29 => Operation::EscrowRelease(deserialize_escrow_release(cursor)?),
+            31 => Operation::EscrowApprove(deserialize_escrow_approve(cursor)?),
+            32 => Operation::TransferToSavings(deserialize_transfer_to_savings(cursor)?),
+            33 => Operation::TransferFromSavings(deserialize_transfer_from_savings(cursor)?),
+            34 => Operation::CancelTransferFromSavings(deserialize_cancel_transfer_from_savings(
+                cursor,
+            )?),
+            35 => Operation::CustomBinary(deserialize_custom_binary(cursor)?),
+            36 => Operation::DeclineVotingRights(deserialize_decline_voting_rights(cursor)?),
+            37 => Operation::ResetAccount(deserialize_reset_account(cursor)?),
+            38 => Operation::SetResetAccount(deserialize_set_reset_account(cursor)?),
+            39 => Operation::ClaimRewardBalance(deserialize_claim_reward_balance(cursor)?),
+            40 => Operation::DelegateVestingShares(deserialize_delegate_vesting_shares(cursor)?),
+            41 => Operation::AccountCreateWithDelegation(
+                deserialize_account_create_with_delegation(cursor)?,
+            ),
+            42 => Operation::WitnessSetProperties(deserialize_witness_set_properties(cursor)?),
+            43 => Operation::AccountUpdate2(deserialize_account_update2(cursor)?),
+            44 => Operation::CreateProposal(deserialize_create_proposal(cursor)?),
+            45 => Operation::UpdateProposalVotes(deserialize_update_proposal_votes(cursor)?),
+            46 => Operation::RemoveProposal(deserialize_remove_proposal(cursor)?),
+            47 => Operation::UpdateProposal(deserialize_update_proposal(cursor)?),
+            48 => Operation::CollateralizedConvert(deserialize_collateralized_convert(cursor)?),
+            49 => Operation::RecurrentTransfer(deserialize_recurrent_transfer(cursor)?),
+            14 | 30 => {
+                return Err(HiveError::Serialization(format!(
+                    "pow operation (id {id}) deserialization is unsupported"
+                )))
+            }
+            // The body of an unmodelled operation is not length-prefixed, so
+            // there is no way to skip past it.
+            _ => {
+                return Err(HiveError::Serialization(format!(
+                    "cannot deserialize unknown operation id {id}"
+                )))
+            }
+        };
+        Ok(operation)
+    }
+}
+
+impl HiveDeserialize for Transaction {
+    fn hive_deserialize(cursor: &mut &[u8]) -> Result<Self> {
+        Ok(Transaction {
+            ref_block_num: read_u16(cursor)?,
+            ref_block_prefix: read_u32(cursor)?,
+            expiration: read_date(cursor)?,
+            operations: read_array(cursor, Operation::hive_deserialize)?,
+            extensions: read_array(cursor, read_string)?,
+        })
+    }
+}
+
+/// The inverse of [`serialize_transaction`](crate::serialization::serialize_transaction).
+/// Fails if `bytes` holds anything after the transaction.
+pub fn deserialize_transaction(bytes: &[u8]) -> Result<Transaction> {
+    let mut cursor = bytes;
+    let transaction = Transaction::hive_deserialize(&mut cursor)?;
+    if !cursor.is_empty() {
+        return Err(HiveError::Serialization(format!(
+            "{} trailing bytes after transaction",
+            cursor.len()
+        )));
+    }
+    Ok(transaction)
+}
+
 pub fn read_u8(cursor: &mut &[u8]) -> Result<u8> {
     if cursor.is_empty() {
         return Err(HiveError::Serialization(
@@ -73,12 +197,662 @@ pub fn read_variable_binary(cursor: &mut &[u8]) -> Result<Vec<u8>> {
     Ok(value)
 }
 
+pub fn read_i16(cursor: &mut &[u8]) -> Result<i16> {
+    read_u16(cursor).map(|value| value as i16)
+}
+
+pub fn read_i64(cursor: &mut &[u8]) -> Result<i64> {
+    read_u64(cursor).map(|value| value as i64)
+}
+
+pub fn read_bool(cursor: &mut &[u8]) -> Result<bool> {
+    match read_u8(cursor)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => Err(HiveError::Serialization(format!(
+            "invalid bool byte {other}"
+        ))),
+    }
+}
+
+pub fn read_fixed_binary(cursor: &mut &[u8], len: usize) -> Result<Vec<u8>> {
+    if cursor.len() < len {
+        return Err(HiveError::Serialization(format!(
+            "buffer underflow for {len}-byte field"
+        )));
+    }
+    let value = cursor[..len].to_vec();
+    *cursor = &cursor[len..];
+    Ok(value)
+}
+
+pub fn read_date(cursor: &mut &[u8]) -> Result<String> {
+    let timestamp = read_u32(cursor)?;
+    let date = DateTime::from_timestamp(timestamp as i64, 0)
+        .ok_or_else(|| HiveError::Serialization(format!("invalid timestamp {timestamp}")))?;
+    Ok(format_hive_time(date))
+}
+
+/// Reads a compressed key, rendered with the mainnet `STM` prefix.
+pub fn read_public_key(cursor: &mut &[u8]) -> Result<String> {
+    let bytes: [u8; 33] = read_fixed_binary(cursor, 33)?
+        .try_into()
+        .map_err(|_| HiveError::Serialization("invalid public key bytes".to_string()))?;
+    Ok(PublicKey::from_bytes(bytes, "STM")?.to_string())
+}
+
+/// Reads an asset, mapping the legacy and testnet wire symbols to
+/// `HIVE`/`HBD`.
+pub fn read_asset(cursor: &mut &[u8]) -> Result<Asset> {
+    let amount = read_i64(cursor)?;
+    let precision = read_u8(cursor)?;
+    let symbol_bytes = read_fixed_binary(cursor, 7)?;
+    let end = symbol_bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(symbol_bytes.len());
+    let symbol = std::str::from_utf8(&symbol_bytes[..end])
+        .map_err(|err| HiveError::Serialization(format!("invalid asset symbol: {err}")))?;
+    let symbol = match symbol {
+        "STEEM" | "TESTS" | "HIVE" => AssetSymbol::Hive,
+        "SBD" | "TBD" | "HBD" => AssetSymbol::Hbd,
+        "VESTS" => AssetSymbol::Vests,
+        other => AssetSymbol::Custom(other.to_string()),
+    };
+    Ok(Asset {
+        amount,
+        precision,
+        symbol,
+        display_symbol: None,
+    })
+}
+
+pub fn read_optional<T, F>(cursor: &mut &[u8], mut deserialize: F) -> Result<Option<T>>
+where
+    F: FnMut(&mut &[u8]) -> Result<T>,
+{
+    if read_bool(cursor)? {
+        deserialize(cursor).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn read_array<T, F>(cursor: &mut &[u8], mut deserialize: F) -> Result<Vec<T>>
+where
+    F: FnMut(&mut &[u8]) -> Result<T>,
+{
+    let len = read_varint32(cursor)? as usize;
+    // Every element takes at least one byte, so cap the preallocation by
+    // what is left rather than trusting the encoded length.
+    let mut items = Vec::with_capacity(len.min(cursor.len()));
+    for _ in 0..len {
+        items.push(deserialize(cursor)?);
+    }
+    Ok(items)
+}
+
+pub fn read_authority(cursor: &mut &[u8]) -> Result<Authority> {
+    Ok(Authority {
+        weight_threshold: read_u32(cursor)?,
+        account_auths: read_array(cursor, |c| Ok((read_string(c)?, read_u16(c)?)))?,
+        key_auths: read_array(cursor, |c| Ok((read_public_key(c)?, read_u16(c)?)))?,
+    })
+}
+
+pub fn read_price(cursor: &mut &[u8]) -> Result<Price> {
+    Ok(Price {
+        base: read_asset(cursor)?,
+        quote: read_asset(cursor)?,
+    })
+}
+
+pub fn read_chain_properties(cursor: &mut &[u8]) -> Result<ChainProperties> {
+    Ok(ChainProperties {
+        account_creation_fee: read_asset(cursor)?,
+        maximum_block_size: read_u32(cursor)?,
+        hbd_interest_rate: read_u16(cursor)?,
+    })
+}
+
+fn read_void_extensions(cursor: &mut &[u8]) -> Result<Vec<()>> {
+    if read_varint32(cursor)? != 0 {
+        return Err(HiveError::Serialization(
+            "void extensions must be empty".to_string(),
+        ));
+    }
+    Ok(Vec::new())
+}
+
+fn read_fixed_binary_hex(cursor: &mut &[u8], len: usize) -> Result<String> {
+    read_fixed_binary(cursor, len).map(hex::encode)
+}
+
+fn read_signed_block_header(cursor: &mut &[u8]) -> Result<SignedBlockHeader> {
+    let header = BlockHeader {
+        previous: read_fixed_binary_hex(cursor, 20)?,
+        timestamp: read_date(cursor)?,
+        witness: read_string(cursor)?,
+        transaction_merkle_root: read_fixed_binary_hex(cursor, 20)?,
+        extensions: Vec::new(),
+    };
+    read_void_extensions(cursor)?;
+    Ok(SignedBlockHeader {
+        header,
+        witness_signature: read_fixed_binary_hex(cursor, 65)?,
+    })
+}
+
+fn deserialize_vote(cursor: &mut &[u8]) -> Result<VoteOperation> {
+    Ok(VoteOperation {
+        voter: read_string(cursor)?,
+        author: read_string(cursor)?,
+        permlink: read_string(cursor)?,
+        weight: read_i16(cursor)?,
+    })
+}
+
+fn deserialize_comment(cursor: &mut &[u8]) -> Result<CommentOperation> {
+    Ok(CommentOperation {
+        parent_author: read_string(cursor)?,
+        parent_permlink: read_string(cursor)?,
+        author: read_string(cursor)?,
+        permlink: read_string(cursor)?,
+        title: read_string(cursor)?,
+        body: read_string(cursor)?,
+        json_metadata: read_string(cursor)?,
+    })
+}
+
+fn deserialize_transfer(cursor: &mut &[u8]) -> Result<TransferOperation> {
+    Ok(TransferOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        amount: read_asset(cursor)?,
+        memo: read_string(cursor)?,
+    })
+}
+
+fn deserialize_transfer_to_vesting(cursor: &mut &[u8]) -> Result<TransferToVestingOperation> {
+    Ok(TransferToVestingOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        amount: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_withdraw_vesting(cursor: &mut &[u8]) -> Result<WithdrawVestingOperation> {
+    Ok(WithdrawVestingOperation {
+        account: read_string(cursor)?,
+        vesting_shares: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_limit_order_create(cursor: &mut &[u8]) -> Result<LimitOrderCreateOperation> {
+    Ok(LimitOrderCreateOperation {
+        owner: read_string(cursor)?,
+        orderid: read_u32(cursor)?,
+        amount_to_sell: read_asset(cursor)?,
+        min_to_receive: read_asset(cursor)?,
+        fill_or_kill: read_bool(cursor)?,
+        expiration: read_date(cursor)?,
+    })
+}
+
+fn deserialize_limit_order_cancel(cursor: &mut &[u8]) -> Result<LimitOrderCancelOperation> {
+    Ok(LimitOrderCancelOperation {
+        owner: read_string(cursor)?,
+        orderid: read_u32(cursor)?,
+    })
+}
+
+fn deserialize_feed_publish(cursor: &mut &[u8]) -> Result<FeedPublishOperation> {
+    Ok(FeedPublishOperation {
+        publisher: read_string(cursor)?,
+        exchange_rate: read_price(cursor)?,
+    })
+}
+
+fn deserialize_convert(cursor: &mut &[u8]) -> Result<ConvertOperation> {
+    Ok(ConvertOperation {
+        owner: read_string(cursor)?,
+        requestid: read_u32(cursor)?,
+        amount: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_account_create(cursor: &mut &[u8]) -> Result<AccountCreateOperation> {
+    Ok(AccountCreateOperation {
+        fee: read_asset(cursor)?,
+        creator: read_string(cursor)?,
+        new_account_name: read_string(cursor)?,
+        owner: read_authority(cursor)?,
+        active: read_authority(cursor)?,
+        posting: read_authority(cursor)?,
+        memo_key: read_public_key(cursor)?,
+        json_metadata: read_string(cursor)?,
+    })
+}
+
+fn deserialize_account_update(cursor: &mut &[u8]) -> Result<AccountUpdateOperation> {
+    Ok(AccountUpdateOperation {
+        account: read_string(cursor)?,
+        owner: read_optional(cursor, read_authority)?,
+        active: read_optional(cursor, read_authority)?,
+        posting: read_optional(cursor, read_authority)?,
+        memo_key: read_public_key(cursor)?,
+        json_metadata: read_string(cursor)?,
+    })
+}
+
+fn deserialize_witness_update(cursor: &mut &[u8]) -> Result<WitnessUpdateOperation> {
+    Ok(WitnessUpdateOperation {
+        owner: read_string(cursor)?,
+        url: read_string(cursor)?,
+        block_signing_key: read_public_key(cursor)?,
+        props: read_chain_properties(cursor)?,
+        fee: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_account_witness_vote(cursor: &mut &[u8]) -> Result<AccountWitnessVoteOperation> {
+    Ok(AccountWitnessVoteOperation {
+        account: read_string(cursor)?,
+        witness: read_string(cursor)?,
+        approve: read_bool(cursor)?,
+    })
+}
+
+fn deserialize_account_witness_proxy(cursor: &mut &[u8]) -> Result<AccountWitnessProxyOperation> {
+    Ok(AccountWitnessProxyOperation {
+        account: read_string(cursor)?,
+        proxy: read_string(cursor)?,
+    })
+}
+
+fn deserialize_custom(cursor: &mut &[u8]) -> Result<CustomOperation> {
+    Ok(CustomOperation {
+        required_auths: read_array(cursor, read_string)?,
+        id: read_u16(cursor)?,
+        data: read_variable_binary(cursor)?,
+    })
+}
+
+fn deserialize_report_over_production(cursor: &mut &[u8]) -> Result<ReportOverProductionOperation> {
+    Ok(ReportOverProductionOperation {
+        reporter: read_string(cursor)?,
+        first_block: read_signed_block_header(cursor)?,
+        second_block: read_signed_block_header(cursor)?,
+    })
+}
+
+fn deserialize_delete_comment(cursor: &mut &[u8]) -> Result<DeleteCommentOperation> {
+    Ok(DeleteCommentOperation {
+        author: read_string(cursor)?,
+        permlink: read_string(cursor)?,
+    })
+}
+
+fn deserialize_custom_json(cursor: &mut &[u8]) -> Result<CustomJsonOperation> {
+    Ok(CustomJsonOperation {
+        required_auths: read_array(cursor, read_string)?,
+        required_posting_auths: read_array(cursor, read_string)?,
+        id: read_string(cursor)?,
+        json: read_string(cursor)?,
+    })
+}
+
+fn deserialize_comment_options(cursor: &mut &[u8]) -> Result<CommentOptionsOperation> {
+    Ok(CommentOptionsOperation {
+        author: read_string(cursor)?,
+        permlink: read_string(cursor)?,
+        max_accepted_payout: read_asset(cursor)?,
+        percent_hbd: read_u16(cursor)?,
+        allow_votes: read_bool(cursor)?,
+        allow_curation_rewards: read_bool(cursor)?,
+        extensions: read_array(cursor, |c| match read_varint32(c)? {
+            0 => Ok(CommentOptionsExtension::Beneficiaries {
+                beneficiaries: read_array(c, |cc| {
+                    Ok(BeneficiaryRoute {
+                        account: read_string(cc)?,
+                        weight: read_u16(cc)?,
+                    })
+                })?,
+            }),
+            tag => Err(HiveError::Serialization(format!(
+                "unknown comment_options extension {tag}"
+            ))),
+        })?,
+    })
+}
+
+fn deserialize_set_withdraw_vesting_route(
+    cursor: &mut &[u8],
+) -> Result<SetWithdrawVestingRouteOperation> {
+    Ok(SetWithdrawVestingRouteOperation {
+        from_account: read_string(cursor)?,
+        to_account: read_string(cursor)?,
+        percent: read_u16(cursor)?,
+        auto_vest: read_bool(cursor)?,
+    })
+}
+
+fn deserialize_limit_order_create2(cursor: &mut &[u8]) -> Result<LimitOrderCreate2Operation> {
+    Ok(LimitOrderCreate2Operation {
+        owner: read_string(cursor)?,
+        orderid: read_u32(cursor)?,
+        amount_to_sell: read_asset(cursor)?,
+        exchange_rate: read_price(cursor)?,
+        fill_or_kill: read_bool(cursor)?,
+        expiration: read_date(cursor)?,
+    })
+}
+
+fn deserialize_claim_account(cursor: &mut &[u8]) -> Result<ClaimAccountOperation> {
+    Ok(ClaimAccountOperation {
+        creator: read_string(cursor)?,
+        fee: read_asset(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_create_claimed_account(cursor: &mut &[u8]) -> Result<CreateClaimedAccountOperation> {
+    Ok(CreateClaimedAccountOperation {
+        creator: read_string(cursor)?,
+        new_account_name: read_string(cursor)?,
+        owner: read_authority(cursor)?,
+        active: read_authority(cursor)?,
+        posting: read_authority(cursor)?,
+        memo_key: read_public_key(cursor)?,
+        json_metadata: read_string(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_request_account_recovery(
+    cursor: &mut &[u8],
+) -> Result<RequestAccountRecoveryOperation> {
+    Ok(RequestAccountRecoveryOperation {
+        recovery_account: read_string(cursor)?,
+        account_to_recover: read_string(cursor)?,
+        new_owner_authority: read_authority(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_recover_account(cursor: &mut &[u8]) -> Result<RecoverAccountOperation> {
+    Ok(RecoverAccountOperation {
+        account_to_recover: read_string(cursor)?,
+        new_owner_authority: read_authority(cursor)?,
+        recent_owner_authority: read_authority(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_change_recovery_account(
+    cursor: &mut &[u8],
+) -> Result<ChangeRecoveryAccountOperation> {
+    Ok(ChangeRecoveryAccountOperation {
+        account_to_recover: read_string(cursor)?,
+        new_recovery_account: read_string(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_escrow_transfer(cursor: &mut &[u8]) -> Result<EscrowTransferOperation> {
+    Ok(EscrowTransferOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        hbd_amount: read_asset(cursor)?,
+        hive_amount: read_asset(cursor)?,
+        escrow_id: read_u32(cursor)?,
+        agent: read_string(cursor)?,
+        fee: read_asset(cursor)?,
+        json_meta: read_string(cursor)?,
+        ratification_deadline: read_date(cursor)?,
+        escrow_expiration: read_date(cursor)?,
+    })
+}
+
+fn deserialize_escrow_dispute(cursor: &mut &[u8]) -> Result<EscrowDisputeOperation> {
+    Ok(EscrowDisputeOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        agent: read_string(cursor)?,
+        who: read_string(cursor)?,
+        escrow_id: read_u32(cursor)?,
+    })
+}
+
+fn deserialize_escrow_release(cursor: &mut &[u8]) -> Result<EscrowReleaseOperation> {
+    Ok(EscrowReleaseOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        agent: read_string(cursor)?,
+        who: read_string(cursor)?,
+        receiver: read_string(cursor)?,
+        escrow_id: read_u32(cursor)?,
+        hbd_amount: read_asset(cursor)?,
+        hive_amount: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_escrow_approve(cursor: &mut &[u8]) -> Result<EscrowApproveOperation> {
+    Ok(EscrowApproveOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        agent: read_string(cursor)?,
+        who: read_string(cursor)?,
+        escrow_id: read_u32(cursor)?,
+        approve: read_bool(cursor)?,
+    })
+}
+
+fn deserialize_transfer_to_savings(cursor: &mut &[u8]) -> Result<TransferToSavingsOperation> {
+    Ok(TransferToSavingsOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        amount: read_asset(cursor)?,
+        memo: read_string(cursor)?,
+    })
+}
+
+fn deserialize_transfer_from_savings(cursor: &mut &[u8]) -> Result<TransferFromSavingsOperation> {
+    Ok(TransferFromSavingsOperation {
+        from: read_string(cursor)?,
+        request_id: read_u32(cursor)?,
+        to: read_string(cursor)?,
+        amount: read_asset(cursor)?,
+        memo: read_string(cursor)?,
+    })
+}
+
+fn deserialize_cancel_transfer_from_savings(
+    cursor: &mut &[u8],
+) -> Result<CancelTransferFromSavingsOperation> {
+    Ok(CancelTransferFromSavingsOperation {
+        from: read_string(cursor)?,
+        request_id: read_u32(cursor)?,
+    })
+}
+
+fn deserialize_custom_binary(cursor: &mut &[u8]) -> Result<CustomBinaryOperation> {
+    Ok(CustomBinaryOperation {
+        required_owner_auths: read_array(cursor, read_string)?,
+        required_active_auths: read_array(cursor, read_string)?,
+        required_posting_auths: read_array(cursor, read_string)?,
+        required_auths: read_array(cursor, read_authority)?,
+        id: read_string(cursor)?,
+        data: read_variable_binary(cursor)?,
+    })
+}
+
+fn deserialize_decline_voting_rights(cursor: &mut &[u8]) -> Result<DeclineVotingRightsOperation> {
+    Ok(DeclineVotingRightsOperation {
+        account: read_string(cursor)?,
+        decline: read_bool(cursor)?,
+    })
+}
+
+fn deserialize_reset_account(cursor: &mut &[u8]) -> Result<ResetAccountOperation> {
+    Ok(ResetAccountOperation {
+        reset_account: read_string(cursor)?,
+        account_to_reset: read_string(cursor)?,
+        new_owner_authority: read_authority(cursor)?,
+    })
+}
+
+fn deserialize_set_reset_account(cursor: &mut &[u8]) -> Result<SetResetAccountOperation> {
+    Ok(SetResetAccountOperation {
+        account: read_string(cursor)?,
+        current_reset_account: read_string(cursor)?,
+        reset_account: read_string(cursor)?,
+    })
+}
+
+fn deserialize_claim_reward_balance(cursor: &mut &[u8]) -> Result<ClaimRewardBalanceOperation> {
+    Ok(ClaimRewardBalanceOperation {
+        account: read_string(cursor)?,
+        reward_hive: read_asset(cursor)?,
+        reward_hbd: read_asset(cursor)?,
+        reward_vests: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_delegate_vesting_shares(
+    cursor: &mut &[u8],
+) -> Result<DelegateVestingSharesOperation> {
+    Ok(DelegateVestingSharesOperation {
+        delegator: read_string(cursor)?,
+        delegatee: read_string(cursor)?,
+        vesting_shares: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_account_create_with_delegation(
+    cursor: &mut &[u8],
+) -> Result<AccountCreateWithDelegationOperation> {
+    Ok(AccountCreateWithDelegationOperation {
+        fee: read_asset(cursor)?,
+        delegation: read_asset(cursor)?,
+        creator: read_string(cursor)?,
+        new_account_name: read_string(cursor)?,
+        owner: read_authority(cursor)?,
+        active: read_authority(cursor)?,
+        posting: read_authority(cursor)?,
+        memo_key: read_public_key(cursor)?,
+        json_metadata: read_string(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_witness_set_properties(cursor: &mut &[u8]) -> Result<WitnessSetPropertiesOperation> {
+    Ok(WitnessSetPropertiesOperation {
+        owner: read_string(cursor)?,
+        props: read_array(cursor, |c| Ok((read_string(c)?, read_variable_binary(c)?)))?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_account_update2(cursor: &mut &[u8]) -> Result<AccountUpdate2Operation> {
+    Ok(AccountUpdate2Operation {
+        account: read_string(cursor)?,
+        owner: read_optional(cursor, read_authority)?,
+        active: read_optional(cursor, read_authority)?,
+        posting: read_optional(cursor, read_authority)?,
+        memo_key: read_optional(cursor, read_public_key)?,
+        json_metadata: read_string(cursor)?,
+        posting_json_metadata: read_string(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_create_proposal(cursor: &mut &[u8]) -> Result<CreateProposalOperation> {
+    Ok(CreateProposalOperation {
+        creator: read_string(cursor)?,
+        receiver: read_string(cursor)?,
+        start_date: read_date(cursor)?,
+        end_date: read_date(cursor)?,
+        daily_pay: read_asset(cursor)?,
+        subject: read_string(cursor)?,
+        permlink: read_string(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_update_proposal_votes(cursor: &mut &[u8]) -> Result<UpdateProposalVotesOperation> {
+    Ok(UpdateProposalVotesOperation {
+        voter: read_string(cursor)?,
+        proposal_ids: read_array(cursor, read_i64)?,
+        approve: read_bool(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_remove_proposal(cursor: &mut &[u8]) -> Result<RemoveProposalOperation> {
+    Ok(RemoveProposalOperation {
+        proposal_owner: read_string(cursor)?,
+        proposal_ids: read_array(cursor, read_i64)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
+fn deserialize_update_proposal(cursor: &mut &[u8]) -> Result<UpdateProposalOperation> {
+    Ok(UpdateProposalOperation {
+        proposal_id: read_u64(cursor)?,
+        creator: read_string(cursor)?,
+        daily_pay: read_asset(cursor)?,
+        subject: read_string(cursor)?,
+        permlink: read_string(cursor)?,
+        extensions: read_array(cursor, |c| match read_varint32(c)? {
+            0 => Ok(UpdateProposalExtension::Void),
+            1 => Ok(UpdateProposalExtension::EndDate {
+                end_date: read_date(c)?,
+            }),
+            tag => Err(HiveError::Serialization(format!(
+                "unknown update_proposal extension {tag}"
+            ))),
+        })?,
+    })
+}
+
+fn deserialize_collateralized_convert(
+    cursor: &mut &[u8],
+) -> Result<CollateralizedConvertOperation> {
+    Ok(CollateralizedConvertOperation {
+        owner: read_string(cursor)?,
+        requestid: read_u32(cursor)?,
+        amount: read_asset(cursor)?,
+    })
+}
+
+fn deserialize_recurrent_transfer(cursor: &mut &[u8]) -> Result<RecurrentTransferOperation> {
+    Ok(RecurrentTransferOperation {
+        from: read_string(cursor)?,
+        to: read_string(cursor)?,
+        amount: read_asset(cursor)?,
+        memo: read_string(cursor)?,
+        recurrence: read_u16(cursor)?,
+        executions: read_u16(cursor)?,
+        extensions: read_void_extensions(cursor)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::crypto::PrivateKey;
     use crate::serialization::deserializer::{
-        read_u16, read_u32, read_u64, read_u8, read_variable_binary,
+        deserialize_transaction, read_u16, read_u32, read_u64, read_u8, read_variable_binary,
+        HiveDeserialize,
     };
+    use crate::serialization::serializer::{serialize_transaction, HiveSerialize};
     use crate::serialization::types::write_variable_binary;
+    use crate::types::{
+        AccountUpdate2Operation, Asset, Authority, BeneficiaryRoute, CommentOptionsExtension,
+        CommentOptionsOperation, CustomBinaryOperation, LimitOrderCreate2Operation, Operation,
+        Price, Transaction, UpdateProposalExtension, UpdateProposalOperation, VoteOperation,
+        WitnessSetPropertiesOperation,
+    };
 
     #[test]
     fn reads_little_endian_primitives() {
@@ -108,4 +882,162 @@ mod tests {
         assert_eq!(value, b"hello");
         assert!(cursor.is_empty());
     }
+
+    #[test]
+    fn operations_round_trip_through_binary() {
+        let key = PrivateKey::from_seed("alice-active")
+            .expect("seed should derive a key")
+            .public_key()
+            .to_string();
+        let authority = Authority {
+            weight_threshold: 1,
+            account_auths: vec![("bob".to_string(), 1)],
+            key_auths: vec![(key.clone(), 1)],
+        };
+        let operations = vec![
+            Operation::AccountUpdate2(AccountUpdate2Operation {
+                account: "alice".to_string(),
+                owner: None,
+                active: Some(authority.clone()),
+                posting: Some(authority),
+                memo_key: Some(key),
+                json_metadata: "{}".to_string(),
+                posting_json_metadata: String::new(),
+                extensions: Vec::new(),
+            }),
+            Operation::CommentOptions(CommentOptionsOperation {
+                author: "alice".to_string(),
+                permlink: "post".to_string(),
+                max_accepted_payout: Asset::hbd(1000.0),
+                percent_hbd: 5000,
+                allow_votes: true,
+                allow_curation_rewards: false,
+                extensions: vec![CommentOptionsExtension::Beneficiaries {
+                    beneficiaries: vec![BeneficiaryRoute {
+                        account: "bob".to_string(),
+                        weight: 1000,
+                    }],
+                }],
+            }),
+            Operation::LimitOrderCreate2(LimitOrderCreate2Operation {
+                owner: "alice".to_string(),
+                orderid: 7,
+                amount_to_sell: Asset::hive(10.0),
+                exchange_rate: Price {
+                    base: Asset::hive(1.0),
+                    quote: Asset::hbd(0.25),
+                },
+                fill_or_kill: false,
+                expiration: "2024-01-01T00:00:00".to_string(),
+            }),
+            Operation::UpdateProposal(UpdateProposalOperation {
+                proposal_id: 42,
+                creator: "alice".to_string(),
+                daily_pay: Asset::hbd(10.0),
+                subject: "subject".to_string(),
+                permlink: "proposal".to_string(),
+                extensions: vec![UpdateProposalExtension::EndDate {
+                    end_date: "2025-06-01T12:00:00".to_string(),
+                }],
+            }),
+            Operation::WitnessSetProperties(WitnessSetPropertiesOperation {
+                owner: "alice".to_string(),
+                props: vec![("key".to_string(), vec![1, 2, 3])],
+                extensions: Vec::new(),
+            }),
+            Operation::CustomBinary(CustomBinaryOperation {
+                required_owner_auths: Vec::new(),
+                required_active_auths: Vec::new(),
+                required_posting_auths: vec!["alice".to_string()],
+                required_auths: Vec::new(),
+                id: "app".to_string(),
+                data: vec![0xde, 0xad],
+            }),
+        ];
+
+        for operation in operations {
+            let mut buf = Vec::new();
+            operation
+                .hive_serialize(&mut buf)
+                .expect("operation should serialize");
+            let mut cursor = buf.as_slice();
+            let decoded =
+                Operation::hive_deserialize(&mut cursor).expect("operation should decode");
+            assert_eq!(decoded, operation);
+            assert!(cursor.is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "testnet"))]
+    fn transfer_dhive_vector_decodes() {
+        // The reference vector uses mainnet wire symbols.
+        use crate::types::TransferOperation;
+
+        let bytes = hex::decode(
+            "0203666f6f03626172e80300000000000003535445454d00000f77656464696e672070726573656e74",
+        )
+        .expect("vector should be hex");
+        let mut cursor = bytes.as_slice();
+        let operation = Operation::hive_deserialize(&mut cursor).expect("operation should decode");
+        assert!(cursor.is_empty());
+        assert_eq!(
+            operation,
+            Operation::Transfer(TransferOperation {
+                from: "foo".to_string(),
+                to: "bar".to_string(),
+                amount: Asset::from_string("1.000 STEEM").expect("asset should parse"),
+                memo: "wedding present".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn transaction_dhive_vector_round_trips() {
+        let bytes = hex::decode(
+            "d204f776e54207486a59010003666f6f036261720362617a1027010a6c6f6e672d70616e7473",
+        )
+        .expect("vector should be hex");
+        let tx = deserialize_transaction(&bytes).expect("transaction should decode");
+        assert_eq!(
+            tx,
+            Transaction {
+                ref_block_num: 1234,
+                ref_block_prefix: 1122334455,
+                expiration: "2017-07-15T16:51:19".to_string(),
+                operations: vec![Operation::Vote(VoteOperation {
+                    voter: "foo".to_string(),
+                    author: "bar".to_string(),
+                    permlink: "baz".to_string(),
+                    weight: 10000,
+                })],
+                extensions: vec!["long-pants".to_string()],
+            }
+        );
+        assert_eq!(
+            serialize_transaction(&tx).expect("transaction should serialize"),
+            bytes
+        );
+    }
+
+    #[test]
+    fn malformed_transactions_are_rejected() {
+        let tx = Transaction {
+            ref_block_num: 1,
+            ref_block_prefix: 2,
+            expiration: "2024-01-01T00:00:00".to_string(),
+            operations: Vec::new(),
+            extensions: Vec::new(),
+        };
+        let mut bytes = serialize_transaction(&tx).expect("transaction should serialize");
+        assert_eq!(deserialize_transaction(&bytes).expect("should decode"), tx);
+
+        assert!(deserialize_transaction(&bytes[..bytes.len() - 1]).is_err());
+        bytes.push(0);
+        assert!(deserialize_transaction(&bytes).is_err());
+
+        // An operation id with no typed model has no length prefix to skip.
+        let mut unknown = [0x63_u8, 0x00].as_slice();
+        assert!(Operation::hive_deserialize(&mut unknown).is_err());
+    }
 }
@@ -1,10 +1,10 @@
 use crate::crypto::utils::sha256;
 use crate::error::{HiveError, Result};
 use crate::serialization::types::{
-    write_array, write_asset, write_authority, write_bool, write_chain_properties, write_date,
-    write_flat_map, write_i16, write_i64, write_optional, write_price, write_public_key,
-    write_string, write_u16, write_u32, write_u64, write_variable_binary, write_varint32,
-    write_void_array,
+    write_array, write_authority, write_bool, write_date, write_flat_map, write_i16, write_i64,
+    write_network_asset, write_network_chain_properties, write_network_price, write_optional,
+    write_public_key, write_string, write_u16, write_u32, write_u64, write_variable_binary,
+    write_varint32, write_void_array,
 };
 use crate::types::{
     AccountCreateOperation, AccountCreateWithDelegationOperation, AccountUpdate2Operation,
@@ -27,25 +27,33 @@ use crate::types::{
 };
 
 pub trait HiveSerialize {
-    fn hive_serialize(&self, buf: &mut Vec<u8>) -> Result<()>;
+    /// Serializes with the wire symbols of the build (see
+    /// [`write_asset`](crate::serialization::types::write_asset)).
+    fn hive_serialize(&self, buf: &mut Vec<u8>) -> Result<()> {
+        self.hive_serialize_for(buf, cfg!(feature = "testnet"))
+    }
+
+    /// Serializes HIVE and HBD amounts with the testnet wire symbols
+    /// (`TESTS`/`TBD`) when `testnet` is set, the mainnet ones otherwise.
+    fn hive_serialize_for(&self, buf: &mut Vec<u8>, testnet: bool) -> Result<()>;
 }
 
 impl HiveSerialize for Operation {
-    fn hive_serialize(&self, buf: &mut Vec<u8>) -> Result<()> {
+    fn hive_serialize_for(&self, buf: &mut Vec<u8>, testnet: bool) -> Result<()> {
         write_varint32(buf, self.id() as u32);
         match self {
             Operation::Vote(op) => serialize_vote(buf, op),
             Operation::Comment(op) => serialize_comment(buf, op),
-            Operation::Transfer(op) => serialize_transfer(buf, op),
-            Operation::TransferToVesting(op) => serialize_transfer_to_vesting(buf, op),
-            Operation::WithdrawVesting(op) => serialize_withdraw_vesting(buf, op),
-            Operation::LimitOrderCreate(op) => serialize_limit_order_create(buf, op),
+            Operation::Transfer(op) => serialize_transfer(buf, op, testnet),
+            Operation::TransferToVesting(op) => serialize_transfer_to_vesting(buf, op, testnet),
+            Operation::WithdrawVesting(op) => serialize_withdraw_vesting(buf, op, testnet),
+            Operation::LimitOrderCreate(op) => serialize_limit_order_create(buf, op, testnet),
             Operation::LimitOrderCancel(op) => serialize_limit_order_cancel(buf, op),
-            Operation::FeedPublish(op) => serialize_feed_publish(buf, op),
-            Operation::Convert(op) => serialize_convert(buf, op),
-            Operation::AccountCreate(op) => serialize_account_create(buf, op),
+            Operation::FeedPublish(op) => serialize_feed_publish(buf, op, testnet),
+            Operation::Convert(op) => serialize_convert(buf, op, testnet),
+            Operation::AccountCreate(op) => serialize_account_create(buf, op, testnet),
             Operation::AccountUpdate(op) => serialize_account_update(buf, op),
-            Operation::WitnessUpdate(op) => serialize_witness_update(buf, op),
+            Operation::WitnessUpdate(op) => serialize_witness_update(buf, op, testnet),
             Operation::AccountWitnessVote(op) => serialize_account_witness_vote(buf, op),
             Operation::AccountWitnessProxy(op) => serialize_account_witness_proxy(buf, op),
             Operation::Pow(op) => serialize_pow(buf, op),
@@ -53,21 +61,21 @@ impl HiveSerialize for Operation {
             Operation::ReportOverProduction(op) => serialize_report_over_production(buf, op),
             Operation::DeleteComment(op) => serialize_delete_comment(buf, op),
             Operation::CustomJson(op) => serialize_custom_json(buf, op),
-            Operation::CommentOptions(op) => serialize_comment_options(buf, op),
+            Operation::CommentOptions(op) => serialize_comment_options(buf, op, testnet),
             Operation::SetWithdrawVestingRoute(op) => serialize_set_withdraw_vesting_route(buf, op),
-            Operation::LimitOrderCreate2(op) => serialize_limit_order_create2(buf, op),
-            Operation::ClaimAccount(op) => serialize_claim_account(buf, op),
+            Operation::LimitOrderCreate2(op) => serialize_limit_order_create2(buf, op, testnet),
+            Operation::ClaimAccount(op) => serialize_claim_account(buf, op, testnet),
             Operation::CreateClaimedAccount(op) => serialize_create_claimed_account(buf, op),
             Operation::RequestAccountRecovery(op) => serialize_request_account_recovery(buf, op),
             Operation::RecoverAccount(op) => serialize_recover_account(buf, op),
             Operation::ChangeRecoveryAccount(op) => serialize_change_recovery_account(buf, op),
-            Operation::EscrowTransfer(op) => serialize_escrow_transfer(buf, op),
+            Operation::EscrowTransfer(op) => serialize_escrow_transfer(buf, op, testnet),
             Operation::EscrowDispute(op) => serialize_escrow_dispute(buf, op),
-            Operation::EscrowRelease(op) => serialize_escrow_release(buf, op),
+            Operation::EscrowRelease(op) => serialize_escrow_release(buf, op, testnet),
             Operation::Pow2(op) => serialize_pow2(buf, op),
             Operation::EscrowApprove(op) => serialize_escrow_approve(buf, op),
-            Operation::TransferToSavings(op) => serialize_transfer_to_savings(buf, op),
-            Operation::TransferFromSavings(op) => serialize_transfer_from_savings(buf, op),
+            Operation::TransferToSavings(op) => serialize_transfer_to_savings(buf, op, testnet),
+            Operation::TransferFromSavings(op) => serialize_transfer_from_savings(buf, op, testnet),
             Operation::CancelTransferFromSavings(op) => {
                 serialize_cancel_transfer_from_savings(buf, op)
             }
@@ -75,32 +83,50 @@ impl HiveSerialize for Operation {
             Operation::DeclineVotingRights(op) => serialize_decline_voting_rights(buf, op),
             Operation::ResetAccount(op) => serialize_reset_account(buf, op),
             Operation::SetResetAccount(op) => serialize_set_reset_account(buf, op),
-            Operation::ClaimRewardBalance(op) => serialize_claim_reward_balance(buf, op),
-            Operation::DelegateVestingShares(op) => serialize_delegate_vesting_shares(buf, op),
+            Operation::ClaimRewardBalance(op) => serialize_claim_reward_balance(buf, op, testnet),
+            Operation::DelegateVestingShares(op) => {
+                serialize_delegate_vesting_shares(buf, op, testnet)
+            }
             Operation::AccountCreateWithDelegation(op) => {
-                serialize_account_create_with_delegation(buf, op)
+                serialize_account_create_with_delegation(buf, op, testnet)
             }
             Operation::WitnessSetProperties(op) => serialize_witness_set_properties(buf, op),
             Operation::AccountUpdate2(op) => serialize_account_update2(buf, op),
-            Operation::CreateProposal(op) => serialize_create_proposal(buf, op),
+            Operation::CreateProposal(op) => serialize_create_proposal(buf, op, testnet),
             Operation::UpdateProposalVotes(op) => serialize_update_proposal_votes(buf, op),
             Operation::RemoveProposal(op) => serialize_remove_proposal(buf, op),
-            Operation::UpdateProposal(op) => serialize_update_proposal(buf, op),
-            Operation::CollateralizedConvert(op) => serialize_collateralized_convert(buf, op),
-            Operation::RecurrentTransfer(op) => serialize_recurrent_transfer(buf, op),
+            Operation::UpdateProposal(op) => serialize_update_proposal(buf, op, testnet),
+            Operation::CollateralizedConvert(op) => {
+                serialize_collateralized_convert(buf, op, testnet)
+            }
+            Operation::RecurrentTransfer(op) => serialize_recurrent_transfer(buf, op, testnet),
             Operation::Virtual { op_type, .. } => Err(HiveError::Serialization(format!(
                 "virtual operation '{op_type}' cannot be serialized for broadcast"
             ))),
+            Operation::Unknown(op) if op.id.is_none() => Err(HiveError::Serialization(format!(
+                "operation '{}' has no wire id",
+                op.name
+            ))),
+            Operation::Unknown(op) if op.body.is_empty() => Err(HiveError::Serialization(format!(
+                "operation '{}' has no pre-serialized binary body",
+                op.name
+            ))),
+            Operation::Unknown(op) => {
+                buf.extend_from_slice(&op.body);
+                Ok(())
+            }
         }
     }
 }
 
 impl HiveSerialize for Transaction {
-    fn hive_serialize(&self, buf: &mut Vec<u8>) -> Result<()> {
+    fn hive_serialize_for(&self, buf: &mut Vec<u8>, testnet: bool) -> Result<()> {
         write_u16(buf, self.ref_block_num);
         write_u32(buf, self.ref_block_prefix);
         write_date(buf, &self.expiration)?;
-        write_array(buf, &self.operations, |b, op| op.hive_serialize(b))?;
+        write_array(buf, &self.operations, |b, op| {
+            op.hive_serialize_for(b, testnet)
+        })?;
         write_array(buf, &self.extensions, |b, ext| {
             write_string(b, ext);
             Ok(())
@@ -111,15 +137,30 @@ impl HiveSerialize for Transaction {
 
 pub fn serialize_transaction(transaction: &Transaction) -> Result<Vec<u8>> {
     let mut buf = Vec::new();
-    transaction.hive_serialize(&mut buf)?;
+    serialize_transaction_into(transaction, &mut buf)?;
+    Ok(buf)
+}
+
+/// Serializes for the network `chain_id` identifies, picking the testnet wire
+/// symbols for the testnet and mirrornet chain ids regardless of build
+/// features.
+pub fn serialize_transaction_for(transaction: &Transaction, chain_id: &ChainId) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    transaction.hive_serialize_for(&mut buf, chain_id.uses_testnet_symbols())?;
     Ok(buf)
 }
 
+/// Serializes into `buf` after clearing it, so a signer or indexer can reuse
+/// one allocation across transactions. On error `buf` holds a partial
+/// encoding.
+pub fn serialize_transaction_into(transaction: &Transaction, buf: &mut Vec<u8>) -> Result<()> {
+    buf.clear();
+    transaction.hive_serialize(buf)
+}
+
 pub fn transaction_digest(transaction: &Transaction, chain_id: &ChainId) -> Result<[u8; 32]> {
-    let tx_bytes = serialize_transaction(transaction)?;
-    let mut to_hash = Vec::with_capacity(chain_id.bytes.len() + tx_bytes.len());
-    to_hash.extend_from_slice(&chain_id.bytes);
-    to_hash.extend_from_slice(&tx_bytes);
+    let mut to_hash = chain_id.bytes.to_vec();
+    transaction.hive_serialize_for(&mut to_hash, chain_id.uses_testnet_symbols())?;
     Ok(sha256(&to_hash))
 }
 
@@ -129,6 +170,14 @@ pub fn generate_trx_id(transaction: &Transaction) -> Result<String> {
     Ok(hex::encode(hash)[..40].to_string())
 }
 
+/// Like [`generate_trx_id`], but over the bytes of
+/// [`serialize_transaction_for`].
+pub fn generate_trx_id_for(transaction: &Transaction, chain_id: &ChainId) -> Result<String> {
+    let tx_bytes = serialize_transaction_for(transaction, chain_id)?;
+    let hash = sha256(&tx_bytes);
+    Ok(hex::encode(hash)[..40].to_string())
+}
+
 /// Writes a `flat_set<account_name_type>` in the chain's canonical (sorted,
 /// ascending) order. Account names are ASCII so byte ordering matches the chain.
 fn write_account_flat_set(buf: &mut Vec<u8>, accounts: &[String]) -> Result<()> {
@@ -197,30 +246,42 @@ fn serialize_comment(buf: &mut Vec<u8>, op: &CommentOperation) -> Result<()> {
     Ok(())
 }
 
-fn serialize_transfer(buf: &mut Vec<u8>, op: &TransferOperation) -> Result<()> {
+fn serialize_transfer(buf: &mut Vec<u8>, op: &TransferOperation, testnet: bool) -> Result<()> {
     write_string(buf, &op.from);
     write_string(buf, &op.to);
-    write_asset(buf, &op.amount)?;
+    write_network_asset(buf, &op.amount, testnet)?;
     write_string(buf, &op.memo);
     Ok(())
 }
 
-fn serialize_transfer_to_vesting(buf: &mut Vec<u8>, op: &TransferToVestingOperation) -> Result<()> {
+fn serialize_transfer_to_vesting(
+    buf: &mut Vec<u8>,
+    op: &TransferToVestingOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.from);
     write_string(buf, &op.to);
-    write_asset(buf, &op.amount)
+    write_network_asset(buf, &op.amount, testnet)
 }
 
-fn serialize_withdraw_vesting(buf: &mut Vec<u8>, op: &WithdrawVestingOperation) -> Result<()> {
+fn serialize_withdraw_vesting(
+    buf: &mut Vec<u8>,
+    op: &WithdrawVestingOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.account);
-    write_asset(buf, &op.vesting_shares)
+    write_network_asset(buf, &op.vesting_shares, testnet)
 }
 
-fn serialize_limit_order_create(buf: &mut Vec<u8>, op: &LimitOrderCreateOperation) -> Result<()> {
+fn serialize_limit_order_create(
+    buf: &mut Vec<u8>,
+    op: &LimitOrderCreateOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.owner);
     write_u32(buf, op.orderid);
-    write_asset(buf, &op.amount_to_sell)?;
-    write_asset(buf, &op.min_to_receive)?;
+    write_network_asset(buf, &op.amount_to_sell, testnet)?;
+    write_network_asset(buf, &op.min_to_receive, testnet)?;
     write_bool(buf, op.fill_or_kill);
     write_date(buf, &op.expiration)
 }
@@ -231,19 +292,27 @@ fn serialize_limit_order_cancel(buf: &mut Vec<u8>, op: &LimitOrderCancelOperatio
     Ok(())
 }
 
-fn serialize_feed_publish(buf: &mut Vec<u8>, op: &FeedPublishOperation) -> Result<()> {
+fn serialize_feed_publish(
+    buf: &mut Vec<u8>,
+    op: &FeedPublishOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.publisher);
-    write_price(buf, &op.exchange_rate)
+    write_network_price(buf, &op.exchange_rate, testnet)
 }
 
-fn serialize_convert(buf: &mut Vec<u8>, op: &ConvertOperation) -> Result<()> {
+fn serialize_convert(buf: &mut Vec<u8>, op: &ConvertOperation, testnet: bool) -> Result<()> {
     write_string(buf, &op.owner);
     write_u32(buf, op.requestid);
-    write_asset(buf, &op.amount)
+    write_network_asset(buf, &op.amount, testnet)
 }
 
-fn serialize_account_create(buf: &mut Vec<u8>, op: &AccountCreateOperation) -> Result<()> {
-    write_asset(buf, &op.fee)?;
+fn serialize_account_create(
+    buf: &mut Vec<u8>,
+    op: &AccountCreateOperation,
+    testnet: bool,
+) -> Result<()> {
+    write_network_asset(buf, &op.fee, testnet)?;
     write_string(buf, &op.creator);
     write_string(buf, &op.new_account_name);
     write_authority(buf, &op.owner)?;
@@ -264,12 +333,16 @@ fn serialize_account_update(buf: &mut Vec<u8>, op: &AccountUpdateOperation) -> R
     Ok(())
 }
 
-fn serialize_witness_update(buf: &mut Vec<u8>, op: &WitnessUpdateOperation) -> Result<()> {
+fn serialize_witness_update(
+    buf: &mut Vec<u8>,
+    op: &WitnessUpdateOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.owner);
     write_string(buf, &op.url);
     write_public_key(buf, &op.block_signing_key)?;
-    write_chain_properties(buf, &op.props)?;
-    write_asset(buf, &op.fee)
+    write_network_chain_properties(buf, &op.props, testnet)?;
+    write_network_asset(buf, &op.fee, testnet)
 }
 
 fn serialize_account_witness_vote(
@@ -328,10 +401,14 @@ fn serialize_custom_json(buf: &mut Vec<u8>, op: &CustomJsonOperation) -> Result<
     Ok(())
 }
 
-fn serialize_comment_options(buf: &mut Vec<u8>, op: &CommentOptionsOperation) -> Result<()> {
+fn serialize_comment_options(
+    buf: &mut Vec<u8>,
+    op: &CommentOptionsOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.author);
     write_string(buf, &op.permlink);
-    write_asset(buf, &op.max_accepted_payout)?;
+    write_network_asset(buf, &op.max_accepted_payout, testnet)?;
     write_u16(buf, op.percent_hbd);
     write_bool(buf, op.allow_votes);
     write_bool(buf, op.allow_curation_rewards);
@@ -363,18 +440,26 @@ fn serialize_set_withdraw_vesting_route(
     Ok(())
 }
 
-fn serialize_limit_order_create2(buf: &mut Vec<u8>, op: &LimitOrderCreate2Operation) -> Result<()> {
+fn serialize_limit_order_create2(
+    buf: &mut Vec<u8>,
+    op: &LimitOrderCreate2Operation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.owner);
     write_u32(buf, op.orderid);
-    write_asset(buf, &op.amount_to_sell)?;
-    write_price(buf, &op.exchange_rate)?;
+    write_network_asset(buf, &op.amount_to_sell, testnet)?;
+    write_network_price(buf, &op.exchange_rate, testnet)?;
     write_bool(buf, op.fill_or_kill);
     write_date(buf, &op.expiration)
 }
 
-fn serialize_claim_account(buf: &mut Vec<u8>, op: &ClaimAccountOperation) -> Result<()> {
+fn serialize_claim_account(
+    buf: &mut Vec<u8>,
+    op: &ClaimAccountOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.creator);
-    write_asset(buf, &op.fee)?;
+    write_network_asset(buf, &op.fee, testnet)?;
     write_void_extensions(buf, &op.extensions)
 }
 
@@ -418,14 +503,18 @@ fn serialize_change_recovery_account(
     write_void_extensions(buf, &op.extensions)
 }
 
-fn serialize_escrow_transfer(buf: &mut Vec<u8>, op: &EscrowTransferOperation) -> Result<()> {
+fn serialize_escrow_transfer(
+    buf: &mut Vec<u8>,
+    op: &EscrowTransferOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.from);
     write_string(buf, &op.to);
-    write_asset(buf, &op.hbd_amount)?;
-    write_asset(buf, &op.hive_amount)?;
+    write_network_asset(buf, &op.hbd_amount, testnet)?;
+    write_network_asset(buf, &op.hive_amount, testnet)?;
     write_u32(buf, op.escrow_id);
     write_string(buf, &op.agent);
-    write_asset(buf, &op.fee)?;
+    write_network_asset(buf, &op.fee, testnet)?;
     write_string(buf, &op.json_meta);
     write_date(buf, &op.ratification_deadline)?;
     write_date(buf, &op.escrow_expiration)
@@ -440,15 +529,19 @@ fn serialize_escrow_dispute(buf: &mut Vec<u8>, op: &EscrowDisputeOperation) -> R
     Ok(())
 }
 
-fn serialize_escrow_release(buf: &mut Vec<u8>, op: &EscrowReleaseOperation) -> Result<()> {
+fn serialize_escrow_release(
+    buf: &mut Vec<u8>,
+    op: &EscrowReleaseOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.from);
     write_string(buf, &op.to);
     write_string(buf, &op.agent);
     write_string(buf, &op.who);
     write_string(buf, &op.receiver);
     write_u32(buf, op.escrow_id);
-    write_asset(buf, &op.hbd_amount)?;
-    write_asset(buf, &op.hive_amount)?;
+    write_network_asset(buf, &op.hbd_amount, testnet)?;
+    write_network_asset(buf, &op.hive_amount, testnet)?;
     Ok(())
 }
 
@@ -468,10 +561,14 @@ fn serialize_escrow_approve(buf: &mut Vec<u8>, op: &EscrowApproveOperation) -> R
     Ok(())
 }
 
-fn serialize_transfer_to_savings(buf: &mut Vec<u8>, op: &TransferToSavingsOperation) -> Result<()> {
+fn serialize_transfer_to_savings(
+    buf: &mut Vec<u8>,
+    op: &TransferToSavingsOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.from);
     write_string(buf, &op.to);
-    write_asset(buf, &op.amount)?;
+    write_network_asset(buf, &op.amount, testnet)?;
     write_string(buf, &op.memo);
     Ok(())
 }
@@ -479,11 +576,12 @@ fn serialize_transfer_to_savings(buf: &mut Vec<u8>, op: &TransferToSavingsOperat
 fn serialize_transfer_from_savings(
     buf: &mut Vec<u8>,
     op: &TransferFromSavingsOperation,
+    testnet: bool,
 ) -> Result<()> {
     write_string(buf, &op.from);
     write_u32(buf, op.request_id);
     write_string(buf, &op.to);
-    write_asset(buf, &op.amount)?;
+    write_network_asset(buf, &op.amount, testnet)?;
     write_string(buf, &op.memo);
     Ok(())
 }
@@ -532,28 +630,31 @@ fn serialize_set_reset_account(buf: &mut Vec<u8>, op: &SetResetAccountOperation)
 fn serialize_claim_reward_balance(
     buf: &mut Vec<u8>,
     op: &ClaimRewardBalanceOperation,
+    testnet: bool,
 ) -> Result<()> {
     write_string(buf, &op.account);
-    write_asset(buf, &op.reward_hive)?;
-    write_asset(buf, &op.reward_hbd)?;
-    write_asset(buf, &op.reward_vests)
+    write_network_asset(buf, &op.reward_hive, testnet)?;
+    write_network_asset(buf, &op.reward_hbd, testnet)?;
+    write_network_asset(buf, &op.reward_vests, testnet)
 }
 
 fn serialize_delegate_vesting_shares(
     buf: &mut Vec<u8>,
     op: &DelegateVestingSharesOperation,
+    testnet: bool,
 ) -> Result<()> {
     write_string(buf, &op.delegator);
     write_string(buf, &op.delegatee);
-    write_asset(buf, &op.vesting_shares)
+    write_network_asset(buf, &op.vesting_shares, testnet)
 }
 
 fn serialize_account_create_with_delegation(
     buf: &mut Vec<u8>,
     op: &AccountCreateWithDelegationOperation,
+    testnet: bool,
 ) -> Result<()> {
-    write_asset(buf, &op.fee)?;
-    write_asset(buf, &op.delegation)?;
+    write_network_asset(buf, &op.fee, testnet)?;
+    write_network_asset(buf, &op.delegation, testnet)?;
     write_string(buf, &op.creator);
     write_string(buf, &op.new_account_name);
     write_authority(buf, &op.owner)?;
@@ -597,12 +698,16 @@ fn serialize_account_update2(buf: &mut Vec<u8>, op: &AccountUpdate2Operation) ->
     write_void_extensions(buf, &op.extensions)
 }
 
-fn serialize_create_proposal(buf: &mut Vec<u8>, op: &CreateProposalOperation) -> Result<()> {
+fn serialize_create_proposal(
+    buf: &mut Vec<u8>,
+    op: &CreateProposalOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.creator);
     write_string(buf, &op.receiver);
     write_date(buf, &op.start_date)?;
     write_date(buf, &op.end_date)?;
-    write_asset(buf, &op.daily_pay)?;
+    write_network_asset(buf, &op.daily_pay, testnet)?;
     write_string(buf, &op.subject);
     write_string(buf, &op.permlink);
     write_void_extensions(buf, &op.extensions)
@@ -630,10 +735,14 @@ fn serialize_remove_proposal(buf: &mut Vec<u8>, op: &RemoveProposalOperation) ->
     write_void_extensions(buf, &op.extensions)
 }
 
-fn serialize_update_proposal(buf: &mut Vec<u8>, op: &UpdateProposalOperation) -> Result<()> {
+fn serialize_update_proposal(
+    buf: &mut Vec<u8>,
+    op: &UpdateProposalOperation,
+    testnet: bool,
+) -> Result<()> {
     write_u64(buf, op.proposal_id);
     write_string(buf, &op.creator);
-    write_asset(buf, &op.daily_pay)?;
+    write_network_asset(buf, &op.daily_pay, testnet)?;
     write_string(buf, &op.subject);
     write_string(buf, &op.permlink);
     write_array(buf, &op.extensions, |b, ext| match ext {
@@ -652,16 +761,21 @@ fn serialize_update_proposal(buf: &mut Vec<u8>, op: &UpdateProposalOperation) ->
 fn serialize_collateralized_convert(
     buf: &mut Vec<u8>,
     op: &CollateralizedConvertOperation,
+    testnet: bool,
 ) -> Result<()> {
     write_string(buf, &op.owner);
     write_u32(buf, op.requestid);
-    write_asset(buf, &op.amount)
+    write_network_asset(buf, &op.amount, testnet)
 }
 
-fn serialize_recurrent_transfer(buf: &mut Vec<u8>, op: &RecurrentTransferOperation) -> Result<()> {
+fn serialize_recurrent_transfer(
+    buf: &mut Vec<u8>,
+    op: &RecurrentTransferOperation,
+    testnet: bool,
+) -> Result<()> {
     write_string(buf, &op.from);
     write_string(buf, &op.to);
-    write_asset(buf, &op.amount)?;
+    write_network_asset(buf, &op.amount, testnet)?;
     write_string(buf, &op.memo);
     write_u16(buf, op.recurrence);
     write_u16(buf, op.executions);
@@ -671,13 +785,17 @@ fn serialize_recurrent_transfer(buf: &mut Vec<u8>, op: &RecurrentTransferOperati
 #[cfg(test)]
 mod tests {
     use crate::serialization::serializer::{
-        generate_trx_id, serialize_transaction, transaction_digest, HiveSerialize,
+        generate_trx_id, serialize_transaction, serialize_transaction_into, transaction_digest,
+        HiveSerialize,
     };
-    use crate::types::Asset;
-    use crate::types::{ChainId, Operation, Transaction, TransferOperation, VoteOperation};
+    use crate::types::{ChainId, Operation, Transaction, VoteOperation};
 
     #[test]
+    #[cfg(not(feature = "testnet"))]
     fn transfer_operation_matches_dhive_vector() {
+        // The reference vector uses mainnet wire symbols.
+        use crate::types::{Asset, TransferOperation};
+
         let operation = Operation::Transfer(TransferOperation {
             from: "foo".to_string(),
             to: "bar".to_string(),
@@ -717,6 +835,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_into_reused_buffer_matches_allocating_version() {
+        use crate::types::CommentOperation;
+
+        let vote = |permlink: &str| {
+            Operation::Vote(VoteOperation {
+                voter: "foo".to_string(),
+                author: "bar".to_string(),
+                permlink: permlink.to_string(),
+                weight: 10000,
+            })
+        };
+        let comment = Operation::Comment(CommentOperation {
+            parent_author: String::new(),
+            parent_permlink: "hive".to_string(),
+            author: "foo".to_string(),
+            permlink: "long-post".to_string(),
+            title: "A long post".to_string(),
+            body: "x".repeat(20_000),
+            json_metadata: "{}".to_string(),
+        });
+        let transactions = [
+            vec![comment],
+            vec![vote("a"), vote("b"), vote("c")],
+            vec![vote("short")],
+            Vec::new(),
+        ];
+
+        let mut buf = Vec::new();
+        for operations in transactions {
+            let tx = Transaction {
+                ref_block_num: 1234,
+                ref_block_prefix: 1122334455,
+                expiration: "2017-07-15T16:51:19".to_string(),
+                operations,
+                extensions: Vec::new(),
+            };
+            serialize_transaction_into(&tx, &mut buf).expect("transaction should serialize");
+            assert_eq!(
+                buf,
+                serialize_transaction(&tx).expect("transaction should serialize")
+            );
+        }
+        assert!(buf.capacity() >= 20_000);
+    }
+
     #[test]
     fn custom_json_required_auths_are_sorted_canonically() {
         use crate::types::CustomJsonOperation;
@@ -801,4 +965,44 @@ mod tests {
         let trx_id = generate_trx_id(&tx).expect("trx id should compute");
         assert_eq!(trx_id, "70a8b9bd8e4a1413eb807f030fa8e81f9c7bb615");
     }
+
+    #[test]
+    fn raw_operation_writes_id_and_pre_serialized_body() {
+        use crate::types::RawOperation;
+
+        let mut body = Vec::new();
+        crate::serialization::types::write_string(&mut body, "foo");
+        let operation = Operation::Unknown(RawOperation {
+            id: Some(50),
+            name: "fork_custom_op".to_string(),
+            payload: serde_json::json!({ "who": "foo" }),
+            body,
+        });
+
+        let mut buf = Vec::new();
+        operation
+            .hive_serialize(&mut buf)
+            .expect("raw operation should serialize");
+        assert_eq!(hex::encode(buf), "3203666f6f");
+    }
+
+    #[test]
+    fn unknown_operation_from_json_needs_an_id_and_body_to_serialize() {
+        let mut operation: Operation =
+            serde_json::from_value(serde_json::json!(["fork_custom_op", { "who": "foo" }]))
+                .expect("unrecognized op should deserialize");
+        let err = operation
+            .hive_serialize(&mut Vec::new())
+            .expect_err("an op without an id should not serialize");
+        assert!(matches!(err, crate::error::HiveError::Serialization(_)));
+
+        let Operation::Unknown(raw) = &mut operation else {
+            panic!("expected unknown operation, got {operation:?}");
+        };
+        raw.id = Some(50);
+        let err = operation
+            .hive_serialize(&mut Vec::new())
+            .expect_err("an op without a binary body should not serialize");
+        assert!(matches!(err, crate::error::HiveError::Serialization(_)));
+    }
 }
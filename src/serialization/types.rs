@@ -79,8 +79,16 @@ pub fn write_public_key(buf: &mut Vec<u8>, key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Writes `asset` with the wire symbols of the build: `TESTS`/`TBD` with the
+/// `testnet` feature, `STEEM`/`SBD` otherwise.
 pub fn write_asset(buf: &mut Vec<u8>, asset: &Asset) -> Result<()> {
-    let (amount, precision, symbol) = asset.steem_symbols();
+    write_network_asset(buf, asset, cfg!(feature = "testnet"))
+}
+
+/// Writes `asset` with the wire symbols of a testnet (`TESTS`/`TBD`) or of
+/// mainnet (`STEEM`/`SBD`).
+pub fn write_network_asset(buf: &mut Vec<u8>, asset: &Asset, testnet: bool) -> Result<()> {
+    let (amount, precision, symbol) = asset.network_symbols(testnet);
     write_i64(buf, amount);
     write_u8(buf, precision);
 
@@ -144,15 +152,13 @@ where
 pub fn write_authority(buf: &mut Vec<u8>, authority: &Authority) -> Result<()> {
     write_u32(buf, authority.weight_threshold);
 
-    // `account_auths` is a `flat_map<account_name_type, weight>`. The chain stores
-    // and signs it sorted by account name (ascending), so we must canonicalize the
-    // order here or the signature will be computed over a non-canonical payload and
-    // rejected. Account names are ASCII, so byte ordering matches the chain's.
-    let mut account_auths = authority.account_auths.clone();
-    account_auths.sort_by(|a, b| a.0.cmp(&b.0));
+    // `account_auths` and `key_auths` are `flat_map`s. The chain stores and signs
+    // them sorted (accounts by name, keys by their 33-byte compressed form, NOT
+    // the base58 string), so write them in canonical order or the signature will
+    // be computed over a non-canonical payload and rejected.
     write_flat_map(
         buf,
-        &account_auths,
+        &authority.sorted_account_auths()?,
         |b, account| {
             write_string(b, account);
             Ok(())
@@ -162,21 +168,16 @@ pub fn write_authority(buf: &mut Vec<u8>, authority: &Authority) -> Result<()> {
             Ok(())
         },
     )?;
-
-    // `key_auths` is a `flat_map<public_key_type, weight>`. `public_key_type` sorts
-    // by its 33-byte compressed representation, NOT by the base58 string, so parse
-    // each key and order by the raw bytes before writing.
-    let mut key_auths = authority
-        .key_auths
-        .iter()
-        .map(|(key, weight)| Ok((PublicKey::from_string(key)?.compressed_bytes(), *weight)))
-        .collect::<Result<Vec<_>>>()?;
-    key_auths.sort_by(|a, b| a.0.cmp(&b.0));
+    let key_auths = authority
+        .sorted_key_auths()?
+        .into_iter()
+        .map(|(compressed, _, weight)| (compressed, weight))
+        .collect::<Vec<_>>();
     write_flat_map(
         buf,
         &key_auths,
-        |b, key_bytes| {
-            b.extend_from_slice(key_bytes);
+        |b, compressed| {
+            b.extend_from_slice(compressed);
             Ok(())
         },
         |b, weight| {
@@ -187,12 +188,31 @@ pub fn write_authority(buf: &mut Vec<u8>, authority: &Authority) -> Result<()> {
 }
 
 pub fn write_price(buf: &mut Vec<u8>, price: &Price) -> Result<()> {
-    write_asset(buf, &price.base)?;
-    write_asset(buf, &price.quote)
+    write_network_price(buf, price, cfg!(feature = "testnet"))
+}
+
+pub fn write_network_price(buf: &mut Vec<u8>, price: &Price, testnet: bool) -> Result<()> {
+    // hived rejects non-positive prices, so catch them before signing.
+    if price.base.amount <= 0 || price.quote.amount <= 0 {
+        return Err(HiveError::Serialization(format!(
+            "price amounts must be positive: {} / {}",
+            price.base, price.quote
+        )));
+    }
+    write_network_asset(buf, &price.base, testnet)?;
+    write_network_asset(buf, &price.quote, testnet)
 }
 
 pub fn write_chain_properties(buf: &mut Vec<u8>, props: &ChainProperties) -> Result<()> {
-    write_asset(buf, &props.account_creation_fee)?;
+    write_network_chain_properties(buf, props, cfg!(feature = "testnet"))
+}
+
+pub fn write_network_chain_properties(
+    buf: &mut Vec<u8>,
+    props: &ChainProperties,
+    testnet: bool,
+) -> Result<()> {
+    write_network_asset(buf, &props.account_creation_fee, testnet)?;
     write_u32(buf, props.maximum_block_size);
     write_u16(buf, props.hbd_interest_rate);
     Ok(())
@@ -271,9 +291,12 @@ pub fn format_hive_time(value: DateTime<Utc>) -> String {
 
 #[cfg(test)]
 mod tests {
+    use crate::crypto::PrivateKey;
     use crate::serialization::types::{
-        read_string, read_varint32, write_date, write_string, write_varint32,
+        read_string, read_varint32, write_asset, write_authority, write_date, write_price,
+        write_string, write_varint32,
     };
+    use crate::types::{Asset, Authority, Price};
 
     #[test]
     fn varint_round_trip() {
@@ -311,6 +334,70 @@ mod tests {
         assert!(read_varint32(&mut slice).is_err());
     }
 
+    #[test]
+    fn asset_uses_network_wire_symbol() {
+        let asset = Asset::from_string("1.000 TESTS").expect("asset should parse");
+        let mut buf = Vec::new();
+        write_asset(&mut buf, &asset).expect("asset should serialize");
+
+        let expected: &[u8; 7] = if cfg!(feature = "testnet") {
+            b"TESTS\0\0"
+        } else {
+            b"STEEM\0\0"
+        };
+        assert_eq!(&buf[..8], &1_000_i64.to_le_bytes());
+        assert_eq!(buf[8], 3);
+        assert_eq!(&buf[9..], expected);
+    }
+
+    #[test]
+    fn unsorted_authority_serializes_in_canonical_order() {
+        let mut keys: Vec<_> = ["k1", "k2", "k3"]
+            .iter()
+            .map(|seed| PrivateKey::from_seed(seed).expect("key").public_key())
+            .collect();
+        let unsorted = Authority {
+            weight_threshold: 2,
+            account_auths: vec![("zeb".to_string(), 1), ("abe".to_string(), 2)],
+            key_auths: keys.iter().rev().map(|k| (k.to_string(), 1)).collect(),
+        };
+        let mut buf = Vec::new();
+        write_authority(&mut buf, &unsorted).expect("authority should serialize");
+
+        // threshold, then `[(account, weight)]` by name, then `[(key, weight)]`
+        // by compressed bytes, exactly as hived's fc::raw packs the flat_maps.
+        keys.sort_by_key(|k| k.compressed_bytes());
+        let mut expected = vec![2, 0, 0, 0, 2, 3];
+        expected.extend_from_slice(b"abe\x02\x00\x03zeb\x01\x00");
+        expected.push(3);
+        for key in &keys {
+            expected.extend_from_slice(&key.compressed_bytes());
+            expected.extend_from_slice(&[1, 0]);
+        }
+        assert_eq!(buf, expected);
+
+        let duplicate = Authority {
+            key_auths: vec![(keys[0].to_string(), 1), (keys[0].to_string(), 1)],
+            ..unsorted
+        };
+        assert!(write_authority(&mut Vec::new(), &duplicate).is_err());
+    }
+
+    #[test]
+    fn price_with_zero_amount_is_rejected() {
+        let valid = Price {
+            base: Asset::hbd(1.0),
+            quote: Asset::hive(3.0),
+        };
+        assert!(write_price(&mut Vec::new(), &valid).is_ok());
+
+        let zero = Price {
+            base: Asset::hbd(1.0),
+            quote: Asset::hive(0.0),
+        };
+        assert!(write_price(&mut Vec::new(), &zero).is_err());
+    }
+
     #[test]
     fn date_matches_known_vectors() {
         let mut buf = Vec::new();
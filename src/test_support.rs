@@ -0,0 +1,147 @@
+//! Fixtures shared by tests in more than one module.
+
+use serde_json::{json, Value};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// RC params, pool and stats responses served by [`mount_cost_state`].
+fn cost_state_fixture() -> (Value, Value, Value) {
+    let params_json = json!({
+        "resource_names": [
+            "resource_history_bytes",
+            "resource_new_accounts",
+            "resource_market_bytes",
+            "resource_state_bytes",
+            "resource_execution_time"
+        ],
+        "resource_params": {
+            "resource_history_bytes": {
+                "price_curve_params": { "coeff_a": "1000000000000", "coeff_b": "100000", "shift": 8 },
+                "resource_dynamics_params": {
+                    "resource_unit": 1,
+                    "budget_per_time_unit": 40000,
+                    "pool_eq": 1,
+                    "max_pool_size": 1,
+                    "decay_params": { "decay_per_time_unit": 1, "decay_per_time_unit_denom_shift": 1 },
+                    "min_decay": 0
+                }
+            },
+            "resource_new_accounts": {
+                "price_curve_params": { "coeff_a": "1000000000000", "coeff_b": "100000", "shift": 8 },
+                "resource_dynamics_params": {
+                    "resource_unit": 1,
+                    "budget_per_time_unit": 1000,
+                    "pool_eq": 1,
+                    "max_pool_size": 1,
+                    "decay_params": { "decay_per_time_unit": 1, "decay_per_time_unit_denom_shift": 1 },
+                    "min_decay": 0
+                }
+            },
+            "resource_market_bytes": {
+                "price_curve_params": { "coeff_a": "1000000000000", "coeff_b": "100000", "shift": 8 },
+                "resource_dynamics_params": {
+                    "resource_unit": 1,
+                    "budget_per_time_unit": 10000,
+                    "pool_eq": 1,
+                    "max_pool_size": 1,
+                    "decay_params": { "decay_per_time_unit": 1, "decay_per_time_unit_denom_shift": 1 },
+                    "min_decay": 0
+                }
+            },
+            "resource_state_bytes": {
+                "price_curve_params": { "coeff_a": "1000000000000", "coeff_b": "100000", "shift": 8 },
+                "resource_dynamics_params": {
+                    "resource_unit": 1,
+                    "budget_per_time_unit": 20000,
+                    "pool_eq": 1,
+                    "max_pool_size": 1,
+                    "decay_params": { "decay_per_time_unit": 1, "decay_per_time_unit_denom_shift": 1 },
+                    "min_decay": 0
+                }
+            },
+            "resource_execution_time": {
+                "price_curve_params": { "coeff_a": "1000000000000", "coeff_b": "100000", "shift": 8 },
+                "resource_dynamics_params": {
+                    "resource_unit": 1,
+                    "budget_per_time_unit": 20000,
+                    "pool_eq": 1,
+                    "max_pool_size": 1,
+                    "decay_params": { "decay_per_time_unit": 1, "decay_per_time_unit_denom_shift": 1 },
+                    "min_decay": 0
+                }
+            }
+        },
+        "size_info": {
+            "resource_execution_time": {
+                "transaction_time": 10,
+                "verify_authority_time": 5,
+                "transfer_time": 20
+            },
+            "resource_state_bytes": {
+                "transaction_base_size": 7
+            }
+        }
+    });
+
+    let pool_json = json!({
+        "resource_pool": {
+            "resource_history_bytes": { "pool": 1000000, "fill_level": 10000 },
+            "resource_new_accounts": { "pool": 1000000, "fill_level": 10000 },
+            "resource_market_bytes": { "pool": 1000000, "fill_level": 10000 },
+            "resource_state_bytes": { "pool": 1000000, "fill_level": 10000 },
+            "resource_execution_time": { "pool": 1000000, "fill_level": 10000 }
+        }
+    });
+
+    let stats_json = json!({
+        "rc_stats": {
+            "regen": 5000000,
+            "share": [4000, 10000, 1000, 3000, 2000]
+        }
+    });
+
+    (params_json, pool_json, stats_json)
+}
+
+/// Mounts the `rc_api` responses `RcApi::calculate_cost` needs.
+pub(crate) async fn mount_cost_state(server: &MockServer) {
+    let (params_json, pool_json, stats_json) = cost_state_fixture();
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "call",
+            "params": ["rc_api", "get_resource_params", {}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 0,
+            "jsonrpc": "2.0",
+            "result": params_json
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "call",
+            "params": ["rc_api", "get_resource_pool", {}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 0,
+            "jsonrpc": "2.0",
+            "result": pool_json
+        })))
+        .mount(server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "method": "call",
+            "params": ["rc_api", "get_rc_stats", {}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 0,
+            "jsonrpc": "2.0",
+            "result": stats_json
+        })))
+        .mount(server)
+        .await;
+}
@@ -1,14 +1,20 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use futures::future::{join_all, BoxFuture};
 use rand::Rng;
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
 use crate::error::{HiveError, Result};
-use crate::transport::HttpTransport;
+use crate::transport::{HttpTransport, RpcEnvelope, Transport};
 
+/// Delay before trying the next node after a node fails. `Exponential`
+/// starts at `base_ms` and doubles with each consecutive failure, capped at
+/// `max_ms`.
 #[derive(Debug, Clone)]
 pub enum BackoffStrategy {
     Exponential { base_ms: u64, max_ms: u64 },
@@ -25,6 +31,56 @@ impl Default for BackoffStrategy {
     }
 }
 
+/// Random spread added to each backoff delay so clients that failed together
+/// don't retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitterPolicy {
+    /// Sleep exactly the [`BackoffStrategy`] delay.
+    None,
+    /// Add up to 10% of the delay.
+    #[default]
+    Proportional,
+    /// Sleep a uniform random time between zero and the delay.
+    Full,
+    /// Sleep half the delay plus a uniform random share of the other half.
+    Equal,
+}
+
+/// RPC error messages that mean "this node can't serve the call" rather than
+/// "the call is invalid", so the next node is tried instead of failing.
+pub const DEFAULT_RETRYABLE_RPC_ERRORS: &[&str] = &["could not find method", "could not find api"];
+
+/// Whether `error` is an RPC error saying the node does not serve the API or
+/// method, i.e. one matching [`DEFAULT_RETRYABLE_RPC_ERRORS`]. Callers use it
+/// to retry through another API on the same node.
+pub(crate) fn is_missing_api_error(error: &HiveError) -> bool {
+    let HiveError::Rpc { message, .. } = error else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    DEFAULT_RETRYABLE_RPC_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Outcome of calling a single node directly, outside the failover order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeProbe {
+    pub node: String,
+    pub ok: bool,
+    pub latency: Duration,
+    pub head_block_number: Option<u32>,
+    pub error: Option<String>,
+}
+
+/// A node probe compared against the highest head block seen across all nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSyncStatus {
+    pub probe: NodeProbe,
+    pub lag_blocks: Option<u32>,
+    pub behind: bool,
+}
+
 #[derive(Debug)]
 struct FailoverState {
     current_index: usize,
@@ -36,9 +92,14 @@ pub struct FailoverTransport {
     transports: Vec<HttpTransport>,
     failover_threshold: u32,
     backoff: BackoffStrategy,
+    jitter: JitterPolicy,
+    retryable_rpc_errors: Vec<String>,
     state: Arc<Mutex<FailoverState>>,
+    last_node: Arc<AtomicUsize>,
 }
 
+const NO_NODE: usize = usize::MAX;
+
 impl FailoverTransport {
     pub fn new(
         nodes: &[String],
@@ -56,55 +117,174 @@ impl FailoverTransport {
             transports,
             failover_threshold: failover_threshold.max(1),
             backoff,
+            jitter: JitterPolicy::default(),
+            retryable_rpc_errors: DEFAULT_RETRYABLE_RPC_ERRORS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
             state: Arc::new(Mutex::new(FailoverState {
                 current_index: 0,
                 failures,
             })),
+            last_node: Arc::new(AtomicUsize::new(NO_NODE)),
         })
     }
 
+    /// Replaces the case-insensitive message fragments that make an RPC error
+    /// fall through to the next node.
+    pub fn with_retryable_rpc_errors(mut self, patterns: Vec<String>) -> Self {
+        self.retryable_rpc_errors = patterns
+            .into_iter()
+            .map(|pattern| pattern.to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: JitterPolicy) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
     pub async fn call<T: DeserializeOwned>(
         &self,
         api: &str,
         method: &str,
         params: Value,
     ) -> Result<T> {
+        self.call_with_timeout(api, method, params, None).await
+    }
+
+    /// Like [`FailoverTransport::call`], with `timeout` replacing the per-node
+    /// request timeout for this call when given.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "hive_rpc", level = "debug", skip(self, params))
+    )]
+    pub async fn call_with_timeout<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+        timeout: Option<Duration>,
+    ) -> Result<T> {
+        self.call_with_failover(|index| {
+            self.transports[index].call_with_timeout(api, method, params.clone(), timeout)
+        })
+        .await
+    }
+
+    /// Like [`FailoverTransport::call`], but returns the full JSON-RPC
+    /// response. RPC errors that would fail over still do; any other RPC error
+    /// is returned inside the envelope.
+    pub async fn call_envelope<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<RpcEnvelope<T>> {
+        self.call_with_failover(|index| {
+            let params = params.clone();
+            async move {
+                let envelope = self.transports[index]
+                    .call_envelope(api, method, params)
+                    .await?;
+                match &envelope.error {
+                    Some(err) => {
+                        let err = HiveError::from(err.clone());
+                        if self.is_retryable_rpc_error(&err) {
+                            Err(err)
+                        } else {
+                            Ok(envelope)
+                        }
+                    }
+                    None => Ok(envelope),
+                }
+            }
+        })
+        .await
+    }
+
+    /// Like [`FailoverTransport::call`], also returning the URL of the node
+    /// that answered.
+    pub async fn call_with_node<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<(T, String)> {
+        self.call_with_failover(|index| {
+            let params = params.clone();
+            async move {
+                let transport = &self.transports[index];
+                let result = transport.call(api, method, params).await?;
+                Ok((result, transport.node_url().to_string()))
+            }
+        })
+        .await
+    }
+
+    async fn call_with_failover<R, F, Fut>(&self, mut attempt: F) -> Result<R>
+    where
+        F: FnMut(usize) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
         if self.transports.is_empty() {
             return Err(HiveError::AllNodesFailed);
         }
 
         let start_index = self.state.lock().await.current_index;
         let mut had_transport_error = false;
+        let mut last_rpc_error = None;
 
         for offset in 0..self.transports.len() {
             let index = (start_index + offset) % self.transports.len();
 
-            match self.transports[index]
-                .call(api, method, params.clone())
-                .await
-            {
+            #[cfg(feature = "tracing")]
+            let started = std::time::Instant::now();
+            let result = attempt(index).await;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                node = self.transports[index].node_url(),
+                attempt = offset + 1,
+                latency_ms = started.elapsed().as_millis() as u64,
+                ok = result.is_ok(),
+                "rpc attempt finished"
+            );
+
+            match result {
                 Ok(result) => {
+                    self.last_node.store(index, Ordering::Relaxed);
                     let mut state = self.state.lock().await;
                     state.current_index = index;
                     state.failures[index] = 0;
                     return Ok(result);
                 }
-                Err(HiveError::Rpc {
-                    code,
-                    message,
-                    data,
-                }) => {
-                    return Err(HiveError::Rpc {
-                        code,
-                        message,
-                        data,
-                    })
+                Err(err @ HiveError::Rpc { .. }) => {
+                    if !self.is_retryable_rpc_error(&err) {
+                        return Err(err);
+                    }
+
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        node = self.transports[index].node_url(),
+                        attempt = offset + 1,
+                        error = %err,
+                        "node cannot serve call, trying next node"
+                    );
+                    last_rpc_error = Some(err);
                 }
                 Err(err) => {
                     if !Self::is_retryable_transport_error(&err) {
                         return Err(err);
                     }
 
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        node = self.transports[index].node_url(),
+                        attempt = offset + 1,
+                        error = %err,
+                        "node failed, failing over"
+                    );
                     let _ = err;
                     had_transport_error = true;
 
@@ -120,13 +300,21 @@ impl FailoverTransport {
                     // Only back off if another node is still going to be tried;
                     // sleeping after the final attempt just delays the error.
                     if offset + 1 < self.transports.len() {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(
+                            delay_ms = delay.as_millis() as u64,
+                            node_failures,
+                            "backing off before next node"
+                        );
                         tokio::time::sleep(delay).await;
                     }
                 }
             }
         }
 
-        if had_transport_error {
+        if let Some(err) = last_rpc_error {
+            Err(err)
+        } else if had_transport_error {
             Err(HiveError::AllNodesFailed)
         } else {
             Err(HiveError::Other(
@@ -135,20 +323,125 @@ impl FailoverTransport {
         }
     }
 
+    /// URL of the node that answered the most recent successful call.
+    pub fn last_node(&self) -> Option<&str> {
+        let index = self.last_node.load(Ordering::Relaxed);
+        self.transports
+            .get(index)
+            .map(|transport| transport.node_url())
+    }
+
+    /// Fetches dynamic global properties from every node concurrently and
+    /// returns the results with healthy nodes first, fastest to slowest.
+    pub async fn probe_nodes(&self) -> Vec<NodeProbe> {
+        let probes = self.transports.iter().map(|transport| async move {
+            let started = Instant::now();
+            let result = transport
+                .call::<Value>("condenser_api", "get_dynamic_global_properties", json!([]))
+                .await;
+            let latency = started.elapsed();
+            match result {
+                Ok(props) => NodeProbe {
+                    node: transport.node_url().to_string(),
+                    ok: true,
+                    latency,
+                    head_block_number: props
+                        .get("head_block_number")
+                        .and_then(Value::as_u64)
+                        .and_then(|number| u32::try_from(number).ok()),
+                    error: None,
+                },
+                Err(err) => NodeProbe {
+                    node: transport.node_url().to_string(),
+                    ok: false,
+                    latency,
+                    head_block_number: None,
+                    error: Some(err.to_string()),
+                },
+            }
+        });
+
+        let mut probes = join_all(probes).await;
+        probes.sort_by_key(|probe| (!probe.ok, probe.latency));
+        probes
+    }
+
+    /// Probes every node and flags those more than `max_lag_blocks` behind the
+    /// most advanced one. If the preferred node is behind or unreachable, the
+    /// fastest in-sync node becomes preferred instead.
+    pub async fn check_sync(&self, max_lag_blocks: u32) -> Vec<NodeSyncStatus> {
+        let probes = self.probe_nodes().await;
+        let max_head = probes
+            .iter()
+            .filter_map(|probe| probe.head_block_number)
+            .max();
+
+        let statuses = probes
+            .into_iter()
+            .map(|probe| {
+                let lag_blocks = probe
+                    .head_block_number
+                    .zip(max_head)
+                    .map(|(head, max)| max.saturating_sub(head));
+                NodeSyncStatus {
+                    behind: lag_blocks.is_some_and(|lag| lag > max_lag_blocks),
+                    lag_blocks,
+                    probe,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let healthy = |status: &NodeSyncStatus| status.probe.ok && !status.behind;
+        let preferred = statuses
+            .iter()
+            .find(|status| healthy(status))
+            .and_then(|status| self.node_index(&status.probe.node));
+        if let Some(preferred) = preferred {
+            let mut state = self.state.lock().await;
+            let current_url = self.transports[state.current_index].node_url();
+            let current_healthy = statuses
+                .iter()
+                .any(|status| status.probe.node == current_url && healthy(status));
+            if !current_healthy {
+                state.current_index = preferred;
+            }
+        }
+
+        statuses
+    }
+
+    fn node_index(&self, node: &str) -> Option<usize> {
+        self.transports
+            .iter()
+            .position(|transport| transport.node_url() == node)
+    }
+
+    fn is_retryable_rpc_error(&self, error: &HiveError) -> bool {
+        let HiveError::Rpc { message, .. } = error else {
+            return false;
+        };
+        let message = message.to_ascii_lowercase();
+        self.retryable_rpc_errors
+            .iter()
+            .any(|pattern| message.contains(pattern.as_str()))
+    }
+
     fn is_retryable_transport_error(error: &HiveError) -> bool {
         matches!(
             error,
-            HiveError::Transport(_) | HiveError::Timeout | HiveError::AllNodesFailed
+            HiveError::Transport { .. } | HiveError::Timeout | HiveError::AllNodesFailed
         )
     }
 
-    fn backoff_delay(&self, tries: u32) -> Duration {
+    /// How long to wait before the next node after a node's `tries`-th
+    /// consecutive failure, jitter included.
+    pub fn backoff_delay(&self, tries: u32) -> Duration {
         let tries = tries.max(1);
         let millis = match self.backoff {
             BackoffStrategy::Exponential { base_ms, max_ms } => {
-                let step = (base_ms / 10).max(1);
-                let scaled_tries = tries as u64 * step;
-                scaled_tries.saturating_mul(scaled_tries).min(max_ms)
+                // base_ms * 2^(tries - 1), saturating long before the shift overflows.
+                let factor = 1_u64.checked_shl(tries - 1).unwrap_or(u64::MAX);
+                base_ms.saturating_mul(factor).min(max_ms)
             }
             BackoffStrategy::Linear { step_ms, max_ms } => {
                 step_ms.saturating_mul(tries as u64).min(max_ms)
@@ -156,13 +449,65 @@ impl FailoverTransport {
             BackoffStrategy::Fixed { ms } => ms,
         };
 
-        // Small positive jitter to avoid synchronized retries.
-        let jitter = if millis > 0 {
-            rand::thread_rng().gen_range(0..=millis / 10)
-        } else {
-            0
+        let millis = match self.jitter {
+            _ if millis == 0 => 0,
+            JitterPolicy::None => millis,
+            JitterPolicy::Proportional => {
+                millis.saturating_add(rand::thread_rng().gen_range(0..=millis / 10))
+            }
+            JitterPolicy::Full => rand::thread_rng().gen_range(0..=millis),
+            JitterPolicy::Equal => {
+                let half = millis / 2;
+                half + rand::thread_rng().gen_range(0..=millis - half)
+            }
         };
-        Duration::from_millis(millis.saturating_add(jitter))
+        Duration::from_millis(millis)
+    }
+}
+
+impl Transport for FailoverTransport {
+    fn call<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+        timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<Value>> {
+        Box::pin(self.call_with_timeout(api, method, params, timeout))
+    }
+
+    fn call_envelope<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+    ) -> BoxFuture<'a, Result<RpcEnvelope<Value>>> {
+        Box::pin(FailoverTransport::call_envelope(self, api, method, params))
+    }
+
+    fn call_with_node<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+    ) -> BoxFuture<'a, Result<(Value, Option<String>)>> {
+        Box::pin(async move {
+            let (result, node) =
+                FailoverTransport::call_with_node(self, api, method, params).await?;
+            Ok((result, Some(node)))
+        })
+    }
+
+    fn last_node(&self) -> Option<String> {
+        FailoverTransport::last_node(self).map(str::to_string)
+    }
+
+    fn probe_nodes(&self) -> BoxFuture<'_, Vec<NodeProbe>> {
+        Box::pin(FailoverTransport::probe_nodes(self))
+    }
+
+    fn check_sync(&self, max_lag_blocks: u32) -> BoxFuture<'_, Vec<NodeSyncStatus>> {
+        Box::pin(FailoverTransport::check_sync(self, max_lag_blocks))
     }
 }
 
@@ -176,13 +521,71 @@ mod tests {
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     use crate::error::HiveError;
-    use crate::transport::{BackoffStrategy, FailoverTransport};
+    use crate::transport::{BackoffStrategy, FailoverTransport, JitterPolicy};
 
     #[derive(Debug, Deserialize)]
     struct Ping {
         pong: bool,
     }
 
+    #[test]
+    fn exponential_backoff_doubles_and_caps() {
+        let transport = FailoverTransport::new(
+            &["http://127.0.0.1:1".to_string()],
+            Duration::from_secs(1),
+            1,
+            BackoffStrategy::Exponential {
+                base_ms: 100,
+                max_ms: 2_000,
+            },
+        )
+        .expect("transport should initialize")
+        .with_jitter(JitterPolicy::None);
+
+        let schedule = (1..=6)
+            .map(|tries| transport.backoff_delay(tries).as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(schedule, vec![100, 200, 400, 800, 1_600, 2_000]);
+        assert_eq!(transport.backoff_delay(64), Duration::from_millis(2_000));
+        assert_eq!(
+            transport.backoff_delay(u32::MAX),
+            Duration::from_millis(2_000)
+        );
+    }
+
+    #[test]
+    fn backoff_schedule_is_exact_without_jitter() {
+        let transport = |jitter| {
+            FailoverTransport::new(
+                &["http://127.0.0.1:1".to_string()],
+                Duration::from_secs(1),
+                1,
+                BackoffStrategy::Linear {
+                    step_ms: 100,
+                    max_ms: 450,
+                },
+            )
+            .expect("transport should initialize")
+            .with_jitter(jitter)
+        };
+
+        let exact = transport(JitterPolicy::None);
+        let schedule = (1..=6)
+            .map(|tries| exact.backoff_delay(tries).as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(schedule, vec![100, 200, 300, 400, 450, 450]);
+
+        for tries in 1..=6 {
+            let base = exact.backoff_delay(tries);
+            let full = transport(JitterPolicy::Full).backoff_delay(tries);
+            let equal = transport(JitterPolicy::Equal).backoff_delay(tries);
+            let proportional = transport(JitterPolicy::Proportional).backoff_delay(tries);
+            assert!(full <= base);
+            assert!(equal >= base / 2 && equal <= base);
+            assert!(proportional >= base && proportional <= base + base / 10);
+        }
+    }
+
     #[tokio::test]
     async fn fails_over_to_next_node_when_first_node_is_unhealthy() {
         let first = MockServer::start().await;
@@ -216,6 +619,46 @@ mod tests {
             .expect("second node should be used");
 
         assert!(result.pong);
+        assert_eq!(transport.last_node(), Some(second.uri().as_str()));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn emits_failover_event_when_first_node_errors() {
+        let first = MockServer::start().await;
+        let second = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&first)
+            .await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": { "pong": true }
+            })))
+            .mount(&second)
+            .await;
+
+        let transport = FailoverTransport::new(
+            &[first.uri(), second.uri()],
+            Duration::from_secs(2),
+            1,
+            BackoffStrategy::Fixed { ms: 0 },
+        )
+        .expect("transport should initialize");
+
+        let result: Ping = transport
+            .call("condenser_api", "get_config", json!([]))
+            .await
+            .expect("second node should be used");
+
+        assert!(result.pong);
+        assert!(logs_contain("failing over"));
+        assert!(logs_contain(&first.uri()));
     }
 
     #[tokio::test]
@@ -267,6 +710,50 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn retries_next_node_when_method_is_missing() {
+        let first = MockServer::start().await;
+        let second = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "error": {
+                    "code": -32003,
+                    "message": "Assert Exception:api_itr != _registered_apis.end(): Could not find method get_config"
+                }
+            })))
+            .expect(1)
+            .mount(&first)
+            .await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 0,
+                "jsonrpc": "2.0",
+                "result": { "pong": true }
+            })))
+            .expect(1)
+            .mount(&second)
+            .await;
+
+        let transport = FailoverTransport::new(
+            &[first.uri(), second.uri()],
+            Duration::from_secs(2),
+            1,
+            BackoffStrategy::default(),
+        )
+        .expect("transport should initialize");
+
+        let result: Ping = transport
+            .call("condenser_api", "get_config", json!([]))
+            .await
+            .expect("second node should serve the method");
+
+        assert!(result.pong);
+    }
+
     #[tokio::test]
     async fn returns_all_nodes_failed_when_every_node_is_unhealthy() {
         let first = MockServer::start().await;
@@ -338,8 +825,8 @@ mod tests {
             .expect_err("serialization error should be returned directly");
 
         match err {
-            HiveError::Serialization(_) => {}
-            other => panic!("expected HiveError::Serialization, got {other:?}"),
+            HiveError::Json(_) => {}
+            other => panic!("expected HiveError::Json, got {other:?}"),
         }
     }
 }
@@ -30,6 +30,77 @@ impl HttpTransport {
         method: &str,
         params: Value,
     ) -> Result<T> {
+        self.call_with_timeout(api, method, params, None).await
+    }
+
+    /// Like [`HttpTransport::call`], with `timeout` replacing the transport's
+    /// default for this request when given.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "hive_rpc_http",
+            level = "debug",
+            skip(self, params),
+            fields(node = %self.node_url)
+        )
+    )]
+    pub async fn call_with_timeout<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+        timeout: Option<Duration>,
+    ) -> Result<T> {
+        let body = self.post(api, method, params, timeout).await?;
+
+        if let Some(err) = body.get("error") {
+            let err = RpcError::from_value(err);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(code = err.code, message = %err.message, "node returned RPC error");
+            return Err(err.into());
+        }
+
+        let value = body
+            .get("result")
+            .cloned()
+            .ok_or_else(|| HiveError::json("missing JSON-RPC result field"))?;
+
+        serde_json::from_value(value).map_err(Into::into)
+    }
+
+    /// Like [`HttpTransport::call`], but returns the whole JSON-RPC response,
+    /// leaving an RPC error in [`RpcEnvelope::error`] instead of failing.
+    pub async fn call_envelope<T: DeserializeOwned>(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<RpcEnvelope<T>> {
+        let mut body = self.post(api, method, params, None).await?;
+        let result = body
+            .get_mut("result")
+            .map(|value| serde_json::from_value(value.take()))
+            .transpose()?;
+
+        Ok(RpcEnvelope {
+            id: body.get("id").cloned().unwrap_or(Value::Null),
+            jsonrpc: body
+                .get("jsonrpc")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            result,
+            error: body.get("error").map(RpcError::from_value),
+        })
+    }
+
+    async fn post(
+        &self,
+        api: &str,
+        method: &str,
+        params: Value,
+        timeout: Option<Duration>,
+    ) -> Result<Value> {
         let payload = json!({
             "id": 0,
             "jsonrpc": "2.0",
@@ -37,44 +108,74 @@ impl HttpTransport {
             "params": [api, method, params],
         });
 
-        let response = self
-            .client
-            .post(&self.node_url)
-            .json(&payload)
-            .send()
-            .await?;
+        let mut request = self.client.post(&self.node_url).json(&payload);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().await?;
         if !response.status().is_success() {
-            return Err(HiveError::Transport(format!(
+            #[cfg(feature = "tracing")]
+            tracing::debug!(status = %response.status(), "node returned non-success HTTP status");
+            return Err(HiveError::transport(format!(
                 "node {} returned HTTP {}",
                 self.node_url,
                 response.status()
             )));
         }
 
-        let body: Value = response.json().await?;
+        Ok(response.json().await?)
+    }
+}
 
-        if let Some(err) = body.get("error") {
-            let code = err.get("code").and_then(Value::as_i64).unwrap_or(-32000);
-            let message = err
+/// A complete JSON-RPC response, as returned by `call_envelope`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcEnvelope<T> {
+    pub id: Value,
+    pub jsonrpc: String,
+    pub result: Option<T>,
+    pub error: Option<RpcError>,
+}
+
+impl<T> RpcEnvelope<T> {
+    /// The result, or the RPC error as [`HiveError::Rpc`].
+    pub fn into_result(self) -> Result<T> {
+        if let Some(err) = self.error {
+            return Err(err.into());
+        }
+        self.result
+            .ok_or_else(|| HiveError::json("missing JSON-RPC result field"))
+    }
+}
+
+/// The `error` member of a JSON-RPC response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    fn from_value(err: &Value) -> Self {
+        Self {
+            code: err.get("code").and_then(Value::as_i64).unwrap_or(-32000),
+            message: err
                 .get("message")
                 .and_then(Value::as_str)
                 .unwrap_or("unknown rpc error")
-                .to_string();
-            let data = err.get("data").cloned();
-
-            return Err(HiveError::Rpc {
-                code,
-                message,
-                data,
-            });
+                .to_string(),
+            data: err.get("data").cloned(),
         }
+    }
+}
 
-        let value = body
-            .get("result")
-            .cloned()
-            .ok_or_else(|| HiveError::Serialization("missing JSON-RPC result field".to_string()))?;
-
-        serde_json::from_value(value).map_err(Into::into)
+impl From<RpcError> for HiveError {
+    fn from(err: RpcError) -> Self {
+        HiveError::Rpc {
+            code: err.code,
+            message: err.message,
+            data: err.data,
+        }
     }
 }
 
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::{self, BoxFuture};
+use serde_json::Value;
+
+use crate::error::{HiveError, Result};
+use crate::transport::Transport;
+
+type Responder = Arc<dyn Fn(&Value) -> Result<Value> + Send + Sync>;
+
+/// In-memory transport answering `(api, method)` calls from registered
+/// responders, for testing code built on [`Client`](crate::Client) without
+/// HTTP. Hand it to [`Client::from_transport`](crate::Client::from_transport).
+///
+/// Calls with no responder fail with an RPC "could not find method" error,
+/// as a node missing the plugin would.
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    responders: Arc<Mutex<HashMap<(String, String), Responder>>>,
+    calls: Arc<Mutex<Vec<(String, String, Value)>>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers every `api.method` call with `result`.
+    pub fn respond(&self, api: &str, method: &str, result: Value) -> &Self {
+        self.respond_with(api, method, move |_| Ok(result.clone()))
+    }
+
+    /// Answers every `api.method` call with `responder(params)`.
+    pub fn respond_with(
+        &self,
+        api: &str,
+        method: &str,
+        responder: impl Fn(&Value) -> Result<Value> + Send + Sync + 'static,
+    ) -> &Self {
+        lock(&self.responders).insert((api.to_string(), method.to_string()), Arc::new(responder));
+        self
+    }
+
+    /// Every call made so far as `(api, method, params)`, oldest first.
+    pub fn calls(&self) -> Vec<(String, String, Value)> {
+        lock(&self.calls).clone()
+    }
+
+    fn dispatch(&self, api: &str, method: &str, params: Value) -> Result<Value> {
+        let responder = lock(&self.responders)
+            .get(&(api.to_string(), method.to_string()))
+            .cloned();
+        let result = match &responder {
+            Some(responder) => responder(&params),
+            None => Err(HiveError::Rpc {
+                code: -32601,
+                message: format!("could not find method {api}.{method}"),
+                data: None,
+            }),
+        };
+        lock(&self.calls).push((api.to_string(), method.to_string(), params));
+        result
+    }
+}
+
+impl Transport for MockTransport {
+    fn call<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+        _timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<Value>> {
+        Box::pin(future::ready(self.dispatch(api, method, params)))
+    }
+}
+
+impl std::fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut methods = lock(&self.responders)
+            .keys()
+            .map(|(api, method)| format!("{api}.{method}"))
+            .collect::<Vec<_>>();
+        methods.sort();
+        f.debug_struct("MockTransport")
+            .field("responders", &methods)
+            .finish()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use crate::client::{Client, ClientOptions};
+    use crate::error::HiveError;
+    use crate::transport::MockTransport;
+
+    #[tokio::test]
+    async fn client_answers_from_registered_responders() {
+        let transport = MockTransport::new();
+        transport.respond("condenser_api", "get_account_count", json!(1234));
+        let client = Client::from_transport(Arc::new(transport.clone()), ClientOptions::default());
+
+        let count = client
+            .database
+            .get_account_count()
+            .await
+            .expect("mock should answer");
+        assert_eq!(count, 1234);
+        assert_eq!(
+            transport.calls(),
+            vec![(
+                "condenser_api".to_string(),
+                "get_account_count".to_string(),
+                json!([])
+            )]
+        );
+
+        let err = client
+            .database
+            .get_config()
+            .await
+            .expect_err("unregistered method should fail");
+        assert!(matches!(err, HiveError::Rpc { code: -32601, .. }), "{err}");
+        assert_eq!(client.last_node(), None);
+    }
+}
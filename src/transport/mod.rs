@@ -1,5 +1,11 @@
 pub mod failover;
 pub mod http;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+pub mod traits;
 
 pub use failover::*;
 pub use http::*;
+#[cfg(any(test, feature = "test-util"))]
+pub use mock::*;
+pub use traits::*;
@@ -0,0 +1,150 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
+use futures::future::{self, BoxFuture};
+use serde_json::{json, Value};
+
+use crate::error::{HiveError, Result};
+use crate::transport::{NodeProbe, NodeSyncStatus, RpcEnvelope, RpcError};
+
+/// What a [`Client`](crate::Client) sends its calls through. Object safe, so
+/// a client holds any implementation as `Arc<dyn Transport>`; see
+/// [`Client::from_transport`](crate::Client::from_transport).
+///
+/// Only [`Transport::call`] is required. The node-level methods default to
+/// reporting nothing, for transports that are not backed by a node pool.
+pub trait Transport: Debug + Send + Sync {
+    /// Sends `api.method(params)` and returns the JSON-RPC `result`, failing
+    /// with [`HiveError::Rpc`] when the node answers with an error. `timeout`,
+    /// when given, replaces the transport's default for this call.
+    fn call<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+        timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<Value>>;
+
+    /// Like [`Transport::call`], but keeps an RPC error in the envelope.
+    fn call_envelope<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+    ) -> BoxFuture<'a, Result<RpcEnvelope<Value>>> {
+        Box::pin(async move {
+            let (result, error) = match self.call(api, method, params, None).await {
+                Ok(value) => (Some(value), None),
+                Err(HiveError::Rpc {
+                    code,
+                    message,
+                    data,
+                }) => (
+                    None,
+                    Some(RpcError {
+                        code,
+                        message,
+                        data,
+                    }),
+                ),
+                Err(err) => return Err(err),
+            };
+            Ok(RpcEnvelope {
+                id: json!(0),
+                jsonrpc: "2.0".to_string(),
+                result,
+                error,
+            })
+        })
+    }
+
+    /// Like [`Transport::call`], also returning the URL of the node that
+    /// answered, when the transport knows it.
+    fn call_with_node<'a>(
+        &'a self,
+        api: &'a str,
+        method: &'a str,
+        params: Value,
+    ) -> BoxFuture<'a, Result<(Value, Option<String>)>> {
+        Box::pin(async move {
+            let value = self.call(api, method, params, None).await?;
+            Ok((value, None))
+        })
+    }
+
+    /// URL of the node that answered the most recent successful call.
+    fn last_node(&self) -> Option<String> {
+        None
+    }
+
+    fn probe_nodes(&self) -> BoxFuture<'_, Vec<NodeProbe>> {
+        Box::pin(future::ready(Vec::new()))
+    }
+
+    fn check_sync(&self, _max_lag_blocks: u32) -> BoxFuture<'_, Vec<NodeSyncStatus>> {
+        Box::pin(future::ready(Vec::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use futures::future::{self, BoxFuture};
+    use serde_json::{json, Value};
+
+    use crate::client::{Client, ClientOptions};
+    use crate::error::{HiveError, Result};
+    use crate::transport::Transport;
+
+    /// Answers `get_config` and counts calls; nothing else.
+    #[derive(Debug, Default)]
+    struct ConfigOnly {
+        calls: AtomicUsize,
+    }
+
+    impl Transport for ConfigOnly {
+        fn call<'a>(
+            &'a self,
+            api: &'a str,
+            method: &'a str,
+            _params: Value,
+            _timeout: Option<Duration>,
+        ) -> BoxFuture<'a, Result<Value>> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            let result = match (api, method) {
+                ("condenser_api", "get_config") => Ok(json!({"HIVE_CHAIN_ID": "beeab0de"})),
+                _ => Err(HiveError::Rpc {
+                    code: -32601,
+                    message: format!("could not find method {api}.{method}"),
+                    data: None,
+                }),
+            };
+            Box::pin(future::ready(result))
+        }
+    }
+
+    #[tokio::test]
+    async fn client_drives_calls_through_custom_transport() {
+        let transport = Arc::new(ConfigOnly::default());
+        let client = Client::from_transport(transport.clone(), ClientOptions::default());
+
+        let config = client
+            .database
+            .get_config()
+            .await
+            .expect("custom transport should answer");
+        assert_eq!(config["HIVE_CHAIN_ID"], "beeab0de");
+
+        let envelope = client
+            .call_envelope::<Value>("condenser_api", "get_version", json!([]))
+            .await
+            .expect("rpc errors stay in the envelope");
+        assert_eq!(envelope.error.map(|err| err.code), Some(-32601));
+        assert_eq!(transport.calls.load(Ordering::Relaxed), 2);
+        assert!(client.last_node().is_none());
+        assert!(client.probe_nodes().await.is_empty());
+    }
+}
@@ -1,10 +1,15 @@
 use std::collections::BTreeMap;
 
+use chrono::{DateTime, Utc};
 use serde::de::Error as _;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::crypto::{KeyRole, PublicKey};
+use crate::error::Result;
+use crate::serialization::types::parse_hive_time;
 use crate::types::{Asset, Authority, Manabar, Operation};
+use crate::utils::reputation_score;
 
 fn deserialize_stringified<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
 where
@@ -20,6 +25,15 @@ where
     }
 }
 
+fn deserialize_i64_like<'de, D>(deserializer: D) -> std::result::Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let text = deserialize_stringified(deserializer)?;
+    text.parse::<i64>()
+        .map_err(|err| D::Error::custom(format!("invalid integer '{text}': {err}")))
+}
+
 fn deserialize_opt_stringified<'de, D>(
     deserializer: D,
 ) -> std::result::Result<Option<String>, D::Error>
@@ -78,6 +92,8 @@ pub struct ExtendedAccount {
     pub to_withdraw: Option<String>,
     #[serde(default, deserialize_with = "deserialize_opt_stringified")]
     pub withdrawn: Option<String>,
+    #[serde(default)]
+    pub next_vesting_withdrawal: Option<String>,
 
     // Mana
     #[serde(default)]
@@ -121,55 +137,267 @@ pub struct ExtendedAccount {
     pub extra: BTreeMap<String, Value>,
 }
 
+impl ExtendedAccount {
+    pub fn authority(&self, role: KeyRole) -> Option<&Authority> {
+        match role {
+            KeyRole::Owner => self.owner.as_ref(),
+            KeyRole::Active => self.active.as_ref(),
+            KeyRole::Posting => self.posting.as_ref(),
+            KeyRole::Memo => None,
+        }
+    }
+
+    /// The memo key, or `None` when the account has none or has set the null
+    /// key to disable encrypted memos. A malformed key is an error.
+    pub fn memo_public_key(&self) -> Result<Option<PublicKey>> {
+        let Some(memo_key) = self.memo_key.as_deref() else {
+            return Ok(None);
+        };
+        let key = PublicKey::from_string(memo_key)?;
+        Ok((!key.is_null()).then_some(key))
+    }
+
+    /// Weighted keys that can act for `role`: the role's own keys plus the
+    /// keys of each referenced account, expanded one level through `resolver`
+    /// and carrying the reference's weight. A key reachable more than once
+    /// keeps its highest weight. For `Memo` this is just the memo key.
+    pub fn effective_keys(
+        &self,
+        role: KeyRole,
+        resolver: impl Fn(&str) -> Option<Authority>,
+    ) -> Vec<(String, u16)> {
+        if role == KeyRole::Memo {
+            return self.memo_key.iter().map(|key| (key.clone(), 1)).collect();
+        }
+        self.authority(role)
+            .map(|authority| authority.weighted_keys(resolver))
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct AccountReputation {
     pub account: String,
-    #[serde(deserialize_with = "deserialize_stringified")]
-    pub reputation: String,
+    #[serde(deserialize_with = "deserialize_i64_like")]
+    pub reputation: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+impl AccountReputation {
+    /// Reputation on the familiar 25-based display scale.
+    pub fn score(&self) -> f64 {
+        reputation_score(self.reputation)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct OwnerHistory {
     #[serde(default)]
-    pub account: Option<String>,
-    #[serde(default)]
-    pub previous_owner_authority: Option<Value>,
-    #[serde(default)]
-    pub last_valid_time: Option<String>,
+    pub id: u64,
+    pub account: String,
+    pub previous_owner_authority: Authority,
+    pub last_valid_time: String,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+impl OwnerHistory {
+    /// Last moment `previous_owner_authority` was the account's owner; it can
+    /// prove ownership in a recovery for 30 days after this.
+    pub fn last_valid_at(&self) -> Result<DateTime<Utc>> {
+        parse_hive_time(&self.last_valid_time)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RecoveryRequest {
     #[serde(default)]
-    pub account_to_recover: Option<String>,
-    #[serde(default)]
-    pub recovery_account: Option<String>,
-    #[serde(default)]
-    pub new_owner_authority: Option<Value>,
-    #[serde(default)]
-    pub expires: Option<String>,
+    pub id: u64,
+    pub account_to_recover: String,
+    pub new_owner_authority: Authority,
+    pub expires: String,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+impl RecoveryRequest {
+    pub fn expires_at(&self) -> Result<DateTime<Utc>> {
+        parse_hive_time(&self.expires)
+    }
+
+    /// Whether the request has lapsed at `now`; the chain drops it once
+    /// `expires` has passed.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> Result<bool> {
+        Ok(self.expires_at()? <= now)
+    }
+}
+
+fn deserialize_bool_like<'de, D>(deserializer: D) -> std::result::Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Bool(flag) => Ok(flag),
+        Value::Number(number) => Ok(number.as_u64().map(|value| value != 0).unwrap_or(false)),
+        Value::Null => Ok(false),
+        other => Err(D::Error::custom(format!(
+            "expected bool or number, got {other}"
+        ))),
+    }
+}
+
+/// One `[sequence, operation_object]` pair returned by `get_account_history`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct AccountHistoryEntry {
-    pub index: u64,
+    pub sequence: u64,
+    pub trx_id: String,
+    pub block: u32,
+    pub trx_in_block: u32,
+    pub op_in_trx: u32,
+    pub virtual_op: bool,
+    pub timestamp: String,
+    pub op: Operation,
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AccountHistoryObject {
+    #[serde(default)]
+    trx_id: String,
+    #[serde(default)]
+    block: u32,
+    #[serde(default)]
+    trx_in_block: u32,
     #[serde(default)]
-    pub timestamp: Option<String>,
+    op_in_trx: u32,
+    #[serde(default, deserialize_with = "deserialize_bool_like")]
+    virtual_op: bool,
     #[serde(default)]
-    pub op: Option<Operation>,
+    timestamp: String,
+    op: Operation,
     #[serde(flatten)]
-    pub extra: BTreeMap<String, Value>,
+    extra: BTreeMap<String, Value>,
+}
+
+impl Serialize for AccountHistoryEntry {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let object = AccountHistoryObject {
+            trx_id: self.trx_id.clone(),
+            block: self.block,
+            trx_in_block: self.trx_in_block,
+            op_in_trx: self.op_in_trx,
+            virtual_op: self.virtual_op,
+            timestamp: self.timestamp.clone(),
+            op: self.op.clone(),
+            extra: self.extra.clone(),
+        };
+        (self.sequence, object).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountHistoryEntry {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (sequence, object) = <(u64, AccountHistoryObject)>::deserialize(deserializer)?;
+        Ok(Self {
+            sequence,
+            trx_id: object.trx_id,
+            block: object.block,
+            trx_in_block: object.trx_in_block,
+            op_in_trx: object.op_in_trx,
+            virtual_op: object.virtual_op,
+            timestamp: object.timestamp,
+            op: object.op,
+            extra: object.extra,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::{Duration, TimeZone, Utc};
     use serde_json::json;
 
-    use crate::types::{AccountReputation, ExtendedAccount};
+    use crate::crypto::KeyRole;
+    use crate::types::{
+        AccountHistoryEntry, AccountReputation, Authority, ExtendedAccount, Operation,
+        OwnerHistory, RecoveryRequest,
+    };
+
+    #[test]
+    fn memo_public_key_treats_null_key_as_disabled() {
+        let account = |memo_key: &str| ExtendedAccount {
+            name: "alice".to_string(),
+            memo_key: Some(memo_key.to_string()),
+            ..ExtendedAccount::default()
+        };
+
+        let disabled = account("STM1111111111111111111111111111111114T1Anm");
+        assert_eq!(disabled.memo_public_key().expect("null key parses"), None);
+        assert_eq!(
+            ExtendedAccount::default()
+                .memo_public_key()
+                .expect("missing key is not an error"),
+            None
+        );
+
+        let enabled = account("STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA")
+            .memo_public_key()
+            .expect("key should parse")
+            .expect("key should be present");
+        assert_eq!(
+            enabled.to_string(),
+            "STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA"
+        );
+        assert!(account("STMnotakey").memo_public_key().is_err());
+    }
+
+    #[test]
+    fn owner_history_and_recovery_request_parse_authorities() {
+        let history: Vec<OwnerHistory> = serde_json::from_value(json!([{
+            "id": 1234,
+            "account": "alice",
+            "previous_owner_authority": {
+                "weight_threshold": 1,
+                "account_auths": [],
+                "key_auths": [["STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA", 1]]
+            },
+            "last_valid_time": "2024-03-01T12:00:00"
+        }]))
+        .expect("owner history should deserialize");
+        assert_eq!(history[0].id, 1234);
+        assert_eq!(
+            history[0].previous_owner_authority,
+            Authority::from_key("STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA")
+        );
+        assert_eq!(
+            history[0].last_valid_at().expect("time should parse"),
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+        );
+
+        let request: RecoveryRequest = serde_json::from_value(json!({
+            "id": 7,
+            "account_to_recover": "alice",
+            "new_owner_authority": {
+                "weight_threshold": 1,
+                "account_auths": [],
+                "key_auths": [["STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA", 1]]
+            },
+            "expires": "2024-03-02T12:00:00"
+        }))
+        .expect("recovery request should deserialize");
+        assert_eq!(request.account_to_recover, "alice");
+        assert_eq!(request.new_owner_authority.weight_threshold, 1);
+        let expires = Utc.with_ymd_and_hms(2024, 3, 2, 12, 0, 0).unwrap();
+        assert!(!request
+            .is_expired(expires - Duration::seconds(1))
+            .expect("expiry"));
+        assert!(request.is_expired(expires).expect("expiry"));
+    }
 
     #[test]
     fn extended_account_supports_numeric_reputation() {
@@ -184,6 +412,32 @@ mod tests {
         assert_eq!(account.reputation.as_deref(), Some("0"));
     }
 
+    #[test]
+    fn effective_keys_expand_account_auths_one_level() {
+        let alice_key = "STM6LLegbAgLAy28EHrffBVuANFWcFgmqRMW13wBmTExqFE9SCkg4";
+        let bob_key = "STM7RbCDXHMuVMk7yk9GhXMk7bCSTrqhdfrGMDQaQPbZTBmj3GnDA";
+        let account = ExtendedAccount {
+            name: "alice".to_string(),
+            active: Some(Authority {
+                weight_threshold: 1,
+                account_auths: vec![("bob".to_string(), 1)],
+                key_auths: vec![(alice_key.to_string(), 1)],
+            }),
+            ..Default::default()
+        };
+
+        let keys = account.effective_keys(KeyRole::Active, |name| {
+            (name == "bob").then(|| Authority::from_key(bob_key))
+        });
+        assert_eq!(
+            keys,
+            vec![(alice_key.to_string(), 1), (bob_key.to_string(), 1)]
+        );
+        assert!(account
+            .effective_keys(KeyRole::Posting, |_| None)
+            .is_empty());
+    }
+
     #[test]
     fn account_reputation_supports_numeric_reputation() {
         let reputation: AccountReputation = serde_json::from_value(json!({
@@ -193,6 +447,54 @@ mod tests {
         .expect("reputation should deserialize");
 
         assert_eq!(reputation.account, "alice");
-        assert_eq!(reputation.reputation, "12345");
+        assert_eq!(reputation.reputation, 12345);
+    }
+
+    #[test]
+    fn account_reputation_score_uses_display_scale() {
+        let reputation: AccountReputation = serde_json::from_value(json!({
+            "account": "alice",
+            "reputation": "95832978796820",
+        }))
+        .expect("reputation should deserialize");
+
+        assert!((reputation.score() - 69.83).abs() < 0.01);
+    }
+
+    #[test]
+    fn account_history_entry_decodes_tuple_and_operation() {
+        let entry: AccountHistoryEntry = serde_json::from_value(json!([
+            1234,
+            {
+                "trx_id": "5b4a6ea5e3a3fef2a0b94d69b6c3b3b0d4e1c7a2",
+                "block": 80_000_000,
+                "trx_in_block": 3,
+                "op_in_trx": 0,
+                "virtual_op": false,
+                "timestamp": "2023-11-14T22:13:20",
+                "op": ["transfer", {
+                    "from": "alice",
+                    "to": "bob",
+                    "amount": "1.000 HIVE",
+                    "memo": "thanks"
+                }]
+            }
+        ]))
+        .expect("history entry should deserialize");
+
+        assert_eq!(entry.sequence, 1234);
+        assert_eq!(entry.block, 80_000_000);
+        assert!(!entry.virtual_op);
+        match &entry.op {
+            Operation::Transfer(transfer) => {
+                assert_eq!(transfer.from, "alice");
+                assert_eq!(transfer.amount.to_string(), "1.000 HIVE");
+            }
+            other => panic!("expected transfer operation, got {other:?}"),
+        }
+
+        let round_trip = serde_json::to_value(&entry).expect("entry should serialize");
+        assert_eq!(round_trip[0], json!(1234));
+        assert_eq!(round_trip[1]["op"][0], json!("transfer"));
     }
 }
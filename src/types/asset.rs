@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 use std::str::FromStr;
 
@@ -31,11 +32,33 @@ impl AssetSymbol {
 /// can no longer represent the value (10^19 overflows i64).
 pub const MAX_PRECISION: u8 = 18;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct Asset {
     pub amount: i64,
     pub precision: u8,
     pub symbol: AssetSymbol,
+    /// The legacy or testnet symbol (`STEEM`, `SBD`, `TESTS`, `TBD`) the value
+    /// was parsed from, if any. Only affects `Display`; equality and hashing
+    /// use the canonical `symbol`.
+    pub display_symbol: Option<String>,
+}
+
+impl PartialEq for Asset {
+    fn eq(&self, other: &Self) -> bool {
+        self.amount == other.amount
+            && self.precision == other.precision
+            && self.symbol == other.symbol
+    }
+}
+
+impl Eq for Asset {}
+
+impl Hash for Asset {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.amount.hash(state);
+        self.precision.hash(state);
+        self.symbol.hash(state);
+    }
 }
 
 impl Asset {
@@ -83,22 +106,51 @@ impl Asset {
             "HIVE" | "STEEM" | "TESTS" => AssetSymbol::Hive,
             "HBD" | "SBD" | "TBD" => AssetSymbol::Hbd,
             "VESTS" => AssetSymbol::Vests,
-            _ => AssetSymbol::Custom(symbol_upper),
+            _ => AssetSymbol::Custom(symbol_upper.clone()),
         };
+        let display_symbol = (symbol_upper != symbol.as_str()).then_some(symbol_upper);
 
         Ok(Self {
             amount,
             precision,
             symbol,
+            display_symbol,
         })
     }
 
+    /// Returns a copy that displays with the canonical `HIVE`/`HBD` symbol
+    /// instead of the legacy symbol it was parsed from.
+    pub fn canonicalized(&self) -> Self {
+        Self {
+            display_symbol: None,
+            ..self.clone()
+        }
+    }
+
+    /// The symbol used by `Display`: the parsed legacy symbol if one was kept,
+    /// otherwise the canonical one.
+    pub fn display_symbol(&self) -> &str {
+        self.display_symbol
+            .as_deref()
+            .unwrap_or_else(|| self.symbol.as_str())
+    }
+
+    /// Returns the amount, precision and legacy wire symbol used by the binary
+    /// serializer. Builds with the `testnet` feature emit `TESTS`/`TBD`.
     pub fn steem_symbols(&self) -> (i64, u8, &str) {
-        let symbol = match &self.symbol {
-            AssetSymbol::Hive => "STEEM",
-            AssetSymbol::Hbd => "SBD",
-            AssetSymbol::Vests => "VESTS",
-            AssetSymbol::Custom(symbol) => symbol.as_str(),
+        self.network_symbols(cfg!(feature = "testnet"))
+    }
+
+    /// Like [`Asset::steem_symbols`], but picks the wire symbols for the given
+    /// network: `TESTS`/`TBD` on testnets, `STEEM`/`SBD` on mainnet.
+    pub fn network_symbols(&self, testnet: bool) -> (i64, u8, &str) {
+        let symbol = match (&self.symbol, testnet) {
+            (AssetSymbol::Hive, false) => "STEEM",
+            (AssetSymbol::Hive, true) => "TESTS",
+            (AssetSymbol::Hbd, false) => "SBD",
+            (AssetSymbol::Hbd, true) => "TBD",
+            (AssetSymbol::Vests, _) => "VESTS",
+            (AssetSymbol::Custom(symbol), _) => symbol.as_str(),
         };
 
         (self.amount, self.precision, symbol)
@@ -120,6 +172,7 @@ impl Asset {
             amount: self.amount.checked_add(rhs.amount)?,
             precision: self.precision,
             symbol: self.symbol.clone(),
+            display_symbol: self.display_symbol.clone(),
         })
     }
 
@@ -132,9 +185,16 @@ impl Asset {
             amount: self.amount.checked_sub(rhs.amount)?,
             precision: self.precision,
             symbol: self.symbol.clone(),
+            display_symbol: self.display_symbol.clone(),
         })
     }
 
+    /// Like `partial_cmp`, but reports a symbol mismatch as an error.
+    pub fn try_cmp(&self, other: &Self) -> Result<Ordering> {
+        self.partial_cmp(other)
+            .ok_or_else(|| HiveError::InvalidAsset(format!("cannot compare {self} with {other}")))
+    }
+
     pub fn min(a: &Self, b: &Self) -> Self {
         assert_same_symbol(a, b);
         if a.amount <= b.amount { a.clone() } else { b.clone() }
@@ -152,6 +212,7 @@ impl Asset {
             amount,
             precision,
             symbol,
+            display_symbol: None,
         }
     }
 }
@@ -165,8 +226,8 @@ fn assert_same_symbol(a: &Asset, b: &Asset) {
     );
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Asset {
-    #[allow(clippy::non_canonical_partial_ord_impl)]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         if self.symbol != other.symbol || self.precision != other.precision {
             return None;
@@ -186,7 +247,7 @@ impl Add for Asset {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
         assert_same_symbol(&self, &rhs);
-        Self { amount: self.amount + rhs.amount, precision: self.precision, symbol: self.symbol }
+        Self { amount: self.amount + rhs.amount, ..self }
     }
 }
 
@@ -194,7 +255,7 @@ impl Sub for Asset {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
         assert_same_symbol(&self, &rhs);
-        Self { amount: self.amount - rhs.amount, precision: self.precision, symbol: self.symbol }
+        Self { amount: self.amount - rhs.amount, ..self }
     }
 }
 
@@ -215,28 +276,28 @@ impl SubAssign for Asset {
 impl Mul<i64> for Asset {
     type Output = Self;
     fn mul(self, rhs: i64) -> Self {
-        Self { amount: self.amount * rhs, precision: self.precision, symbol: self.symbol }
+        Self { amount: self.amount * rhs, ..self }
     }
 }
 
 impl Mul<f64> for Asset {
     type Output = Self;
     fn mul(self, rhs: f64) -> Self {
-        Self { amount: (self.amount as f64 * rhs).round() as i64, precision: self.precision, symbol: self.symbol }
+        Self { amount: (self.amount as f64 * rhs).round() as i64, ..self }
     }
 }
 
 impl Div<i64> for Asset {
     type Output = Self;
     fn div(self, rhs: i64) -> Self {
-        Self { amount: self.amount / rhs, precision: self.precision, symbol: self.symbol }
+        Self { amount: self.amount / rhs, ..self }
     }
 }
 
 impl Div<f64> for Asset {
     type Output = Self;
     fn div(self, rhs: f64) -> Self {
-        Self { amount: (self.amount as f64 / rhs).round() as i64, precision: self.precision, symbol: self.symbol }
+        Self { amount: (self.amount as f64 / rhs).round() as i64, ..self }
     }
 }
 
@@ -251,12 +312,12 @@ impl Display for Asset {
         let fraction = abs % scale;
 
         if self.precision == 0 {
-            write!(f, "{sign}{whole} {}", self.symbol.as_str())
+            write!(f, "{sign}{whole} {}", self.display_symbol())
         } else {
             write!(
                 f,
                 "{sign}{whole}.{fraction:0width$} {}",
-                self.symbol.as_str(),
+                self.display_symbol(),
                 width = self.precision as usize
             )
         }
@@ -285,8 +346,75 @@ impl<'de> Deserialize<'de> for Asset {
     where
         D: Deserializer<'de>,
     {
-        let value = String::deserialize(deserializer)?;
-        Self::from_string(&value).map_err(D::Error::custom)
+        // Some nodes send the NAI amount of large supplies as a bare number
+        // rather than a string.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum NaiAmount {
+            Text(String),
+            Signed(i64),
+            Unsigned(u64),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Legacy(String),
+            Nai {
+                amount: NaiAmount,
+                precision: u8,
+                nai: String,
+            },
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Legacy(value) => Self::from_string(&value),
+            Repr::Nai {
+                amount,
+                precision,
+                nai,
+            } => {
+                let amount = match amount {
+                    NaiAmount::Text(amount) => amount,
+                    NaiAmount::Signed(amount) => amount.to_string(),
+                    NaiAmount::Unsigned(amount) => amount.to_string(),
+                };
+                Self::from_nai(&amount, precision, &nai)
+            }
+        }
+        .map_err(D::Error::custom)
+    }
+}
+
+impl Asset {
+    /// Builds an asset from the `database_api` object form
+    /// (`{"amount": "1000", "precision": 3, "nai": "@@000000021"}`).
+    pub fn from_nai(amount: &str, precision: u8, nai: &str) -> Result<Self> {
+        let (symbol, expected) = match nai {
+            "@@000000021" => (AssetSymbol::Hive, 3),
+            "@@000000013" => (AssetSymbol::Hbd, 3),
+            "@@000000037" => (AssetSymbol::Vests, 6),
+            other => {
+                return Err(HiveError::InvalidAsset(format!(
+                    "unknown asset nai '{other}'"
+                )))
+            }
+        };
+        if precision != expected {
+            return Err(HiveError::InvalidAsset(format!(
+                "nai {nai} expects precision {expected}, got {precision}"
+            )));
+        }
+        let amount = amount.parse::<i64>().map_err(|err| {
+            HiveError::InvalidAsset(format!("invalid nai amount '{amount}': {err}"))
+        })?;
+
+        Ok(Self {
+            amount,
+            precision,
+            symbol,
+            display_symbol: None,
+        })
     }
 }
 
@@ -400,9 +528,12 @@ fn parse_amount(raw: &str, precision: u8) -> Result<i64> {
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
     use serde_json::json;
 
     use super::{Asset, AssetSymbol};
+    use crate::error::HiveError;
 
     #[test]
     fn parse_and_round_trip_canonical_assets() {
@@ -421,7 +552,7 @@ mod tests {
         assert_eq!(asset.amount, -100_333);
         assert_eq!(asset.precision, 3);
         assert_eq!(asset.symbol, AssetSymbol::Hbd);
-        assert_eq!(asset.to_string(), "-100.333 HBD");
+        assert_eq!(asset.canonicalized().to_string(), "-100.333 HBD");
     }
 
     #[test]
@@ -459,6 +590,7 @@ mod tests {
             amount: i64::MAX,
             precision: 3,
             symbol: AssetSymbol::Hive,
+            display_symbol: None,
         };
         let one = Asset::from_string("0.001 HIVE").expect("asset should parse");
         assert!(a.checked_add(&one).is_none());
@@ -483,6 +615,7 @@ mod tests {
             amount: 1,
             precision: 30,
             symbol: AssetSymbol::Custom("FOO".to_string()),
+            display_symbol: None,
         };
         let _ = asset.to_string();
         let _ = asset.as_f64();
@@ -529,14 +662,86 @@ mod tests {
         assert_eq!(a.partial_cmp(&Asset::hbd(1.0)), None);
     }
 
+    #[test]
+    fn nai_amount_may_be_a_number() {
+        let asset: Asset = serde_json::from_value(json!({
+            "amount": 180000000000_i64,
+            "precision": 3,
+            "nai": "@@000000021"
+        }))
+        .expect("numeric nai amount should parse");
+        assert_eq!(asset, Asset::hive(180_000_000.0));
+    }
+
+    #[test]
+    fn try_cmp_and_sorting_by_amount() {
+        let mut transfers = vec![Asset::hive(3.5), Asset::hive(0.001), Asset::hive(2.0)];
+        transfers.sort();
+        assert_eq!(
+            transfers,
+            vec![Asset::hive(0.001), Asset::hive(2.0), Asset::hive(3.5)]
+        );
+        assert_eq!(
+            Asset::hive(2.0).try_cmp(&Asset::hive(1.0)).unwrap(),
+            Ordering::Greater
+        );
+        assert!(matches!(
+            Asset::hive(1.0).try_cmp(&Asset::hbd(1.0)),
+            Err(HiveError::InvalidAsset(_))
+        ));
+    }
+
     #[test]
     fn steem_symbol_mapping() {
         let hive = Asset::from_string("1.000 HIVE").expect("asset should parse");
         let hbd = Asset::from_string("2.000 HBD").expect("asset should parse");
         let vests = Asset::from_string("3.000000 VESTS").expect("asset should parse");
 
+        assert_eq!(hive.network_symbols(false), (1_000, 3, "STEEM"));
+        assert_eq!(hbd.network_symbols(false), (2_000, 3, "SBD"));
+        assert_eq!(vests.network_symbols(false), (3_000_000, 6, "VESTS"));
+        #[cfg(not(feature = "testnet"))]
         assert_eq!(hive.steem_symbols(), (1_000, 3, "STEEM"));
-        assert_eq!(hbd.steem_symbols(), (2_000, 3, "SBD"));
-        assert_eq!(vests.steem_symbols(), (3_000_000, 6, "VESTS"));
+    }
+
+    #[test]
+    fn legacy_symbol_round_trips_unless_canonicalized() {
+        let steem = Asset::from_string("1.000 STEEM").expect("asset should parse");
+        assert_eq!(steem.symbol, AssetSymbol::Hive);
+        assert_eq!(steem.to_string(), "1.000 STEEM");
+        assert_eq!(steem.canonicalized().to_string(), "1.000 HIVE");
+        assert_eq!(steem, Asset::hive(1.0));
+
+        let sbd: Asset = serde_json::from_str("\"2.500 SBD\"").expect("asset should deserialize");
+        assert_eq!(serde_json::to_string(&sbd).unwrap(), "\"2.500 SBD\"");
+        assert_eq!(sbd.canonicalized().to_string(), "2.500 HBD");
+
+        let hive = Asset::from_string("1.000 HIVE").expect("asset should parse");
+        assert!(hive.display_symbol.is_none());
+    }
+
+    #[test]
+    fn deserializes_nai_object_form() {
+        let hbd: Asset = serde_json::from_value(serde_json::json!({
+            "amount": "10000",
+            "precision": 3,
+            "nai": "@@000000013"
+        }))
+        .expect("nai asset should deserialize");
+        assert_eq!(hbd, Asset::hbd(10.0));
+
+        let bad = serde_json::json!({"amount": "1", "precision": 6, "nai": "@@000000021"});
+        assert!(serde_json::from_value::<Asset>(bad).is_err());
+    }
+
+    #[test]
+    fn testnet_symbol_mapping() {
+        let tests = Asset::from_string("1.000 TESTS").expect("asset should parse");
+        let tbd = Asset::from_string("2.000 TBD").expect("asset should parse");
+
+        assert_eq!(tests.network_symbols(true), (1_000, 3, "TESTS"));
+        assert_eq!(tbd.network_symbols(true), (2_000, 3, "TBD"));
+        #[cfg(feature = "testnet")]
+        assert_eq!(tests.steem_symbols(), (1_000, 3, "TESTS"));
     }
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::crypto::PublicKey;
+use crate::error::{HiveError, Result};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct Authority {
     pub weight_threshold: u32,
@@ -8,3 +11,254 @@ pub struct Authority {
     #[serde(default)]
     pub key_auths: Vec<(String, u16)>,
 }
+
+impl Authority {
+    /// A threshold-1 authority satisfied by a single key.
+    pub fn from_key(public_key: &str) -> Self {
+        Self {
+            weight_threshold: 1,
+            account_auths: Vec::new(),
+            key_auths: vec![(public_key.to_string(), 1)],
+        }
+    }
+
+    /// A threshold-1 authority delegated to a single account.
+    pub fn from_account(account: &str) -> Self {
+        Self {
+            weight_threshold: 1,
+            account_auths: vec![(account.to_string(), 1)],
+            key_auths: Vec::new(),
+        }
+    }
+
+    /// Sorts `account_auths` by name and `key_auths` by compressed key bytes,
+    /// the order the chain stores and signs them in. Fails on an unparsable or
+    /// duplicate entry.
+    pub fn canonicalize(&mut self) -> Result<()> {
+        let account_auths = self
+            .sorted_account_auths()?
+            .into_iter()
+            .map(|(account, weight)| (account.to_string(), weight))
+            .collect();
+        let key_auths = self
+            .sorted_key_auths()?
+            .into_iter()
+            .map(|(_, key, weight)| (key.to_string(), weight))
+            .collect();
+        self.account_auths = account_auths;
+        self.key_auths = key_auths;
+        Ok(())
+    }
+
+    /// `account_auths` in canonical (name) order.
+    pub(crate) fn sorted_account_auths(&self) -> Result<Vec<(&str, u16)>> {
+        let mut account_auths = self
+            .account_auths
+            .iter()
+            .map(|(account, weight)| (account.as_str(), *weight))
+            .collect::<Vec<_>>();
+        account_auths.sort_by_key(|entry| entry.0);
+        if account_auths.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(HiveError::Other(
+                "authority contains a duplicate account".to_string(),
+            ));
+        }
+        Ok(account_auths)
+    }
+
+    /// `key_auths` in canonical (compressed byte) order, each key parsed once
+    /// and returned alongside its compressed bytes.
+    pub(crate) fn sorted_key_auths(&self) -> Result<Vec<([u8; 33], &str, u16)>> {
+        let mut key_auths = self
+            .key_auths
+            .iter()
+            .map(|(key, weight)| {
+                Ok((
+                    PublicKey::from_string(key)?.compressed_bytes(),
+                    key.as_str(),
+                    *weight,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        key_auths.sort_by_key(|entry| entry.0);
+        if key_auths.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(HiveError::InvalidKey(
+                "authority contains a duplicate key".to_string(),
+            ));
+        }
+        Ok(key_auths)
+    }
+
+    /// Own keys plus the keys of each referenced account (one level through
+    /// `resolver`, at the reference's weight); duplicates keep the highest.
+    pub(crate) fn weighted_keys(
+        &self,
+        resolver: impl Fn(&str) -> Option<Authority>,
+    ) -> Vec<(String, u16)> {
+        let mut keys: Vec<(String, u16)> = Vec::new();
+        let mut add = |key: &str, weight: u16| match keys.iter_mut().find(|(k, _)| k == key) {
+            Some(existing) => existing.1 = existing.1.max(weight),
+            None => keys.push((key.to_string(), weight)),
+        };
+        for (key, weight) in &self.key_auths {
+            add(key, *weight);
+        }
+        for (account, weight) in &self.account_auths {
+            if let Some(referenced) = resolver(account) {
+                for (key, _) in &referenced.key_auths {
+                    add(key, *weight);
+                }
+            }
+        }
+        keys
+    }
+
+    pub fn builder() -> AuthorityBuilder {
+        AuthorityBuilder::default()
+    }
+}
+
+/// Builds an [`Authority`] whose auth maps are validated and sorted the way the
+/// chain stores them.
+#[derive(Debug, Clone)]
+pub struct AuthorityBuilder {
+    threshold: u32,
+    account_auths: Vec<(String, u16)>,
+    key_auths: Vec<(String, u16)>,
+}
+
+impl Default for AuthorityBuilder {
+    fn default() -> Self {
+        Self {
+            threshold: 1,
+            account_auths: Vec::new(),
+            key_auths: Vec::new(),
+        }
+    }
+}
+
+impl AuthorityBuilder {
+    pub fn threshold(mut self, threshold: u32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn add_key(mut self, public_key: impl Into<String>, weight: u16) -> Self {
+        self.key_auths.push((public_key.into(), weight));
+        self
+    }
+
+    pub fn add_account(mut self, account: impl Into<String>, weight: u16) -> Self {
+        self.account_auths.push((account.into(), weight));
+        self
+    }
+
+    /// Validates the keys and returns the authority with accounts sorted by name
+    /// and keys by their compressed bytes.
+    pub fn build(self) -> Result<Authority> {
+        if self.threshold == 0 {
+            return Err(HiveError::Other(
+                "authority weight threshold must be greater than zero".to_string(),
+            ));
+        }
+
+        let mut authority = Authority {
+            weight_threshold: self.threshold,
+            account_auths: self.account_auths,
+            key_auths: self.key_auths,
+        };
+        authority.canonicalize()?;
+
+        let total_weight = authority
+            .account_auths
+            .iter()
+            .chain(authority.key_auths.iter())
+            .map(|(_, weight)| u64::from(*weight))
+            .sum::<u64>();
+        if total_weight < u64::from(self.threshold) {
+            return Err(HiveError::Other(format!(
+                "authority weight threshold {} is unreachable with total weight {total_weight}",
+                self.threshold
+            )));
+        }
+
+        Ok(authority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::{PrivateKey, PublicKey};
+    use crate::serialization::types::write_authority;
+    use crate::types::Authority;
+
+    fn key(seed: &str) -> String {
+        PrivateKey::from_seed(seed)
+            .expect("seed should derive a key")
+            .public_key()
+            .to_string()
+    }
+
+    #[test]
+    fn from_key_has_single_key_at_threshold_one() {
+        let public_key = key("alice-active");
+        let authority = Authority::from_key(&public_key);
+        assert_eq!(authority.weight_threshold, 1);
+        assert_eq!(authority.key_auths, vec![(public_key, 1)]);
+        assert!(authority.account_auths.is_empty());
+
+        let delegated = Authority::from_account("bob");
+        assert_eq!(delegated.account_auths, vec![("bob".to_string(), 1)]);
+    }
+
+    #[test]
+    fn builder_sorts_auths_to_match_serialization() {
+        let keys = [key("one"), key("two"), key("three")];
+        let authority = keys
+            .iter()
+            .fold(Authority::builder().threshold(2), |builder, k| {
+                builder.add_key(k.clone(), 1)
+            })
+            .add_account("zed", 1)
+            .add_account("amy", 1)
+            .build()
+            .expect("authority should build");
+
+        assert_eq!(authority.account_auths[0].0, "amy");
+        let mut expected = authority.clone();
+        expected.key_auths.reverse();
+        let (mut built, mut reversed) = (Vec::new(), Vec::new());
+        write_authority(&mut built, &authority).expect("authority should serialize");
+        write_authority(&mut reversed, &expected).expect("authority should serialize");
+        assert_eq!(built, reversed);
+
+        let bytes: Vec<[u8; 33]> = authority
+            .key_auths
+            .iter()
+            .map(|(k, _)| {
+                PublicKey::from_string(k)
+                    .expect("key should parse")
+                    .compressed_bytes()
+            })
+            .collect();
+        assert!(bytes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn builder_rejects_invalid_authorities() {
+        assert!(Authority::builder()
+            .add_key("not-a-key", 1)
+            .build()
+            .is_err());
+        assert!(Authority::builder()
+            .threshold(3)
+            .add_account("amy", 1)
+            .build()
+            .is_err());
+        assert!(Authority::builder()
+            .add_account("amy", 1)
+            .add_account("amy", 1)
+            .build()
+            .is_err());
+    }
+}
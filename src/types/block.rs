@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::types::{SignedTransaction, Transaction};
+use crate::types::SignedTransaction;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct BlockHeader {
@@ -25,7 +25,7 @@ pub struct SignedBlock {
     #[serde(flatten)]
     pub header: SignedBlockHeader,
     #[serde(default)]
-    pub transactions: Vec<Transaction>,
+    pub transactions: Vec<SignedTransaction>,
     #[serde(default)]
     pub signed_transactions: Vec<SignedTransaction>,
     #[serde(default)]
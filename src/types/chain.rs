@@ -3,7 +3,10 @@ use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::error::Result;
+use crate::types::misc::deserialize_number_like;
 use crate::types::{Asset, Price};
+use crate::utils::parse_hardfork_version;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct DynamicGlobalProperties {
@@ -55,10 +58,14 @@ pub struct DynamicGlobalProperties {
     pub extra: BTreeMap<String, Value>,
 }
 
+/// Witness-voted chain parameters. Deserializes the witness median from
+/// `get_chain_properties`, accepting numbers sent as strings.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ChainProperties {
     pub account_creation_fee: Asset,
+    #[serde(deserialize_with = "deserialize_number_like")]
     pub maximum_block_size: u32,
+    #[serde(deserialize_with = "deserialize_number_like")]
     pub hbd_interest_rate: u16,
 }
 
@@ -98,3 +105,10 @@ pub struct Version {
     pub hive_revision: String,
     pub fc_revision: String,
 }
+
+impl Version {
+    /// `blockchain_version` as a comparable `(major, minor, patch)` tuple.
+    pub fn blockchain_semver(&self) -> Result<(u32, u32, u32)> {
+        parse_hardfork_version(&self.blockchain_version)
+    }
+}
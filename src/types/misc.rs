@@ -1,12 +1,45 @@
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
+use chrono::{DateTime, Utc};
 use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
+use crate::error::HiveError;
+use crate::serialization::types::parse_hive_time;
+use crate::types::{Asset, Operation, OperationName, Price, VirtualOperation};
+
+pub(crate) fn deserialize_number_like<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let text = match Value::deserialize(deserializer)? {
+        Value::String(text) => text,
+        Value::Number(number) => number.to_string(),
+        other => {
+            return Err(D::Error::custom(format!(
+                "expected string or number, got {other}"
+            )))
+        }
+    };
+    text.parse::<T>()
+        .map_err(|err| D::Error::custom(format!("invalid number '{text}': {err}")))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct VestingDelegation {
+    #[serde(default)]
+    pub delegator: String,
+    #[serde(default)]
+    pub delegatee: String,
+    #[serde(default)]
+    pub vesting_shares: Option<Asset>,
+    #[serde(default)]
+    pub min_delegation_time: Option<String>,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
@@ -31,12 +64,43 @@ pub struct WitnessProps {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct OrderBook {
+    #[serde(default)]
+    pub bids: Vec<OrderBookEntry>,
+    #[serde(default)]
+    pub asks: Vec<OrderBookEntry>,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrderBookEntry {
+    pub order_price: Price,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub real_price: f64,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub hive: i64,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub hbd: i64,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OpenOrder {
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub id: u64,
+    pub created: String,
+    pub expiration: String,
+    pub seller: String,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub orderid: u32,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub for_sale: i64,
+    pub sell_price: Price,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub real_price: f64,
+    #[serde(default)]
+    pub rewarded: bool,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
@@ -53,14 +117,38 @@ pub struct MarketBucket {
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SavingsWithdraw {
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub id: u64,
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub memo: String,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub request_id: u32,
+    pub amount: Asset,
+    pub complete: String,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+impl SavingsWithdraw {
+    /// When the withdrawal is paid out to `to`.
+    pub fn completes_at(&self) -> crate::error::Result<DateTime<Utc>> {
+        parse_hive_time(&self.complete)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ConversionRequest {
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub id: u64,
+    pub owner: String,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub requestid: u32,
+    pub amount: Asset,
+    pub conversion_date: String,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
@@ -73,12 +161,21 @@ pub struct CollateralizedConversionRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct FollowEntry {
+    pub follower: String,
+    pub following: String,
+    #[serde(default)]
+    pub what: Vec<String>,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct FollowCount {
+    pub account: String,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub follower_count: u32,
+    #[serde(deserialize_with = "deserialize_number_like")]
+    pub following_count: u32,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
@@ -107,18 +204,113 @@ pub struct Proposal {
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RecurrentTransfer {
+    pub id: u64,
+    pub from: String,
+    pub to: String,
+    pub amount: Asset,
+    #[serde(default)]
+    pub memo: String,
+    pub recurrence: u16,
+    #[serde(default)]
+    pub consecutive_failures: u8,
+    pub remaining_executions: u16,
+    pub trigger_date: String,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+impl RecurrentTransfer {
+    pub fn next_execution(&self) -> crate::error::Result<DateTime<Utc>> {
+        parse_hive_time(&self.trigger_date)
+    }
+
+    /// The total still to be sent, i.e. `amount` times the remaining executions.
+    pub fn total_remaining(&self) -> crate::error::Result<Asset> {
+        let amount = self
+            .amount
+            .amount
+            .checked_mul(i64::from(self.remaining_executions))
+            .ok_or_else(|| {
+                HiveError::InvalidAsset("recurrent transfer total overflows".to_string())
+            })?;
+        Ok(Asset {
+            amount,
+            precision: self.amount.precision,
+            symbol: self.amount.symbol.clone(),
+            display_symbol: None,
+        })
+    }
+}
+
+/// One entry of `get_ops_in_block`: an operation plus where it was applied.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AppliedOperation {
+    #[serde(default)]
+    pub trx_id: String,
+    #[serde(default)]
+    pub block: u32,
+    #[serde(default)]
+    pub trx_in_block: u32,
+    #[serde(default)]
+    pub op_in_trx: u32,
+    /// Non-zero for virtual operations; some nodes send a bool instead.
+    #[serde(default, deserialize_with = "deserialize_virtual_op")]
+    pub virtual_op: u32,
+    #[serde(default)]
+    pub timestamp: String,
+    pub op: Operation,
     #[serde(flatten)]
     pub extra: BTreeMap<String, Value>,
 }
 
+impl AppliedOperation {
+    pub fn is_virtual(&self) -> bool {
+        self.virtual_op != 0
+    }
+
+    /// `None` for operations outside the known operation list, e.g.
+    /// registered custom operations.
+    pub fn operation_name(&self) -> Option<OperationName> {
+        OperationName::from_name(self.op.name())
+    }
+
+    /// The typed virtual operation, for entries with a non-zero `virtual_op`.
+    pub fn virtual_operation(&self) -> Option<crate::error::Result<VirtualOperation>> {
+        if !self.is_virtual() {
+            return None;
+        }
+        VirtualOperation::from_operation(&self.op)
+    }
+
+    pub fn is(&self, name: OperationName) -> bool {
+        self.operation_name() == Some(name)
+    }
+
+    /// Keeps the operations whose name is one of `names`.
+    pub fn filter_by_name(operations: Vec<Self>, names: &[OperationName]) -> Vec<Self> {
+        operations
+            .into_iter()
+            .filter(|op| {
+                op.operation_name()
+                    .is_some_and(|name| names.contains(&name))
+            })
+            .collect()
+    }
+}
+
+fn deserialize_virtual_op<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Bool(flag) => Ok(u32::from(flag)),
+        Value::Null => Ok(0),
+        other => deserialize_number_like(other).map_err(D::Error::custom),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct CommunityDetail {
     #[serde(flatten)]
@@ -189,6 +381,18 @@ impl ChainId {
             .expect("valid testnet chain id")
     }
 
+    pub fn mirrornet() -> Self {
+        Self::from_hex("4200000000000000000000000000000000000000000000000000000000000000")
+            .expect("valid mirrornet chain id")
+    }
+
+    /// Whether transactions for this chain carry HIVE and HBD as the testnet
+    /// wire symbols `TESTS`/`TBD`: true for the testnet and mirrornet chain
+    /// ids, and for every chain in builds with the `testnet` feature.
+    pub fn uses_testnet_symbols(&self) -> bool {
+        cfg!(feature = "testnet") || *self == Self::testnet() || *self == Self::mirrornet()
+    }
+
     pub fn from_hex(value: &str) -> Result<Self, hex::FromHexError> {
         let bytes = hex::decode(value)?;
         let bytes: [u8; 32] = bytes
@@ -197,11 +401,61 @@ impl ChainId {
         Ok(Self { bytes })
     }
 
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
     pub fn to_hex(&self) -> String {
         hex::encode(self.bytes)
     }
 }
 
+/// A chain to connect to, resolving to its chain id and key prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+    /// The mainnet mirror whose state is converted to its own chain id and
+    /// `TST` keys.
+    Mirrornet,
+    Custom(ChainId),
+}
+
+impl Network {
+    pub fn chain_id(&self) -> ChainId {
+        match self {
+            Self::Mainnet => ChainId::mainnet(),
+            Self::Testnet => ChainId::testnet(),
+            Self::Mirrornet => ChainId::mirrornet(),
+            Self::Custom(chain_id) => *chain_id,
+        }
+    }
+
+    /// Public key prefix used on this network. Custom chains are assumed to
+    /// keep the mainnet `STM` prefix.
+    pub fn address_prefix(&self) -> &'static str {
+        match self {
+            Self::Testnet | Self::Mirrornet => "TST",
+            Self::Mainnet | Self::Custom(_) => "STM",
+        }
+    }
+
+    /// Public API nodes serving this network; empty for custom chains.
+    pub fn default_nodes(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mainnet => &["https://api.hive.blog", "https://api.openhive.network"],
+            Self::Testnet => &["https://testnet.openhive.network"],
+            Self::Mirrornet => &["https://api.fake.openhive.network"],
+            Self::Custom(_) => &[],
+        }
+    }
+}
+
 impl Default for ChainId {
     fn default() -> Self {
         Self::mainnet()
@@ -232,3 +486,215 @@ impl<'de> Deserialize<'de> for ChainId {
         Self::from_hex(&value).map_err(D::Error::custom)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::serialization::types::format_hive_time;
+    use crate::types::{
+        AppliedOperation, Asset, ChainId, ConversionRequest, FollowCount, FollowEntry, Network,
+        OpenOrder, OperationName, OrderBook, RecurrentTransfer, SavingsWithdraw,
+    };
+
+    #[test]
+    fn recurrent_transfer_deserializes_and_totals_remaining() {
+        let transfer: RecurrentTransfer = serde_json::from_value(json!({
+            "id": 1841,
+            "trigger_date": "2024-05-02T09:15:42",
+            "from": "alice",
+            "to": "bob",
+            "amount": "2.500 HBD",
+            "memo": "rent",
+            "recurrence": 24,
+            "consecutive_failures": 0,
+            "remaining_executions": 11,
+            "pair_id": 0
+        }))
+        .expect("recurrent transfer should deserialize");
+
+        assert_eq!(transfer.amount, Asset::hbd(2.5));
+        assert_eq!(
+            transfer.total_remaining().expect("total should compute"),
+            Asset::hbd(27.5)
+        );
+        assert_eq!(
+            format_hive_time(transfer.next_execution().expect("date should parse")),
+            "2024-05-02T09:15:42"
+        );
+        assert_eq!(transfer.extra.get("pair_id"), Some(&json!(0)));
+    }
+
+    #[test]
+    fn order_book_parses_prices_and_tolerant_numbers() {
+        let book: OrderBook = serde_json::from_value(json!({
+            "bids": [{
+                "order_price": {"base": "75.218 HBD", "quote": "250.000 HIVE"},
+                "real_price": "0.30087200",
+                "hive": 250000,
+                "hbd": 75218,
+                "created": "2024-05-02T09:10:03"
+            }],
+            "asks": [{
+                "order_price": {"base": "120.000 HIVE", "quote": "36.600 HBD"},
+                "real_price": "0.30500000",
+                "hive": "120000",
+                "hbd": 36600,
+                "created": "2024-05-02T08:55:21"
+            }]
+        }))
+        .expect("order book should deserialize");
+
+        let best_bid = &book.bids[0];
+        assert_eq!(best_bid.order_price.base, Asset::hbd(75.218));
+        assert_eq!(best_bid.order_price.quote, Asset::hive(250.0));
+        assert!((best_bid.real_price - 0.300872).abs() < 1e-9);
+
+        let best_ask = &book.asks[0];
+        assert_eq!(best_ask.order_price.base, Asset::hive(120.0));
+        assert_eq!(best_ask.hive, 120_000);
+        assert!(best_ask.real_price > best_bid.real_price);
+    }
+
+    #[test]
+    fn open_order_deserializes() {
+        let order: OpenOrder = serde_json::from_value(json!({
+            "id": 5211341,
+            "created": "2024-05-02T08:55:21",
+            "expiration": "2024-05-30T08:55:21",
+            "seller": "alice",
+            "orderid": 1714640121,
+            "for_sale": 120000,
+            "sell_price": {"base": "120.000 HIVE", "quote": "36.600 HBD"},
+            "real_price": "0.30500000",
+            "rewarded": false
+        }))
+        .expect("open order should deserialize");
+
+        assert_eq!(order.orderid, 1714640121);
+        assert_eq!(order.sell_price.quote, Asset::hbd(36.6));
+        assert!((order.real_price - 0.305).abs() < 1e-9);
+    }
+
+    #[test]
+    fn follow_types_deserialize() {
+        let count: FollowCount = serde_json::from_value(json!({
+            "account": "alice",
+            "follower_count": 1532,
+            "following_count": "284"
+        }))
+        .expect("follow count should deserialize");
+        assert_eq!(count.follower_count, 1532);
+        assert_eq!(count.following_count, 284);
+
+        let page: Vec<FollowEntry> = serde_json::from_value(json!([
+            {"follower": "bob", "following": "alice", "what": ["blog"]},
+            {"follower": "carol", "following": "alice", "what": ["ignore"]}
+        ]))
+        .expect("followers should deserialize");
+        assert_eq!(page[0].follower, "bob");
+        assert_eq!(page[0].what, ["blog"]);
+        assert_eq!(page[1].what, ["ignore"]);
+    }
+
+    #[test]
+    fn savings_withdraw_and_conversion_requests_deserialize() {
+        let withdraw: SavingsWithdraw = serde_json::from_value(json!({
+            "id": 213470,
+            "from": "alice",
+            "to": "bob",
+            "memo": "rent",
+            "request_id": "1700000000",
+            "amount": "25.000 HBD",
+            "complete": "2024-03-04T05:06:07"
+        }))
+        .expect("savings withdraw should deserialize");
+        assert_eq!(withdraw.request_id, 1_700_000_000);
+        assert_eq!(withdraw.amount, Asset::hbd(25.0));
+        assert_eq!(
+            format_hive_time(withdraw.completes_at().expect("date should parse")),
+            "2024-03-04T05:06:07"
+        );
+
+        let conversion: ConversionRequest = serde_json::from_value(json!({
+            "id": 289745,
+            "owner": "alice",
+            "requestid": 1234,
+            "amount": "10.000 HBD",
+            "conversion_date": "2024-03-08T00:00:00"
+        }))
+        .expect("conversion request should deserialize");
+        assert_eq!(conversion.requestid, 1234);
+        assert_eq!(conversion.amount, Asset::hbd(10.0));
+        assert_eq!(conversion.conversion_date, "2024-03-08T00:00:00");
+    }
+
+    #[test]
+    fn chain_id_byte_and_hex_round_trips() {
+        let hex = "18dcf0a285365fc58b71f18b3d3fec954aa0c141c44e4e5cb4cf777b9eab274e";
+        let chain_id = ChainId::from_hex(hex).expect("chain id should parse");
+        assert_eq!(chain_id.to_hex(), hex);
+        assert_eq!(ChainId::from_bytes(chain_id.to_bytes()), chain_id);
+
+        assert_eq!(
+            Network::Testnet.chain_id().to_bytes()[..4],
+            [0x18, 0xdc, 0xf0, 0xa2]
+        );
+        assert_eq!(Network::Testnet.chain_id(), ChainId::testnet());
+        assert_eq!(Network::Testnet.address_prefix(), "TST");
+        assert_eq!(Network::Mirrornet.chain_id().to_bytes()[0], 0x42);
+        assert_eq!(Network::Mirrornet.address_prefix(), "TST");
+        assert_eq!(Network::default().chain_id(), ChainId::mainnet());
+        let custom = ChainId::from_bytes([7; 32]);
+        assert_eq!(Network::Custom(custom).chain_id(), custom);
+    }
+
+    #[test]
+    fn applied_operations_decode_and_filter_by_name() {
+        let ops: Vec<AppliedOperation> = serde_json::from_value(json!([
+            {
+                "trx_id": "6bd22c5b3e8ea3c7b3b5ff3bcd4d4e7b9ec8c7bd",
+                "block": 80000000,
+                "trx_in_block": 0,
+                "op_in_trx": 0,
+                "virtual_op": 0,
+                "timestamp": "2023-11-09T11:42:00",
+                "op": ["transfer", {
+                    "from": "alice",
+                    "to": "bob",
+                    "amount": "1.000 HIVE",
+                    "memo": ""
+                }]
+            },
+            {
+                "trx_id": "0000000000000000000000000000000000000000",
+                "block": 80000000,
+                "trx_in_block": 4294967295_u32,
+                "op_in_trx": 0,
+                "virtual_op": 1,
+                "timestamp": "2023-11-09T11:42:00",
+                "op": ["producer_reward", {
+                    "producer": "carol",
+                    "vesting_shares": "470.000000 VESTS"
+                }]
+            }
+        ]))
+        .expect("ops should decode");
+
+        assert!(!ops[0].is_virtual());
+        assert!(ops[0].is(OperationName::Transfer));
+        assert!(ops[1].is_virtual());
+        assert_eq!(ops[1].operation_name(), Some(OperationName::ProducerReward));
+
+        let transfers = AppliedOperation::filter_by_name(ops, &[OperationName::Transfer]);
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].op.name(), "transfer");
+
+        let legacy: AppliedOperation = serde_json::from_value(json!({
+            "virtual_op": true,
+            "op": ["fill_order", {}]
+        }))
+        .expect("bool virtual_op should decode");
+        assert_eq!(legacy.virtual_op, 1);
+    }
+}
@@ -9,6 +9,7 @@ pub mod operation;
 pub mod price;
 pub mod rc;
 pub mod transaction;
+pub mod virtual_operation;
 
 pub use account::*;
 pub use asset::*;
@@ -21,3 +22,4 @@ pub use operation::*;
 pub use price::*;
 pub use rc::*;
 pub use transaction::*;
+pub use virtual_operation::*;
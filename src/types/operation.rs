@@ -1,11 +1,17 @@
 use std::collections::BTreeMap;
+use std::sync::OnceLock;
 
-use serde::de::Error as _;
+use serde::de::{DeserializeOwned, Error as _};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
-use crate::types::{Asset, Authority, BeneficiaryRoute, ChainProperties, Price, SignedBlockHeader};
+use crate::crypto::PublicKey;
+use crate::error::{HiveError, Result};
+use crate::types::{
+    Asset, AssetSymbol, Authority, BeneficiaryRoute, ChainProperties, ExtendedAccount, Price,
+    RecoveryRequest, SignedBlockHeader,
+};
 
 // Field declaration order in each operation struct is intentionally aligned with
 // Hive's binary serializer order.
@@ -62,6 +68,62 @@ pub enum Operation {
     CollateralizedConvert(CollateralizedConvertOperation),             // 48
     RecurrentTransfer(RecurrentTransferOperation),                     // 49
     Virtual { op_type: String, body: Value },
+    Unknown(RawOperation),
+}
+
+/// An operation the crate has no typed model for, e.g. one added by a Hive fork.
+///
+/// `payload` is the JSON body used for the `[name, payload]` wire form, while
+/// `body` holds the caller's pre-serialized binary body written after the id.
+/// Any unrecognized name in JSON deserializes into this with no `id` and an
+/// empty `body`; both must be filled in before the operation can be
+/// serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawOperation {
+    pub id: Option<u8>,
+    pub name: String,
+    pub payload: Value,
+    pub body: Vec<u8>,
+}
+
+/// Signers required by one or more operations.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RequiredAuthorities {
+    pub owner: Vec<String>,
+    pub active: Vec<String>,
+    pub posting: Vec<String>,
+    /// Authorities required directly rather than through an account.
+    pub other: Vec<Authority>,
+}
+
+impl RequiredAuthorities {
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_empty()
+            && self.active.is_empty()
+            && self.posting.is_empty()
+            && self.other.is_empty()
+    }
+
+    /// Merges the requirements of several operations, dropping duplicates.
+    pub fn for_operations(operations: &[Operation]) -> Self {
+        let mut merged = Self::default();
+        for operation in operations {
+            let required = operation.required_authorities();
+            extend_unique(&mut merged.owner, required.owner);
+            extend_unique(&mut merged.active, required.active);
+            extend_unique(&mut merged.posting, required.posting);
+            extend_unique(&mut merged.other, required.other);
+        }
+        merged
+    }
+}
+
+fn extend_unique<T: PartialEq>(target: &mut Vec<T>, items: Vec<T>) {
+    for item in items {
+        if !target.contains(&item) {
+            target.push(item);
+        }
+    }
 }
 
 impl Operation {
@@ -118,6 +180,7 @@ impl Operation {
             Self::CollateralizedConvert(_) => "collateralized_convert",
             Self::RecurrentTransfer(_) => "recurrent_transfer",
             Self::Virtual { op_type, .. } => op_type.as_str(),
+            Self::Unknown(op) => op.name.as_str(),
         }
     }
 
@@ -129,6 +192,190 @@ impl Operation {
         matches!(self, Self::Virtual { .. })
     }
 
+    /// Parses a `[name, body]` op array, rejecting names that are not a known
+    /// Hive operation. Deserialize with serde instead to accept any name as
+    /// [`Operation::Unknown`].
+    pub fn from_op_array(value: &Value) -> Result<Self> {
+        let Some([name, body]) = value.as_array().map(Vec::as_slice) else {
+            return Err(HiveError::json("operation must be a [name, body] array"));
+        };
+        let name = name
+            .as_str()
+            .ok_or_else(|| HiveError::json("operation name must be a string"))?;
+        if !body.is_object() {
+            return Err(HiveError::json(format!(
+                "operation '{name}' body must be an object"
+            )));
+        }
+
+        if OperationName::from_name(name).is_none() {
+            return Err(HiveError::json(format!("unknown operation name '{name}'")));
+        }
+
+        serde_json::from_value(value.clone())
+            .map_err(|err| HiveError::json(format!("invalid '{name}' operation: {err}")))
+    }
+
+    /// Returns the operation in its `[name, body]` JSON form.
+    pub fn to_op_array(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// Accounts (by role) and raw authorities that must sign this operation,
+    /// following hived's `get_required_*_authorities`.
+    pub fn required_authorities(&self) -> RequiredAuthorities {
+        let mut required = RequiredAuthorities::default();
+        let owner = |account: &str| RequiredAuthorities {
+            owner: vec![account.to_string()],
+            ..Default::default()
+        };
+        let active = |account: &str| RequiredAuthorities {
+            active: vec![account.to_string()],
+            ..Default::default()
+        };
+        let posting = |account: &str| RequiredAuthorities {
+            posting: vec![account.to_string()],
+            ..Default::default()
+        };
+
+        match self {
+            Self::Vote(op) => posting(&op.voter),
+            Self::Comment(op) => posting(&op.author),
+            Self::DeleteComment(op) => posting(&op.author),
+            Self::CommentOptions(op) => posting(&op.author),
+            Self::ClaimRewardBalance(op) => posting(&op.account),
+            Self::Transfer(op) => active(&op.from),
+            Self::TransferToVesting(op) => active(&op.from),
+            Self::WithdrawVesting(op) => active(&op.account),
+            Self::LimitOrderCreate(op) => active(&op.owner),
+            Self::LimitOrderCancel(op) => active(&op.owner),
+            Self::LimitOrderCreate2(op) => active(&op.owner),
+            Self::FeedPublish(op) => active(&op.publisher),
+            Self::Convert(op) => active(&op.owner),
+            Self::CollateralizedConvert(op) => active(&op.owner),
+            Self::AccountCreate(op) => active(&op.creator),
+            Self::AccountCreateWithDelegation(op) => active(&op.creator),
+            Self::ClaimAccount(op) => active(&op.creator),
+            Self::CreateClaimedAccount(op) => active(&op.creator),
+            Self::AccountUpdate(op) => {
+                if op.owner.is_some() {
+                    owner(&op.account)
+                } else {
+                    active(&op.account)
+                }
+            }
+            Self::AccountUpdate2(op) => {
+                if op.owner.is_some() {
+                    owner(&op.account)
+                } else if op.active.is_some()
+                    || op.memo_key.is_some()
+                    || !op.json_metadata.is_empty()
+                {
+                    active(&op.account)
+                } else {
+                    posting(&op.account)
+                }
+            }
+            Self::WitnessUpdate(op) => active(&op.owner),
+            Self::AccountWitnessVote(op) => active(&op.account),
+            Self::AccountWitnessProxy(op) => active(&op.account),
+            Self::Custom(op) => {
+                required.active = op.required_auths.clone();
+                required
+            }
+            Self::CustomJson(op) => {
+                required.active = op.required_auths.clone();
+                required.posting = op.required_posting_auths.clone();
+                required
+            }
+            Self::CustomBinary(op) => {
+                required.owner = op.required_owner_auths.clone();
+                required.active = op.required_active_auths.clone();
+                required.posting = op.required_posting_auths.clone();
+                required.other = op.required_auths.clone();
+                required
+            }
+            Self::SetWithdrawVestingRoute(op) => active(&op.from_account),
+            Self::RequestAccountRecovery(op) => active(&op.recovery_account),
+            Self::RecoverAccount(op) => {
+                required.other = vec![
+                    op.new_owner_authority.clone(),
+                    op.recent_owner_authority.clone(),
+                ];
+                required
+            }
+            Self::ChangeRecoveryAccount(op) => owner(&op.account_to_recover),
+            Self::EscrowTransfer(op) => active(&op.from),
+            Self::EscrowDispute(op) => active(&op.who),
+            Self::EscrowRelease(op) => active(&op.who),
+            Self::EscrowApprove(op) => active(&op.who),
+            Self::TransferToSavings(op) => active(&op.from),
+            Self::TransferFromSavings(op) => active(&op.from),
+            Self::CancelTransferFromSavings(op) => active(&op.from),
+            Self::DeclineVotingRights(op) => owner(&op.account),
+            Self::ResetAccount(op) => active(&op.reset_account),
+            Self::SetResetAccount(op) => owner(&op.account),
+            Self::DelegateVestingShares(op) => active(&op.delegator),
+            Self::WitnessSetProperties(op) => {
+                // Signed by the witness signing key carried in the "key" prop.
+                let key = op
+                    .props
+                    .iter()
+                    .find(|(name, _)| name == "key")
+                    .and_then(|(_, bytes)| <[u8; 33]>::try_from(bytes.as_slice()).ok())
+                    .and_then(|bytes| PublicKey::from_bytes(bytes, "STM").ok());
+                match key {
+                    Some(key) => {
+                        required.other = vec![Authority {
+                            weight_threshold: 1,
+                            account_auths: vec![],
+                            key_auths: vec![(key.to_string(), 1)],
+                        }];
+                        required
+                    }
+                    None => active(&op.owner),
+                }
+            }
+            Self::CreateProposal(op) => active(&op.creator),
+            Self::UpdateProposalVotes(op) => active(&op.voter),
+            Self::RemoveProposal(op) => active(&op.proposal_owner),
+            Self::UpdateProposal(op) => active(&op.creator),
+            Self::RecurrentTransfer(op) => active(&op.from),
+            Self::Pow(_)
+            | Self::Pow2(_)
+            | Self::ReportOverProduction(_)
+            | Self::Virtual { .. }
+            | Self::Unknown(_) => required,
+        }
+    }
+
+    /// The first hardfork in which the chain accepts this operation.
+    pub fn min_hardfork(&self) -> u32 {
+        match self {
+            Self::EscrowTransfer(_)
+            | Self::EscrowDispute(_)
+            | Self::EscrowRelease(_)
+            | Self::EscrowApprove(_)
+            | Self::TransferToSavings(_)
+            | Self::TransferFromSavings(_)
+            | Self::CancelTransferFromSavings(_)
+            | Self::DeclineVotingRights(_) => 14,
+            Self::ClaimRewardBalance(_)
+            | Self::DelegateVestingShares(_)
+            | Self::AccountCreateWithDelegation(_) => 17,
+            Self::ClaimAccount(_)
+            | Self::CreateClaimedAccount(_)
+            | Self::WitnessSetProperties(_) => 20,
+            Self::AccountUpdate2(_)
+            | Self::CreateProposal(_)
+            | Self::UpdateProposalVotes(_)
+            | Self::RemoveProposal(_) => 21,
+            Self::UpdateProposal(_) => 24,
+            Self::CollateralizedConvert(_) | Self::RecurrentTransfer(_) => 25,
+            _ => 0,
+        }
+    }
+
     pub fn id(&self) -> u8 {
         match self {
             Self::Vote(_) => 0,
@@ -182,6 +429,7 @@ impl Operation {
             Self::CollateralizedConvert(_) => 48,
             Self::RecurrentTransfer(_) => 49,
             Self::Virtual { .. } => 255,
+            Self::Unknown(op) => op.id.unwrap_or(255),
         }
     }
 }
@@ -245,6 +493,7 @@ impl Serialize for Operation {
             Self::CollateralizedConvert(op) => seq.serialize_element(op)?,
             Self::RecurrentTransfer(op) => seq.serialize_element(op)?,
             Self::Virtual { body, .. } => seq.serialize_element(body)?,
+            Self::Unknown(op) => seq.serialize_element(&op.payload)?,
         }
         seq.end()
     }
@@ -362,10 +611,18 @@ impl<'de> Deserialize<'de> for Operation {
                 parse_variant!(CollateralizedConvert, CollateralizedConvertOperation)
             }
             "recurrent_transfer" => parse_variant!(RecurrentTransfer, RecurrentTransferOperation),
-            _ => Ok(Self::Virtual {
+            // Every broadcastable name is matched above, so a known name here
+            // is a virtual operation.
+            _ if OperationName::from_name(op_name).is_some() => Ok(Self::Virtual {
                 op_type: op_name.to_string(),
                 body: op_value,
             }),
+            _ => Ok(Self::Unknown(RawOperation {
+                id: None,
+                name: op_name.to_string(),
+                payload: op_value,
+                body: Vec::new(),
+            })),
         }
     }
 }
@@ -471,10 +728,264 @@ pub enum OperationName {
     DeclinedVotingRights = 92,
 }
 
+/// Every operation name, indexed by id. The first
+/// [`OperationName::NON_VIRTUAL_COUNT`] entries are the broadcastable ops.
+const ALL_OPERATION_NAMES: [OperationName; 93] = [
+    OperationName::Vote,
+    OperationName::Comment,
+    OperationName::Transfer,
+    OperationName::TransferToVesting,
+    OperationName::WithdrawVesting,
+    OperationName::LimitOrderCreate,
+    OperationName::LimitOrderCancel,
+    OperationName::FeedPublish,
+    OperationName::Convert,
+    OperationName::AccountCreate,
+    OperationName::AccountUpdate,
+    OperationName::WitnessUpdate,
+    OperationName::AccountWitnessVote,
+    OperationName::AccountWitnessProxy,
+    OperationName::Pow,
+    OperationName::Custom,
+    OperationName::ReportOverProduction,
+    OperationName::DeleteComment,
+    OperationName::CustomJson,
+    OperationName::CommentOptions,
+    OperationName::SetWithdrawVestingRoute,
+    OperationName::LimitOrderCreate2,
+    OperationName::ClaimAccount,
+    OperationName::CreateClaimedAccount,
+    OperationName::RequestAccountRecovery,
+    OperationName::RecoverAccount,
+    OperationName::ChangeRecoveryAccount,
+    OperationName::EscrowTransfer,
+    OperationName::EscrowDispute,
+    OperationName::EscrowRelease,
+    OperationName::Pow2,
+    OperationName::EscrowApprove,
+    OperationName::TransferToSavings,
+    OperationName::TransferFromSavings,
+    OperationName::CancelTransferFromSavings,
+    OperationName::CustomBinary,
+    OperationName::DeclineVotingRights,
+    OperationName::ResetAccount,
+    OperationName::SetResetAccount,
+    OperationName::ClaimRewardBalance,
+    OperationName::DelegateVestingShares,
+    OperationName::AccountCreateWithDelegation,
+    OperationName::WitnessSetProperties,
+    OperationName::AccountUpdate2,
+    OperationName::CreateProposal,
+    OperationName::UpdateProposalVotes,
+    OperationName::RemoveProposal,
+    OperationName::UpdateProposal,
+    OperationName::CollateralizedConvert,
+    OperationName::RecurrentTransfer,
+    OperationName::FillConvertRequest,
+    OperationName::AuthorReward,
+    OperationName::CurationReward,
+    OperationName::CommentReward,
+    OperationName::LiquidityReward,
+    OperationName::Interest,
+    OperationName::FillVestingWithdraw,
+    OperationName::FillOrder,
+    OperationName::ShutdownWitness,
+    OperationName::FillTransferFromSavings,
+    OperationName::Hardfork,
+    OperationName::CommentPayoutUpdate,
+    OperationName::ReturnVestingDelegation,
+    OperationName::CommentBenefactorReward,
+    OperationName::ProducerReward,
+    OperationName::ClearNullAccountBalance,
+    OperationName::ProposalPay,
+    OperationName::DhfFunding,
+    OperationName::HardforkHive,
+    OperationName::HardforkHiveRestore,
+    OperationName::DelayedVoting,
+    OperationName::ConsolidateTreasuryBalance,
+    OperationName::EffectiveCommentVote,
+    OperationName::IneffectiveDeleteComment,
+    OperationName::DhfConversion,
+    OperationName::ExpiredAccountNotification,
+    OperationName::ChangedRecoveryAccount,
+    OperationName::TransferToVestingCompleted,
+    OperationName::PowReward,
+    OperationName::VestingSharesSplit,
+    OperationName::AccountCreated,
+    OperationName::FillCollateralizedConvertRequest,
+    OperationName::SystemWarning,
+    OperationName::FillRecurrentTransfer,
+    OperationName::FailedRecurrentTransfer,
+    OperationName::LimitOrderCancelled,
+    OperationName::ProducerMissedBlock,
+    OperationName::ProposalFee,
+    OperationName::CollateralizedConvertImmediateConversion,
+    OperationName::EscrowApproved,
+    OperationName::EscrowRejected,
+    OperationName::ProxyCleared,
+    OperationName::DeclinedVotingRights,
+];
+
+/// Snake_case wire names, indexed by id like [`ALL_OPERATION_NAMES`].
+const OPERATION_NAME_STRS: [&str; 93] = [
+    "vote",
+    "comment",
+    "transfer",
+    "transfer_to_vesting",
+    "withdraw_vesting",
+    "limit_order_create",
+    "limit_order_cancel",
+    "feed_publish",
+    "convert",
+    "account_create",
+    "account_update",
+    "witness_update",
+    "account_witness_vote",
+    "account_witness_proxy",
+    "pow",
+    "custom",
+    "report_over_production",
+    "delete_comment",
+    "custom_json",
+    "comment_options",
+    "set_withdraw_vesting_route",
+    "limit_order_create2",
+    "claim_account",
+    "create_claimed_account",
+    "request_account_recovery",
+    "recover_account",
+    "change_recovery_account",
+    "escrow_transfer",
+    "escrow_dispute",
+    "escrow_release",
+    "pow2",
+    "escrow_approve",
+    "transfer_to_savings",
+    "transfer_from_savings",
+    "cancel_transfer_from_savings",
+    "custom_binary",
+    "decline_voting_rights",
+    "reset_account",
+    "set_reset_account",
+    "claim_reward_balance",
+    "delegate_vesting_shares",
+    "account_create_with_delegation",
+    "witness_set_properties",
+    "account_update2",
+    "create_proposal",
+    "update_proposal_votes",
+    "remove_proposal",
+    "update_proposal",
+    "collateralized_convert",
+    "recurrent_transfer",
+    "fill_convert_request",
+    "author_reward",
+    "curation_reward",
+    "comment_reward",
+    "liquidity_reward",
+    "interest",
+    "fill_vesting_withdraw",
+    "fill_order",
+    "shutdown_witness",
+    "fill_transfer_from_savings",
+    "hardfork",
+    "comment_payout_update",
+    "return_vesting_delegation",
+    "comment_benefactor_reward",
+    "producer_reward",
+    "clear_null_account_balance",
+    "proposal_pay",
+    "dhf_funding",
+    "hardfork_hive",
+    "hardfork_hive_restore",
+    "delayed_voting",
+    "consolidate_treasury_balance",
+    "effective_comment_vote",
+    "ineffective_delete_comment",
+    "dhf_conversion",
+    "expired_account_notification",
+    "changed_recovery_account",
+    "transfer_to_vesting_completed",
+    "pow_reward",
+    "vesting_shares_split",
+    "account_created",
+    "fill_collateralized_convert_request",
+    "system_warning",
+    "fill_recurrent_transfer",
+    "failed_recurrent_transfer",
+    "limit_order_cancelled",
+    "producer_missed_block",
+    "proposal_fee",
+    "collateralized_convert_immediate_conversion",
+    "escrow_approved",
+    "escrow_rejected",
+    "proxy_cleared",
+    "declined_voting_rights",
+];
+
 impl OperationName {
+    /// Number of broadcastable (non-virtual) operations; virtual ids follow.
+    pub const NON_VIRTUAL_COUNT: usize = 50;
+
     pub fn id(self) -> u8 {
         self as u8
     }
+
+    /// All broadcastable operations, in id order.
+    pub fn all() -> &'static [OperationName] {
+        &ALL_OPERATION_NAMES[..Self::NON_VIRTUAL_COUNT]
+    }
+
+    /// All operations including virtual ones, in id order.
+    pub fn all_with_virtual() -> &'static [OperationName] {
+        &ALL_OPERATION_NAMES
+    }
+
+    pub fn from_id(id: u8) -> Option<OperationName> {
+        ALL_OPERATION_NAMES.get(usize::from(id)).copied()
+    }
+
+    /// Looks up an operation by its snake_case name, e.g. `"custom_json"`.
+    pub fn from_name(name: &str) -> Option<OperationName> {
+        serde_json::from_value(Value::String(name.to_string())).ok()
+    }
+
+    pub fn is_virtual(self) -> bool {
+        usize::from(self.id()) >= Self::NON_VIRTUAL_COUNT
+    }
+
+    /// The snake_case name used on the wire, e.g. `"custom_json"`.
+    pub fn name(self) -> &'static str {
+        OPERATION_NAME_STRS[usize::from(self.id())]
+    }
+
+    pub fn metadata(self) -> OperationMeta {
+        OperationMeta {
+            id: self.id(),
+            name: self.name(),
+            is_virtual: self.is_virtual(),
+        }
+    }
+
+    /// Metadata for every operation, virtual ones included, in id order.
+    pub fn all_metadata() -> &'static [OperationMeta] {
+        static METADATA: OnceLock<Vec<OperationMeta>> = OnceLock::new();
+        METADATA.get_or_init(|| {
+            ALL_OPERATION_NAMES
+                .iter()
+                .map(|name| name.metadata())
+                .collect()
+        })
+    }
+}
+
+/// Describes an operation for filter pickers and similar UIs; pair the ids
+/// with [`crate::utils::make_bit_mask_filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OperationMeta {
+    pub id: u8,
+    pub name: &'static str,
+    pub is_virtual: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -527,6 +1038,63 @@ pub struct LimitOrderCreateOperation {
     pub expiration: String,
 }
 
+impl LimitOrderCreateOperation {
+    /// Builds an order selling `sell` at `price` units of the other market asset
+    /// per unit sold (HBD per HIVE when selling HIVE, and vice versa).
+    pub fn at_price(
+        owner: impl Into<String>,
+        orderid: u32,
+        sell: Asset,
+        price: f64,
+        fill_or_kill: bool,
+        expiration: impl Into<String>,
+    ) -> Result<Self> {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(HiveError::InvalidAsset(format!(
+                "limit order price must be positive, got {price}"
+            )));
+        }
+        if sell.amount <= 0 {
+            return Err(HiveError::InvalidAsset(
+                "limit order must sell a positive amount".to_string(),
+            ));
+        }
+        let receive_symbol = match sell.symbol {
+            AssetSymbol::Hive => AssetSymbol::Hbd,
+            AssetSymbol::Hbd => AssetSymbol::Hive,
+            _ => {
+                return Err(HiveError::InvalidAsset(format!(
+                    "limit orders can only sell HIVE or HBD, got {}",
+                    sell.symbol.as_str()
+                )))
+            }
+        };
+
+        let receive_precision = 3_u8;
+        let scale = 10_f64.powi(i32::from(receive_precision) - i32::from(sell.precision));
+        let amount = (sell.amount as f64 * price * scale).round();
+        if amount < 1.0 || amount >= i64::MAX as f64 {
+            return Err(HiveError::InvalidAsset(format!(
+                "price {price} does not yield a representable amount to receive"
+            )));
+        }
+
+        Ok(Self {
+            owner: owner.into(),
+            orderid,
+            amount_to_sell: sell,
+            min_to_receive: Asset {
+                amount: amount as i64,
+                precision: receive_precision,
+                symbol: receive_symbol,
+                display_symbol: None,
+            },
+            fill_or_kill,
+            expiration: expiration.into(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct LimitOrderCancelOperation {
     pub owner: String,
@@ -568,6 +1136,19 @@ pub struct AccountUpdateOperation {
     pub json_metadata: String,
 }
 
+impl AccountUpdateOperation {
+    /// Whether broadcasting this update would leave `account` unchanged, so
+    /// the broadcast (and its RC, and an owner history entry) can be skipped.
+    /// Authorities compare regardless of entry order; an empty
+    /// `json_metadata` leaves the current one in place.
+    pub fn is_noop_against(&self, account: &ExtendedAccount) -> bool {
+        self.account == account.name
+            && authorities_unchanged(&self.owner, &self.active, &self.posting, account)
+            && account.memo_key.as_deref() == Some(self.memo_key.as_str())
+            && metadata_unchanged(&self.json_metadata, &account.json_metadata)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct WitnessUpdateOperation {
     pub owner: String,
@@ -605,6 +1186,31 @@ pub struct CustomOperation {
     pub data: Vec<u8>,
 }
 
+impl CustomOperation {
+    /// Builds a numeric custom op whose `data` is `payload` encoded as JSON.
+    pub fn new(id: u16, required_auths: &[&str], payload: &impl Serialize) -> Result<Self> {
+        let data = serde_json::to_vec(payload)?;
+        Ok(Self::from_bytes(id, required_auths, data))
+    }
+
+    /// Builds a numeric custom op carrying an already-encoded binary payload.
+    pub fn from_bytes(id: u16, required_auths: &[&str], data: Vec<u8>) -> Self {
+        Self {
+            required_auths: required_auths
+                .iter()
+                .map(|account| account.to_string())
+                .collect(),
+            id,
+            data,
+        }
+    }
+
+    /// Decodes a JSON `data` payload built with [`CustomOperation::new`].
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.data)?)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ReportOverProductionOperation {
     pub reporter: String,
@@ -628,6 +1234,45 @@ pub struct CustomJsonOperation {
     pub json: String,
 }
 
+/// Payload field [`CustomJsonOperation::with_nonce`] writes the nonce to.
+pub const CUSTOM_JSON_NONCE_FIELD: &str = "nonce";
+
+impl CustomJsonOperation {
+    /// Adds a random 128-bit hex `nonce` field to the JSON object payload, so
+    /// a consumer can spot the same logical message broadcast twice by an
+    /// at-least-once pipeline. Call it once per message, before any retry.
+    pub fn with_nonce(self) -> Result<Self> {
+        let nonce = format!("{:032x}", rand::random::<u128>());
+        self.with_nonce_value(&nonce)
+    }
+
+    /// Like [`with_nonce`](Self::with_nonce) with an app-supplied dedup id.
+    /// The existing fields are kept as written. Fails if the payload is not a
+    /// JSON object or already has a `nonce`.
+    pub fn with_nonce_value(mut self, nonce: &str) -> Result<Self> {
+        let payload: serde_json::Map<String, Value> =
+            serde_json::from_str(&self.json).map_err(|err| {
+                HiveError::json(format!("custom_json payload must be a JSON object: {err}"))
+            })?;
+        if payload.contains_key(CUSTOM_JSON_NONCE_FIELD) {
+            return Err(HiveError::json(format!(
+                "custom_json payload already has a '{CUSTOM_JSON_NONCE_FIELD}' field"
+            )));
+        }
+
+        let field = format!(
+            "{}:{}",
+            Value::from(CUSTOM_JSON_NONCE_FIELD),
+            Value::from(nonce)
+        );
+        let body = self.json.trim_end();
+        let body = &body[..body.len() - 1];
+        let separator = if payload.is_empty() { "" } else { "," };
+        self.json = format!("{body}{separator}{field}}}");
+        Ok(self)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CommentOptionsOperation {
     pub author: String,
@@ -658,6 +1303,46 @@ pub struct LimitOrderCreate2Operation {
     pub expiration: String,
 }
 
+impl LimitOrderCreate2Operation {
+    /// Builds an order selling `sell` at `exchange_rate`, whose base must be the
+    /// asset being sold.
+    pub fn at_price(
+        owner: impl Into<String>,
+        orderid: u32,
+        sell: Asset,
+        exchange_rate: Price,
+        fill_or_kill: bool,
+        expiration: impl Into<String>,
+    ) -> Result<Self> {
+        if exchange_rate.base.amount <= 0 || exchange_rate.quote.amount <= 0 {
+            return Err(HiveError::InvalidAsset(
+                "exchange rate must have positive base and quote".to_string(),
+            ));
+        }
+        if exchange_rate.base.symbol != sell.symbol {
+            return Err(HiveError::InvalidAsset(format!(
+                "exchange rate base {} does not match sold asset {}",
+                exchange_rate.base.symbol.as_str(),
+                sell.symbol.as_str()
+            )));
+        }
+        if sell.amount <= 0 {
+            return Err(HiveError::InvalidAsset(
+                "limit order must sell a positive amount".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            owner: owner.into(),
+            orderid,
+            amount_to_sell: sell,
+            exchange_rate,
+            fill_or_kill,
+            expiration: expiration.into(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ClaimAccountOperation {
     pub creator: String,
@@ -697,6 +1382,34 @@ pub struct RecoverAccountOperation {
     pub extensions: Vec<()>,
 }
 
+impl RecoverAccountOperation {
+    /// `new_owner_authority` must match the recovery partner's outstanding
+    /// request; `recent_owner_authority` must have been the account's owner
+    /// within the last 30 days. The transaction needs signatures satisfying
+    /// both.
+    pub fn build(
+        account: impl Into<String>,
+        new_owner_authority: Authority,
+        recent_owner_authority: Authority,
+    ) -> Self {
+        Self {
+            account_to_recover: account.into(),
+            new_owner_authority,
+            recent_owner_authority,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// The user's side of an outstanding recovery `request`.
+    pub fn from_request(request: &RecoveryRequest, recent_owner_authority: Authority) -> Self {
+        Self::build(
+            request.account_to_recover.clone(),
+            request.new_owner_authority.clone(),
+            recent_owner_authority,
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ChangeRecoveryAccountOperation {
     pub account_to_recover: String,
@@ -853,6 +1566,32 @@ pub struct WitnessSetPropertiesOperation {
     pub extensions: Vec<()>,
 }
 
+impl WitnessSetPropertiesOperation {
+    /// Each prop's serialized value as hex, sorted by key: the exact bytes that
+    /// go into the signed transaction.
+    pub fn props_hex(&self) -> Vec<(String, String)> {
+        let mut props = self
+            .props
+            .iter()
+            .map(|(key, value)| (key.clone(), hex::encode(value)))
+            .collect::<Vec<_>>();
+        props.sort_by(|a, b| a.0.cmp(&b.0));
+        props
+    }
+
+    /// Decodes a key-valued prop (`key` or `new_signing_key`) back into a
+    /// public key with `prefix`, or `None` if the prop is absent.
+    pub fn prop_public_key(&self, name: &str, prefix: &str) -> Result<Option<PublicKey>> {
+        let Some((_, value)) = self.props.iter().find(|(key, _)| key == name) else {
+            return Ok(None);
+        };
+        let bytes: [u8; 33] = value.as_slice().try_into().map_err(|_| {
+            HiveError::InvalidKey(format!("{name} prop must be 33 bytes, got {}", value.len()))
+        })?;
+        PublicKey::from_bytes(bytes, prefix).map(Some)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AccountUpdate2Operation {
     pub account: String,
@@ -866,6 +1605,52 @@ pub struct AccountUpdate2Operation {
     pub extensions: Vec<()>,
 }
 
+impl AccountUpdate2Operation {
+    /// Like [`AccountUpdateOperation::is_noop_against`]; fields left as
+    /// `None` or empty do not change the account.
+    pub fn is_noop_against(&self, account: &ExtendedAccount) -> bool {
+        self.account == account.name
+            && authorities_unchanged(&self.owner, &self.active, &self.posting, account)
+            && self
+                .memo_key
+                .as_ref()
+                .map_or(true, |memo_key| account.memo_key.as_ref() == Some(memo_key))
+            && metadata_unchanged(&self.json_metadata, &account.json_metadata)
+            && metadata_unchanged(&self.posting_json_metadata, &account.posting_json_metadata)
+    }
+}
+
+fn authorities_unchanged(
+    owner: &Option<Authority>,
+    active: &Option<Authority>,
+    posting: &Option<Authority>,
+    account: &ExtendedAccount,
+) -> bool {
+    [
+        (owner, &account.owner),
+        (active, &account.active),
+        (posting, &account.posting),
+    ]
+    .into_iter()
+    .all(|(new, current)| match (new, current) {
+        (None, _) => true,
+        (Some(new), Some(current)) => canonical(new) == canonical(current),
+        (Some(_), None) => false,
+    })
+}
+
+fn canonical(authority: &Authority) -> Authority {
+    let mut canonical = authority.clone();
+    match canonical.canonicalize() {
+        Ok(()) => canonical,
+        Err(_) => authority.clone(),
+    }
+}
+
+fn metadata_unchanged(new: &str, current: &Option<String>) -> bool {
+    new.is_empty() || current.as_deref() == Some(new)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CreateProposalOperation {
     pub creator: String,
@@ -945,10 +1730,117 @@ pub enum UpdateProposalExtension {
 
 #[cfg(test)]
 mod tests {
+    use serde::{Deserialize, Serialize};
     use serde_json::json;
 
-    use super::{Operation, OperationName, TransferOperation};
-    use crate::types::Asset;
+    use super::{
+        AccountUpdate2Operation, AccountUpdateOperation, CustomJsonOperation, CustomOperation,
+        LimitOrderCreate2Operation, LimitOrderCreateOperation, Operation, OperationName,
+        RequiredAuthorities, TransferOperation, UpdateProposalOperation, VoteOperation,
+    };
+    use crate::types::{Asset, Authority, ExtendedAccount, Price};
+    use crate::utils::WitnessSetPropsBuilder;
+
+    #[test]
+    fn custom_json_with_nonce_adds_distinct_nonces() {
+        let op = CustomJsonOperation {
+            required_auths: Vec::new(),
+            required_posting_auths: vec!["alice".to_string()],
+            id: "game".to_string(),
+            json: r#"{"move":"e4","turn":3}"#.to_string(),
+        };
+
+        let first = op
+            .clone()
+            .with_nonce()
+            .expect("object payload takes a nonce");
+        let second = op
+            .clone()
+            .with_nonce()
+            .expect("object payload takes a nonce");
+        assert_ne!(first.json, second.json);
+        assert!(first.json.starts_with(r#"{"move":"e4","turn":3,"nonce":""#));
+        for signed in [&first, &second] {
+            let payload: serde_json::Value =
+                serde_json::from_str(&signed.json).expect("payload stays valid json");
+            assert_eq!(payload["move"], "e4");
+            assert_eq!(payload["turn"], 3);
+            assert_eq!(payload["nonce"].as_str().map(str::len), Some(32));
+            assert_eq!(signed.id, op.id);
+            assert_eq!(signed.required_posting_auths, op.required_posting_auths);
+        }
+
+        let empty = CustomJsonOperation {
+            json: "{}".to_string(),
+            ..op.clone()
+        };
+        assert_eq!(
+            empty.with_nonce_value("abc").expect("empty object").json,
+            r#"{"nonce":"abc"}"#
+        );
+        assert!(first.with_nonce().is_err());
+        let array = CustomJsonOperation {
+            json: r#"["follow",{}]"#.to_string(),
+            ..op
+        };
+        assert!(array.with_nonce().is_err());
+    }
+
+    #[test]
+    fn account_update_noop_detection() {
+        const KEY_A: &str = "STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA";
+        const KEY_B: &str = "STM6LLegbAgLAy28EHrffBVuANFWcFgmqRMW13wBmTExqFE9SCkg4";
+        let account: ExtendedAccount = serde_json::from_value(json!({
+            "name": "alice",
+            "memo_key": KEY_A,
+            "json_metadata": "{}",
+            "active": {
+                "weight_threshold": 1,
+                "account_auths": [],
+                "key_auths": [[KEY_A, 1], [KEY_B, 1]]
+            }
+        }))
+        .expect("account should parse");
+
+        let mut active = Authority {
+            weight_threshold: 1,
+            account_auths: Vec::new(),
+            key_auths: vec![(KEY_B.to_string(), 1), (KEY_A.to_string(), 1)],
+        };
+        let update = AccountUpdateOperation {
+            account: "alice".to_string(),
+            owner: None,
+            active: Some(active.clone()),
+            posting: None,
+            memo_key: KEY_A.to_string(),
+            json_metadata: String::new(),
+        };
+        assert!(update.is_noop_against(&account));
+
+        active.weight_threshold = 2;
+        let update = AccountUpdateOperation {
+            active: Some(active.clone()),
+            ..update
+        };
+        assert!(!update.is_noop_against(&account));
+
+        let update2 = AccountUpdate2Operation {
+            account: "alice".to_string(),
+            owner: None,
+            active: None,
+            posting: None,
+            memo_key: None,
+            json_metadata: "{}".to_string(),
+            posting_json_metadata: String::new(),
+            extensions: Vec::new(),
+        };
+        assert!(update2.is_noop_against(&account));
+        assert!(!AccountUpdate2Operation {
+            posting_json_metadata: "{\"profile\":{}}".to_string(),
+            ..update2
+        }
+        .is_noop_against(&account));
+    }
 
     #[test]
     fn operation_tuple_format_round_trip() {
@@ -984,7 +1876,7 @@ mod tests {
     }
 
     #[test]
-    fn unknown_operation_deserializes_as_virtual() {
+    fn known_virtual_operation_deserializes_as_virtual() {
         let op: Operation = serde_json::from_value(json!([
             "author_reward",
             {
@@ -1018,4 +1910,258 @@ mod tests {
         ];
         assert_eq!(ids, [0, 2, 18, 42, 49]);
     }
+
+    #[test]
+    fn operation_name_lookups_round_trip() {
+        assert_eq!(OperationName::from_id(18), Some(OperationName::CustomJson));
+        assert_eq!(
+            OperationName::from_name("recurrent_transfer"),
+            Some(OperationName::RecurrentTransfer)
+        );
+        assert_eq!(OperationName::from_name("not_an_op"), None);
+        assert_eq!(OperationName::from_id(200), None);
+        assert_eq!(OperationName::all().len(), 50);
+        assert!(OperationName::all().iter().all(|name| !name.is_virtual()));
+
+        for (index, name) in OperationName::all_with_virtual().iter().enumerate() {
+            assert_eq!(usize::from(name.id()), index);
+            assert_eq!(OperationName::from_id(name.id()), Some(*name));
+        }
+    }
+
+    #[test]
+    fn operation_metadata_matches_names_and_ids() {
+        let custom_json = OperationName::CustomJson.metadata();
+        assert_eq!(
+            (custom_json.id, custom_json.name, custom_json.is_virtual),
+            (18, "custom_json", false)
+        );
+        let producer_reward = OperationName::ProducerReward.metadata();
+        assert_eq!(producer_reward.name, "producer_reward");
+        assert!(producer_reward.is_virtual);
+
+        let metadata = OperationName::all_metadata();
+        assert_eq!(metadata.len(), 93);
+        assert!(metadata[..50].iter().all(|meta| !meta.is_virtual));
+        assert!(metadata[50..].iter().all(|meta| meta.is_virtual));
+        for meta in metadata {
+            let name = OperationName::from_name(meta.name).expect("name should parse");
+            assert_eq!(name.id(), meta.id);
+        }
+    }
+
+    #[test]
+    fn min_hardfork_reflects_operation_introduction() {
+        let transfer = Operation::Transfer(TransferOperation {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: Asset::hive(1.0),
+            memo: String::new(),
+        });
+        let update_proposal = Operation::UpdateProposal(UpdateProposalOperation {
+            proposal_id: 1,
+            creator: "alice".to_string(),
+            daily_pay: Asset::hbd(1.0),
+            subject: "subject".to_string(),
+            permlink: "permlink".to_string(),
+            extensions: vec![],
+        });
+
+        assert_eq!(transfer.min_hardfork(), 0);
+        assert_eq!(update_proposal.min_hardfork(), 24);
+    }
+
+    #[test]
+    fn unrecognized_operation_deserializes_as_unknown() {
+        let op: Operation = serde_json::from_value(json!(["fork_custom_op", { "who": "alice" }]))
+            .expect("custom op should deserialize");
+
+        match &op {
+            Operation::Unknown(raw) => {
+                assert_eq!(raw.id, None);
+                assert_eq!(raw.name, "fork_custom_op");
+                assert_eq!(raw.payload["who"], "alice");
+            }
+            other => panic!("expected unknown operation, got {other:?}"),
+        }
+        assert!(!op.is_virtual());
+
+        let serialized = serde_json::to_value(&op).expect("custom op should serialize");
+        assert_eq!(serialized, json!(["fork_custom_op", { "who": "alice" }]));
+    }
+
+    #[test]
+    fn op_array_helpers_round_trip_transfer() {
+        let value = json!([
+            "transfer",
+            {"from": "alice", "to": "bob", "amount": "1.000 HIVE", "memo": "hi"}
+        ]);
+
+        let op = Operation::from_op_array(&value).expect("transfer should parse");
+        assert!(matches!(&op, Operation::Transfer(transfer) if transfer.to == "bob"));
+        assert_eq!(op.to_op_array(), value);
+    }
+
+    #[test]
+    fn op_array_rejects_unknown_operation_name() {
+        let err = Operation::from_op_array(&json!(["teleport", {"to": "bob"}]))
+            .expect_err("unknown op should fail");
+        assert!(err
+            .to_string()
+            .contains("unknown operation name 'teleport'"));
+    }
+
+    #[test]
+    fn limit_order_at_price_computes_min_to_receive() {
+        let op = LimitOrderCreateOperation::at_price(
+            "alice",
+            1,
+            Asset::hive(10.0),
+            0.3,
+            false,
+            "2024-05-30T00:00:00",
+        )
+        .expect("order should build");
+        assert_eq!(op.min_to_receive.to_string(), "3.000 HBD");
+
+        let err = LimitOrderCreateOperation::at_price(
+            "alice",
+            1,
+            Asset::hive(10.0),
+            0.0,
+            false,
+            "2024-05-30T00:00:00",
+        )
+        .expect_err("zero price should fail");
+        assert!(err.to_string().contains("must be positive"));
+    }
+
+    #[test]
+    fn limit_order2_at_price_requires_matching_base() {
+        let rate = Price {
+            base: Asset::hive(1.0),
+            quote: Asset::hbd(0.3),
+        };
+        let op = LimitOrderCreate2Operation::at_price(
+            "alice",
+            2,
+            Asset::hive(10.0),
+            rate.clone(),
+            false,
+            "2024-05-30T00:00:00",
+        )
+        .expect("order should build");
+        assert_eq!(op.exchange_rate, rate);
+
+        assert!(LimitOrderCreate2Operation::at_price(
+            "alice",
+            2,
+            Asset::hbd(10.0),
+            rate,
+            false,
+            "2024-05-30T00:00:00",
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn required_authorities_follow_operation_roles() {
+        let vote = Operation::Vote(VoteOperation {
+            voter: "alice".to_string(),
+            author: "bob".to_string(),
+            permlink: "post".to_string(),
+            weight: 10000,
+        });
+        let transfer = Operation::Transfer(TransferOperation {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: Asset::hive(1.0),
+            memo: String::new(),
+        });
+
+        assert_eq!(vote.required_authorities().posting, ["alice"]);
+        assert_eq!(transfer.required_authorities().active, ["alice"]);
+
+        let merged = RequiredAuthorities::for_operations(&[vote, transfer.clone(), transfer]);
+        assert_eq!(merged.posting, ["alice"]);
+        assert_eq!(merged.active, ["alice"]);
+        assert!(merged.owner.is_empty());
+    }
+
+    #[test]
+    fn account_update2_needs_active_for_json_metadata() {
+        let update = AccountUpdate2Operation {
+            account: "alice".to_string(),
+            owner: None,
+            active: None,
+            posting: None,
+            memo_key: None,
+            json_metadata: String::new(),
+            posting_json_metadata: "{\"profile\":{}}".to_string(),
+            extensions: Vec::new(),
+        };
+        let posting_only = Operation::AccountUpdate2(update.clone()).required_authorities();
+        assert_eq!(posting_only.posting, ["alice"]);
+        assert!(posting_only.active.is_empty());
+
+        let json_metadata = Operation::AccountUpdate2(AccountUpdate2Operation {
+            json_metadata: "{\"app\":\"hive-rs\"}".to_string(),
+            posting_json_metadata: String::new(),
+            ..update
+        })
+        .required_authorities();
+        assert_eq!(json_metadata.active, ["alice"]);
+        assert!(json_metadata.posting.is_empty());
+    }
+
+    #[test]
+    fn witness_props_hex_shows_signed_bytes() {
+        let key = "STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA";
+        let operation = WitnessSetPropsBuilder::new("alice", key)
+            .hbd_interest_rate(1500)
+            .new_signing_key(key)
+            .build()
+            .expect("props should build");
+
+        let props = operation.props_hex();
+        let keys = props
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["hbd_interest_rate", "key", "new_signing_key"]);
+        assert_eq!(props[0].1, "dc05");
+
+        let decoded = operation
+            .prop_public_key("new_signing_key", "STM")
+            .expect("key should decode")
+            .expect("prop should be present");
+        assert_eq!(decoded.to_string(), key);
+        assert_eq!(props[2].1, decoded.to_hex());
+        assert!(operation
+            .prop_public_key("url", "STM")
+            .expect("absent prop is not an error")
+            .is_none());
+    }
+
+    #[test]
+    fn custom_op_payload_round_trips() {
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct Move {
+            game: u32,
+            square: String,
+        }
+
+        let played = Move {
+            game: 7,
+            square: "e4".to_string(),
+        };
+        let op = CustomOperation::new(777, &["alice"], &played).expect("payload should encode");
+        assert_eq!(op.id, 777);
+        assert_eq!(op.required_auths, vec!["alice".to_string()]);
+        assert_eq!(op.payload::<Move>().expect("payload should decode"), played);
+
+        let raw = CustomOperation::from_bytes(1, &[], vec![0xde, 0xad]);
+        assert_eq!(raw.data, vec![0xde, 0xad]);
+        assert!(raw.payload::<Move>().is_err());
+    }
 }
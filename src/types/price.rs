@@ -1,9 +1,81 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::Asset;
+use crate::types::{Asset, AssetSymbol};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Price {
     pub base: Asset,
     pub quote: Asset,
 }
+
+impl Price {
+    /// The same exchange rate expressed the other way round.
+    pub fn reciprocal(&self) -> Price {
+        Price {
+            base: self.quote.clone(),
+            quote: self.base.clone(),
+        }
+    }
+
+    /// Whether the chain would accept this price: both amounts positive and
+    /// the two sides in different assets.
+    pub fn is_valid(&self) -> bool {
+        self.base.amount > 0 && self.quote.amount > 0 && self.base.symbol != self.quote.symbol
+    }
+
+    /// This price as HBD per HIVE, flipping a HIVE/HBD quote. `None` unless it
+    /// is a valid price between HBD and HIVE.
+    pub fn hbd_per_hive(&self) -> Option<Price> {
+        if !self.is_valid() {
+            return None;
+        }
+        match (&self.base.symbol, &self.quote.symbol) {
+            (AssetSymbol::Hbd, AssetSymbol::Hive) => Some(self.clone()),
+            (AssetSymbol::Hive, AssetSymbol::Hbd) => Some(self.reciprocal()),
+            _ => None,
+        }
+    }
+
+    /// Units of `base` per unit of `quote`, for display. Returns `0.0` when the
+    /// quote is zero.
+    pub fn as_f64(&self) -> f64 {
+        let quote = self.quote.as_f64();
+        if quote == 0.0 {
+            return 0.0;
+        }
+        self.base.as_f64() / quote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Asset, Price};
+
+    #[test]
+    fn reciprocal_and_validity() {
+        let price = Price {
+            base: Asset::hbd(1.0),
+            quote: Asset::hive(3.0),
+        };
+        let inverse = price.reciprocal();
+        assert_eq!(inverse.base, Asset::hive(3.0));
+        assert_eq!(inverse.quote, Asset::hbd(1.0));
+        assert!((price.as_f64() - 1.0 / 3.0).abs() < 1e-12);
+        assert!((inverse.as_f64() - 3.0).abs() < 1e-12);
+        assert!(price.is_valid());
+        assert_eq!(inverse.reciprocal(), price);
+
+        let zero = Price {
+            base: Asset::hbd(0.0),
+            quote: Asset::hive(3.0),
+        };
+        assert!(!zero.is_valid());
+        assert_eq!(zero.reciprocal().as_f64(), 0.0);
+
+        let same_symbol = Price {
+            base: Asset::hive(1.0),
+            quote: Asset::hive(2.0),
+        };
+        assert!(!same_symbol.is_valid());
+    }
+}
@@ -117,7 +117,7 @@ pub struct ManaResult {
     pub percentage: f64,
 }
 
-const MANA_REGEN_SECONDS: i64 = 432_000; // 5 days
+pub(crate) const MANA_REGEN_SECONDS: i64 = 432_000; // 5 days
 
 pub fn compute_mana(current_mana: i64, last_update_time: u64, max_mana: i64) -> ManaResult {
     let now = std::time::SystemTime::now()
@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::Operation;
+use crate::crypto::verify::{authority_satisfied, recover_signers};
+use crate::error::Result;
+use crate::serialization::serializer::{serialize_transaction, transaction_digest};
+use crate::serialization::types::write_varint32;
+use crate::types::{Authority, ChainId, Operation};
+
+const SIGNATURE_SIZE_BYTES: usize = 65;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct Transaction {
@@ -13,6 +19,20 @@ pub struct Transaction {
     pub extensions: Vec<String>,
 }
 
+impl Transaction {
+    /// Size in bytes of the unsigned binary transaction.
+    pub fn serialized_size(&self) -> Result<usize> {
+        Ok(serialize_transaction(self)?.len())
+    }
+
+    /// Size in bytes the transaction will have once `num_signatures` are attached.
+    pub fn estimated_signed_size(&self, num_signatures: usize) -> Result<usize> {
+        let mut count_prefix = Vec::new();
+        write_varint32(&mut count_prefix, num_signatures as u32);
+        Ok(self.serialized_size()? + count_prefix.len() + SIGNATURE_SIZE_BYTES * num_signatures)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct SignedTransaction {
     pub ref_block_num: u16,
@@ -26,6 +46,48 @@ pub struct SignedTransaction {
     pub signatures: Vec<String>,
 }
 
+impl SignedTransaction {
+    /// The transaction without its signatures, as used for digests and ids.
+    pub fn unsigned(&self) -> Transaction {
+        Transaction {
+            ref_block_num: self.ref_block_num,
+            ref_block_prefix: self.ref_block_prefix,
+            expiration: self.expiration.clone(),
+            operations: self.operations.clone(),
+            extensions: self.extensions.clone(),
+        }
+    }
+
+    pub fn signatures_count(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Whether the keys recovered from the current signatures carry enough
+    /// weight to meet `authority`'s threshold. Only `key_auths` count; account
+    /// references are not followed. A malformed signature yields `false`.
+    pub fn satisfies(&self, authority: &Authority, chain_id: &ChainId) -> Result<bool> {
+        let digest = transaction_digest(&self.unsigned(), chain_id)?;
+        Ok(recover_signers(&self.signatures, &digest)
+            .is_some_and(|signers| authority_satisfied(authority, &signers, &|_| None, 0)))
+    }
+}
+
+/// A transaction as returned by `get_transaction`, with its block position.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct AppliedTransaction {
+    pub ref_block_num: u16,
+    pub ref_block_prefix: u32,
+    pub expiration: String,
+    pub operations: Vec<Operation>,
+    pub extensions: Vec<String>,
+    pub signatures: Vec<String>,
+    pub transaction_id: String,
+    pub block_num: u32,
+    #[serde(alias = "trx_num")]
+    pub transaction_num: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct TransactionConfirmation {
     pub id: String,
@@ -37,4 +99,103 @@ pub struct TransactionConfirmation {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct TransactionStatus {
     pub status: String,
+    /// Set when the transaction is in a block (`found_in_block`,
+    /// `found_in_block_and_irreversible`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_num: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::{sign_transaction, PrivateKey};
+    use crate::serialization::serializer::serialize_transaction;
+    use crate::types::{
+        AppliedTransaction, Authority, ChainId, Operation, Transaction, VoteOperation,
+    };
+
+    #[test]
+    fn size_helpers_match_serialized_bytes() {
+        let tx = Transaction {
+            ref_block_num: 1234,
+            ref_block_prefix: 1122334455,
+            expiration: "2017-07-15T16:51:19".to_string(),
+            operations: vec![Operation::Vote(VoteOperation {
+                voter: "foo".to_string(),
+                author: "bar".to_string(),
+                permlink: "baz".to_string(),
+                weight: 10000,
+            })],
+            extensions: vec![],
+        };
+
+        let unsigned = serialize_transaction(&tx)
+            .expect("tx should serialize")
+            .len();
+        assert_eq!(tx.serialized_size().expect("size"), unsigned);
+        assert_eq!(tx.estimated_signed_size(0).expect("size"), unsigned + 1);
+        assert_eq!(
+            tx.estimated_signed_size(2).expect("size"),
+            unsigned + 1 + 130
+        );
+    }
+
+    #[test]
+    fn applied_transaction_decodes_get_transaction_response() {
+        let tx: AppliedTransaction = serde_json::from_value(serde_json::json!({
+            "ref_block_num": 36374,
+            "ref_block_prefix": 3498149902_u32,
+            "expiration": "2024-01-01T00:01:00",
+            "operations": [["vote", {
+                "voter": "alice",
+                "author": "bob",
+                "permlink": "post",
+                "weight": 10000
+            }]],
+            "extensions": [],
+            "signatures": ["1f00"],
+            "transaction_id": "6fde0190a97835ea6d9e651293e90c89911f933c",
+            "block_num": 82345678,
+            "transaction_num": 3
+        }))
+        .expect("transaction should deserialize");
+
+        assert!(matches!(tx.operations[0], Operation::Vote(ref vote) if vote.voter == "alice"));
+        assert_eq!(tx.block_num, 82345678);
+        assert_eq!(tx.transaction_num, 3);
+        assert_eq!(tx.signatures, vec!["1f00".to_string()]);
+    }
+
+    #[test]
+    fn satisfies_counts_recovered_key_weights() {
+        let keys: Vec<PrivateKey> = ["one", "two", "three"]
+            .iter()
+            .map(|seed| PrivateKey::from_seed(seed).expect("seed should derive a key"))
+            .collect();
+        let authority = keys
+            .iter()
+            .fold(Authority::builder().threshold(2), |builder, key| {
+                builder.add_key(key.public_key().to_string(), 1)
+            })
+            .build()
+            .expect("authority should build");
+        let chain_id = ChainId::mainnet();
+        let tx = Transaction {
+            ref_block_num: 1234,
+            ref_block_prefix: 1122334455,
+            expiration: "2024-01-01T00:01:00".to_string(),
+            operations: vec![],
+            extensions: vec![],
+        };
+
+        let one = sign_transaction(&tx, &[&keys[0]], &chain_id).expect("tx should sign");
+        assert_eq!(one.signatures_count(), 1);
+        assert!(!one.satisfies(&authority, &chain_id).unwrap());
+
+        let two = sign_transaction(&tx, &[&keys[0], &keys[2]], &chain_id).expect("tx should sign");
+        assert_eq!(two.signatures_count(), 2);
+        assert!(two.satisfies(&authority, &chain_id).unwrap());
+        assert!(!two.satisfies(&authority, &ChainId::testnet()).unwrap());
+    }
 }
@@ -0,0 +1,362 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::error::{HiveError, Result};
+use crate::types::{Asset, Operation};
+
+/// A node-generated operation, as reported by `get_ops_in_block` and account
+/// history. These never appear in transactions, so they only deserialize.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VirtualOperation {
+    FillConvertRequest(FillConvertRequestOperation),
+    AuthorReward(AuthorRewardOperation),
+    CurationReward(CurationRewardOperation),
+    CommentReward(CommentRewardOperation),
+    LiquidityReward(LiquidityRewardOperation),
+    Interest(InterestOperation),
+    FillVestingWithdraw(FillVestingWithdrawOperation),
+    FillOrder(FillOrderOperation),
+    FillTransferFromSavings(FillTransferFromSavingsOperation),
+    ReturnVestingDelegation(ReturnVestingDelegationOperation),
+    CommentBenefactorReward(CommentBenefactorRewardOperation),
+    ProducerReward(ProducerRewardOperation),
+    ProposalPay(ProposalPayOperation),
+    TransferToVestingCompleted(TransferToVestingCompletedOperation),
+    FillCollateralizedConvertRequest(FillCollateralizedConvertRequestOperation),
+    FillRecurrentTransfer(FillRecurrentTransferOperation),
+    FailedRecurrentTransfer(FailedRecurrentTransferOperation),
+    /// A virtual operation without a typed model here.
+    Other {
+        op_type: String,
+        body: Value,
+    },
+}
+
+impl VirtualOperation {
+    /// Decodes a virtual op from its name and JSON body. Unmodelled names
+    /// become [`VirtualOperation::Other`]; a modelled name with a malformed
+    /// body is an error.
+    pub fn from_parts(op_type: &str, body: Value) -> Result<Self> {
+        macro_rules! parse_variant {
+            ($variant:ident) => {
+                serde_json::from_value(body)
+                    .map(Self::$variant)
+                    .map_err(|err| {
+                        HiveError::json(format!("invalid '{op_type}' virtual operation: {err}"))
+                    })
+            };
+        }
+
+        match op_type {
+            "fill_convert_request" => parse_variant!(FillConvertRequest),
+            "author_reward" => parse_variant!(AuthorReward),
+            "curation_reward" => parse_variant!(CurationReward),
+            "comment_reward" => parse_variant!(CommentReward),
+            "liquidity_reward" => parse_variant!(LiquidityReward),
+            "interest" => parse_variant!(Interest),
+            "fill_vesting_withdraw" => parse_variant!(FillVestingWithdraw),
+            "fill_order" => parse_variant!(FillOrder),
+            "fill_transfer_from_savings" => parse_variant!(FillTransferFromSavings),
+            "return_vesting_delegation" => parse_variant!(ReturnVestingDelegation),
+            "comment_benefactor_reward" => parse_variant!(CommentBenefactorReward),
+            "producer_reward" => parse_variant!(ProducerReward),
+            "proposal_pay" => parse_variant!(ProposalPay),
+            "transfer_to_vesting_completed" => parse_variant!(TransferToVestingCompleted),
+            "fill_collateralized_convert_request" => {
+                parse_variant!(FillCollateralizedConvertRequest)
+            }
+            "fill_recurrent_transfer" => parse_variant!(FillRecurrentTransfer),
+            "failed_recurrent_transfer" => parse_variant!(FailedRecurrentTransfer),
+            _ => Ok(Self::Other {
+                op_type: op_type.to_string(),
+                body,
+            }),
+        }
+    }
+
+    /// The typed form of an [`Operation::Virtual`]; `None` for regular ops.
+    pub fn from_operation(operation: &Operation) -> Option<Result<Self>> {
+        match operation {
+            Operation::Virtual { op_type, body } => Some(Self::from_parts(op_type, body.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn op_name(&self) -> &str {
+        match self {
+            Self::FillConvertRequest(_) => "fill_convert_request",
+            Self::AuthorReward(_) => "author_reward",
+            Self::CurationReward(_) => "curation_reward",
+            Self::CommentReward(_) => "comment_reward",
+            Self::LiquidityReward(_) => "liquidity_reward",
+            Self::Interest(_) => "interest",
+            Self::FillVestingWithdraw(_) => "fill_vesting_withdraw",
+            Self::FillOrder(_) => "fill_order",
+            Self::FillTransferFromSavings(_) => "fill_transfer_from_savings",
+            Self::ReturnVestingDelegation(_) => "return_vesting_delegation",
+            Self::CommentBenefactorReward(_) => "comment_benefactor_reward",
+            Self::ProducerReward(_) => "producer_reward",
+            Self::ProposalPay(_) => "proposal_pay",
+            Self::TransferToVestingCompleted(_) => "transfer_to_vesting_completed",
+            Self::FillCollateralizedConvertRequest(_) => "fill_collateralized_convert_request",
+            Self::FillRecurrentTransfer(_) => "fill_recurrent_transfer",
+            Self::FailedRecurrentTransfer(_) => "failed_recurrent_transfer",
+            Self::Other { op_type, .. } => op_type,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VirtualOperation {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (op_type, body) = <(String, Value)>::deserialize(deserializer)?;
+        Self::from_parts(&op_type, body).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FillConvertRequestOperation {
+    pub owner: String,
+    pub requestid: u32,
+    pub amount_in: Asset,
+    pub amount_out: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct AuthorRewardOperation {
+    pub author: String,
+    pub permlink: String,
+    pub hbd_payout: Asset,
+    pub hive_payout: Asset,
+    pub vesting_payout: Asset,
+    #[serde(default)]
+    pub curators_vesting_payout: Option<Asset>,
+    #[serde(default)]
+    pub payout_must_be_claimed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct CurationRewardOperation {
+    pub curator: String,
+    pub reward: Asset,
+    pub comment_author: String,
+    pub comment_permlink: String,
+    #[serde(default)]
+    pub payout_must_be_claimed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct CommentRewardOperation {
+    pub author: String,
+    pub permlink: String,
+    pub payout: Asset,
+    #[serde(default)]
+    pub author_rewards: i64,
+    #[serde(default)]
+    pub total_payout_value: Option<Asset>,
+    #[serde(default)]
+    pub curator_payout_value: Option<Asset>,
+    #[serde(default)]
+    pub beneficiary_payout_value: Option<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct LiquidityRewardOperation {
+    pub owner: String,
+    pub payout: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct InterestOperation {
+    pub owner: String,
+    pub interest: Asset,
+    #[serde(default)]
+    pub is_saved_into_hbd_balance: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FillVestingWithdrawOperation {
+    pub from_account: String,
+    pub to_account: String,
+    pub withdrawn: Asset,
+    pub deposited: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FillOrderOperation {
+    pub current_owner: String,
+    pub current_orderid: u32,
+    pub current_pays: Asset,
+    pub open_owner: String,
+    pub open_orderid: u32,
+    pub open_pays: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FillTransferFromSavingsOperation {
+    pub from: String,
+    pub to: String,
+    pub amount: Asset,
+    pub request_id: u32,
+    pub memo: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ReturnVestingDelegationOperation {
+    pub account: String,
+    pub vesting_shares: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct CommentBenefactorRewardOperation {
+    pub benefactor: String,
+    pub author: String,
+    pub permlink: String,
+    pub hbd_payout: Asset,
+    pub hive_payout: Asset,
+    pub vesting_payout: Asset,
+    #[serde(default)]
+    pub payout_must_be_claimed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ProducerRewardOperation {
+    pub producer: String,
+    pub vesting_shares: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ProposalPayOperation {
+    pub proposal_id: u32,
+    pub receiver: String,
+    pub payer: String,
+    pub payment: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct TransferToVestingCompletedOperation {
+    pub from_account: String,
+    pub to_account: String,
+    pub hive_vested: Asset,
+    pub vesting_shares_received: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FillCollateralizedConvertRequestOperation {
+    pub owner: String,
+    pub requestid: u32,
+    pub amount_in: Asset,
+    pub amount_out: Asset,
+    pub excess_collateral: Asset,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FillRecurrentTransferOperation {
+    pub from: String,
+    pub to: String,
+    pub amount: Asset,
+    pub memo: String,
+    pub remaining_executions: u16,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct FailedRecurrentTransferOperation {
+    pub from: String,
+    pub to: String,
+    pub amount: Asset,
+    pub memo: String,
+    pub consecutive_failures: u8,
+    pub remaining_executions: u16,
+    pub deleted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::types::{AppliedOperation, Asset, VirtualOperation};
+
+    #[test]
+    fn decodes_virtual_ops_from_ops_in_block() {
+        let ops: Vec<AppliedOperation> = serde_json::from_value(json!([
+            {
+                "trx_id": "0000000000000000000000000000000000000000",
+                "block": 80000000,
+                "trx_in_block": 4294967295_u32,
+                "op_in_trx": 0,
+                "virtual_op": 1,
+                "timestamp": "2023-11-09T11:42:00",
+                "op": ["author_reward", {
+                    "author": "alice",
+                    "permlink": "hello",
+                    "hbd_payout": "1.234 HBD",
+                    "hive_payout": "0.000 HIVE",
+                    "vesting_payout": "2500.123456 VESTS",
+                    "curators_vesting_payout": "2400.000000 VESTS",
+                    "payout_must_be_claimed": true
+                }]
+            },
+            {
+                "trx_id": "6bd22c5b3e8ea3c7b3b5ff3bcd4d4e7b9ec8c7bd",
+                "block": 80000000,
+                "trx_in_block": 3,
+                "op_in_trx": 1,
+                "virtual_op": 2,
+                "timestamp": "2023-11-09T11:42:00",
+                "op": ["fill_order", {
+                    "current_owner": "bob",
+                    "current_orderid": 42,
+                    "current_pays": "10.000 HBD",
+                    "open_owner": "carol",
+                    "open_orderid": 7,
+                    "open_pays": "30.000 HIVE"
+                }]
+            },
+            {
+                "virtual_op": 0,
+                "op": ["vote", {
+                    "voter": "alice",
+                    "author": "bob",
+                    "permlink": "post",
+                    "weight": 10000
+                }]
+            }
+        ]))
+        .expect("ops should decode");
+
+        match ops[0].virtual_operation() {
+            Some(Ok(VirtualOperation::AuthorReward(reward))) => {
+                assert_eq!(reward.author, "alice");
+                assert_eq!(reward.hbd_payout, Asset::from_string("1.234 HBD").unwrap());
+                assert!(reward.payout_must_be_claimed);
+            }
+            other => panic!("expected author_reward, got {other:?}"),
+        }
+        match ops[1].virtual_operation() {
+            Some(Ok(VirtualOperation::FillOrder(fill))) => {
+                assert_eq!(fill.current_orderid, 42);
+                assert_eq!(fill.open_pays, Asset::from_string("30.000 HIVE").unwrap());
+            }
+            other => panic!("expected fill_order, got {other:?}"),
+        }
+        assert!(ops[2].virtual_operation().is_none());
+    }
+
+    #[test]
+    fn unmodelled_virtual_ops_fall_back_to_other() {
+        let op: VirtualOperation =
+            serde_json::from_value(json!(["system_warning", {"message": "hi"}]))
+                .expect("virtual op should decode");
+        assert_eq!(op.op_name(), "system_warning");
+        assert!(matches!(op, VirtualOperation::Other { .. }));
+
+        assert!(serde_json::from_value::<VirtualOperation>(json!([
+            "producer_reward",
+            {"producer": "carol"}
+        ]))
+        .is_err());
+    }
+}
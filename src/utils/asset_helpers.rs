@@ -30,5 +30,27 @@ pub fn get_vests(props: &DynamicGlobalProperties, hive_power: &Asset) -> Asset {
         amount,
         precision: 6,
         symbol: AssetSymbol::Vests,
+        display_symbol: None,
+    }
+}
+
+pub fn vests_to_hp(props: &DynamicGlobalProperties, vests: &Asset) -> Asset {
+    let shares = match props.total_vesting_shares.as_ref() {
+        Some(value) if value.amount != 0 => value,
+        _ => return Asset::hive(0.0),
+    };
+
+    let fund = match props.total_vesting_fund_hive.as_ref() {
+        Some(value) => value,
+        None => return Asset::hive(0.0),
+    };
+
+    let amount =
+        ((vests.amount as f64) * (fund.amount as f64) / (shares.amount as f64)).round() as i64;
+    Asset {
+        amount,
+        precision: 3,
+        symbol: AssetSymbol::Hive,
+        display_symbol: None,
     }
 }
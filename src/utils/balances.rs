@@ -0,0 +1,635 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::error::{HiveError, Result};
+use crate::types::{AppliedOperation, Asset, AssetSymbol, Operation, VirtualOperation};
+
+/// One account's balances as rebuilt by folding its history through
+/// [`apply_operation_to_balances`].
+///
+/// `reward_*` hold payouts that must still be claimed with
+/// `claim_reward_balance`. Funds sitting in open orders, pending conversions
+/// and escrow are not part of any balance, matching how hived reports
+/// `balance` and `hbd_balance`; `open_orders` keeps what each order still has
+/// for sale so a cancellation can be refunded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceState {
+    pub account: String,
+    pub hive: Asset,
+    pub hbd: Asset,
+    pub vests: Asset,
+    pub savings_hive: Asset,
+    pub savings_hbd: Asset,
+    pub reward_hive: Asset,
+    pub reward_hbd: Asset,
+    pub reward_vests: Asset,
+    /// Amount still for sale in each open order, by order id.
+    pub open_orders: BTreeMap<u32, Asset>,
+}
+
+impl BalanceState {
+    /// All balances zero, for replaying an account from its creation.
+    pub fn new(account: impl Into<String>) -> Self {
+        Self {
+            account: account.into(),
+            hive: Asset::hive(0.0),
+            hbd: Asset::hbd(0.0),
+            vests: Asset::vests(0.0),
+            savings_hive: Asset::hive(0.0),
+            savings_hbd: Asset::hbd(0.0),
+            reward_hive: Asset::hive(0.0),
+            reward_hbd: Asset::hbd(0.0),
+            reward_vests: Asset::vests(0.0),
+            open_orders: BTreeMap::new(),
+        }
+    }
+
+    fn is(&self, account: &str) -> bool {
+        self.account == account
+    }
+
+    fn liquid(&mut self, symbol: &AssetSymbol) -> Option<&mut Asset> {
+        match symbol {
+            AssetSymbol::Hive => Some(&mut self.hive),
+            AssetSymbol::Hbd => Some(&mut self.hbd),
+            AssetSymbol::Vests => Some(&mut self.vests),
+            AssetSymbol::Custom(_) => None,
+        }
+    }
+
+    fn savings(&mut self, symbol: &AssetSymbol) -> Option<&mut Asset> {
+        match symbol {
+            AssetSymbol::Hive => Some(&mut self.savings_hive),
+            AssetSymbol::Hbd => Some(&mut self.savings_hbd),
+            _ => None,
+        }
+    }
+
+    fn reward(&mut self, symbol: &AssetSymbol) -> Option<&mut Asset> {
+        match symbol {
+            AssetSymbol::Hive => Some(&mut self.reward_hive),
+            AssetSymbol::Hbd => Some(&mut self.reward_hbd),
+            AssetSymbol::Vests => Some(&mut self.reward_vests),
+            AssetSymbol::Custom(_) => None,
+        }
+    }
+
+    fn credit(&mut self, amount: &Asset) -> Result<()> {
+        adjust(self.liquid(&amount.symbol), amount, Asset::checked_add)
+    }
+
+    fn debit(&mut self, amount: &Asset) -> Result<()> {
+        adjust(self.liquid(&amount.symbol), amount, Asset::checked_sub)
+    }
+
+    /// Takes `paid` off what order `orderid` still has for sale, dropping the
+    /// order once it is filled.
+    fn fill_order(&mut self, orderid: u32, paid: &Asset) -> Result<()> {
+        let Some(remaining) = self.open_orders.get_mut(&orderid) else {
+            return Ok(());
+        };
+        adjust(Some(remaining), paid, Asset::checked_sub)?;
+        if remaining.amount == 0 {
+            self.open_orders.remove(&orderid);
+        }
+        Ok(())
+    }
+
+    /// Refunds what order `orderid` still had for sale, if it is still open.
+    fn cancel_order(&mut self, orderid: u32) -> Result<()> {
+        match self.open_orders.remove(&orderid) {
+            Some(remaining) => self.credit(&remaining),
+            None => Ok(()),
+        }
+    }
+
+    /// Credits a reward either to the liquid balances or, when it still has
+    /// to be claimed, to the reward balances.
+    fn pay(&mut self, amount: &Asset, must_be_claimed: bool) -> Result<()> {
+        if must_be_claimed {
+            adjust(self.reward(&amount.symbol), amount, Asset::checked_add)
+        } else {
+            self.credit(amount)
+        }
+    }
+}
+
+fn adjust(
+    slot: Option<&mut Asset>,
+    amount: &Asset,
+    op: fn(&Asset, &Asset) -> Option<Asset>,
+) -> Result<()> {
+    let Some(slot) = slot else {
+        return Ok(());
+    };
+    let updated = op(slot, amount).ok_or_else(|| {
+        HiveError::InvalidAsset(format!("cannot apply {amount} to a balance of {slot}"))
+    })?;
+    if updated.amount < 0 {
+        return Err(HiveError::InvalidAsset(format!(
+            "applying {amount} to a balance of {slot} would make it negative"
+        )));
+    }
+    *slot = updated;
+    Ok(())
+}
+
+/// Applies one history entry to `balances`, so an account's history from
+/// `get_account_history` can be folded into its balances at any point.
+///
+/// Movements are taken from the operation that actually settles them: a
+/// `transfer_to_vesting` debits HIVE and its `transfer_to_vesting_completed`
+/// credits VESTS, a `transfer_from_savings` only lands with
+/// `fill_transfer_from_savings`, and so on. Operations that do not touch this
+/// account's balances, or that are not modelled, are ignored. Errors for a
+/// malformed virtual operation, an asset that does not fit its balance, or a
+/// movement that would take a balance below zero.
+pub fn apply_operation_to_balances(
+    balances: &mut BalanceState,
+    op: &AppliedOperation,
+) -> Result<()> {
+    match VirtualOperation::from_operation(&op.op) {
+        Some(virtual_op) => apply_virtual(balances, &virtual_op?),
+        None => apply_regular(balances, &op.op),
+    }
+}
+
+fn apply_regular(balances: &mut BalanceState, op: &Operation) -> Result<()> {
+    match op {
+        Operation::Transfer(op) => {
+            if balances.is(&op.from) {
+                balances.debit(&op.amount)?;
+            }
+            if balances.is(&op.to) {
+                balances.credit(&op.amount)?;
+            }
+        }
+        Operation::TransferToVesting(op) if balances.is(&op.from) => {
+            balances.debit(&op.amount)?;
+        }
+        Operation::TransferToSavings(op) => {
+            if balances.is(&op.from) {
+                balances.debit(&op.amount)?;
+            }
+            if balances.is(&op.to) {
+                adjust(
+                    balances.savings(&op.amount.symbol),
+                    &op.amount,
+                    Asset::checked_add,
+                )?;
+            }
+        }
+        Operation::TransferFromSavings(op) if balances.is(&op.from) => {
+            adjust(
+                balances.savings(&op.amount.symbol),
+                &op.amount,
+                Asset::checked_sub,
+            )?;
+        }
+        Operation::ClaimRewardBalance(op) if balances.is(&op.account) => {
+            for reward in [&op.reward_hive, &op.reward_hbd, &op.reward_vests] {
+                adjust(balances.reward(&reward.symbol), reward, Asset::checked_sub)?;
+                balances.credit(reward)?;
+            }
+        }
+        Operation::LimitOrderCreate(op) if balances.is(&op.owner) => {
+            balances.debit(&op.amount_to_sell)?;
+            balances
+                .open_orders
+                .insert(op.orderid, op.amount_to_sell.clone());
+        }
+        Operation::LimitOrderCreate2(op) if balances.is(&op.owner) => {
+            balances.debit(&op.amount_to_sell)?;
+            balances
+                .open_orders
+                .insert(op.orderid, op.amount_to_sell.clone());
+        }
+        Operation::LimitOrderCancel(op) if balances.is(&op.owner) => {
+            balances.cancel_order(op.orderid)?;
+        }
+        Operation::Convert(op) if balances.is(&op.owner) => {
+            balances.debit(&op.amount)?;
+        }
+        Operation::CollateralizedConvert(op) if balances.is(&op.owner) => {
+            balances.debit(&op.amount)?;
+        }
+        Operation::AccountCreate(op) if balances.is(&op.creator) => {
+            balances.debit(&op.fee)?;
+        }
+        Operation::AccountCreateWithDelegation(op) if balances.is(&op.creator) => {
+            balances.debit(&op.fee)?;
+        }
+        Operation::ClaimAccount(op) if balances.is(&op.creator) => {
+            balances.debit(&op.fee)?;
+        }
+        Operation::EscrowTransfer(op) if balances.is(&op.from) => {
+            for amount in [&op.hive_amount, &op.hbd_amount, &op.fee] {
+                balances.debit(amount)?;
+            }
+        }
+        Operation::EscrowRelease(op) if balances.is(&op.receiver) => {
+            for amount in [&op.hive_amount, &op.hbd_amount] {
+                balances.credit(amount)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn apply_virtual(balances: &mut BalanceState, op: &VirtualOperation) -> Result<()> {
+    match op {
+        VirtualOperation::AuthorReward(op) if balances.is(&op.author) => {
+            for payout in [&op.hive_payout, &op.hbd_payout, &op.vesting_payout] {
+                balances.pay(payout, op.payout_must_be_claimed)?;
+            }
+        }
+        VirtualOperation::CommentBenefactorReward(op) if balances.is(&op.benefactor) => {
+            for payout in [&op.hive_payout, &op.hbd_payout, &op.vesting_payout] {
+                balances.pay(payout, op.payout_must_be_claimed)?;
+            }
+        }
+        VirtualOperation::CurationReward(op) if balances.is(&op.curator) => {
+            balances.pay(&op.reward, op.payout_must_be_claimed)?;
+        }
+        VirtualOperation::ProducerReward(op) if balances.is(&op.producer) => {
+            balances.credit(&op.vesting_shares)?;
+        }
+        VirtualOperation::LiquidityReward(op) if balances.is(&op.owner) => {
+            balances.credit(&op.payout)?;
+        }
+        VirtualOperation::ProposalPay(op) if balances.is(&op.receiver) => {
+            balances.credit(&op.payment)?;
+        }
+        VirtualOperation::Interest(op) if balances.is(&op.owner) => {
+            if op.is_saved_into_hbd_balance {
+                balances.credit(&op.interest)?;
+            } else {
+                adjust(
+                    balances.savings(&op.interest.symbol),
+                    &op.interest,
+                    Asset::checked_add,
+                )?;
+            }
+        }
+        VirtualOperation::TransferToVestingCompleted(op) if balances.is(&op.to_account) => {
+            balances.credit(&op.vesting_shares_received)?;
+        }
+        VirtualOperation::FillVestingWithdraw(op) => {
+            if balances.is(&op.from_account) {
+                balances.debit(&op.withdrawn)?;
+            }
+            if balances.is(&op.to_account) {
+                balances.credit(&op.deposited)?;
+            }
+        }
+        VirtualOperation::FillOrder(op) => {
+            // Each side already paid when it created its order; a fill only
+            // delivers what the other side pays.
+            if balances.is(&op.current_owner) {
+                balances.credit(&op.open_pays)?;
+                balances.fill_order(op.current_orderid, &op.current_pays)?;
+            }
+            if balances.is(&op.open_owner) {
+                balances.credit(&op.current_pays)?;
+                balances.fill_order(op.open_orderid, &op.open_pays)?;
+            }
+        }
+        VirtualOperation::FillConvertRequest(op) if balances.is(&op.owner) => {
+            balances.credit(&op.amount_out)?;
+        }
+        // The HBD was already paid out by
+        // `collateralized_convert_immediate_conversion`; the fill only returns
+        // the collateral that was not needed.
+        VirtualOperation::FillCollateralizedConvertRequest(op) if balances.is(&op.owner) => {
+            balances.credit(&op.excess_collateral)?;
+        }
+        VirtualOperation::FillTransferFromSavings(op) if balances.is(&op.to) => {
+            balances.credit(&op.amount)?;
+        }
+        VirtualOperation::FillRecurrentTransfer(op) => {
+            if balances.is(&op.from) {
+                balances.debit(&op.amount)?;
+            }
+            if balances.is(&op.to) {
+                balances.credit(&op.amount)?;
+            }
+        }
+        VirtualOperation::Other { op_type, body } => {
+            apply_untyped_virtual(balances, op_type, body)?
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Virtual operations without a typed model that still move funds.
+fn apply_untyped_virtual(balances: &mut BalanceState, op_type: &str, body: &Value) -> Result<()> {
+    let involves = |field: &str| body.get(field).and_then(Value::as_str) == Some(&balances.account);
+    let amount = |field: &str| -> Result<Option<Asset>> {
+        body.get(field)
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+            .map_err(Into::into)
+    };
+
+    match op_type {
+        // Emitted alongside a user's limit_order_cancel and when an order
+        // expires; whichever is seen first refunds the order.
+        "limit_order_cancelled" if involves("seller") => {
+            let orderid = body.get("orderid").and_then(Value::as_u64);
+            if let Some(orderid) = orderid.and_then(|id| u32::try_from(id).ok()) {
+                balances.cancel_order(orderid)?;
+            }
+        }
+        // Emitted when the agent or receiver rejects an escrow and when an
+        // unratified escrow expires: the sender gets everything back.
+        "escrow_rejected" if involves("from") => {
+            for field in ["hive_amount", "hbd_amount", "fee"] {
+                if let Some(refund) = amount(field)? {
+                    balances.credit(&refund)?;
+                }
+            }
+        }
+        // HBD from a collateralized conversion is paid when the request is
+        // made, days before `fill_collateralized_convert_request`.
+        "collateralized_convert_immediate_conversion" if involves("owner") => {
+            if let Some(hbd_out) = amount("hbd_out")? {
+                balances.credit(&hbd_out)?;
+            }
+        }
+        // The escrow is ratified and the agent collects its fee.
+        "escrow_approved" if involves("agent") => {
+            if let Some(fee) = amount("fee")? {
+                balances.credit(&fee)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::types::{AppliedOperation, Asset};
+    use crate::utils::{apply_operation_to_balances, BalanceState};
+
+    fn applied(virtual_op: u32, op: Value) -> AppliedOperation {
+        serde_json::from_value(json!({
+            "block": 80000000,
+            "virtual_op": virtual_op,
+            "timestamp": "2023-11-09T11:42:00",
+            "op": op
+        }))
+        .expect("op should decode")
+    }
+
+    #[test]
+    fn replays_deposit_transfer_and_reward() {
+        let history = [
+            applied(
+                0,
+                json!(["transfer", {
+                    "from": "exchange",
+                    "to": "alice",
+                    "amount": "100.000 HIVE",
+                    "memo": "deposit"
+                }]),
+            ),
+            applied(
+                0,
+                json!(["transfer", {
+                    "from": "alice",
+                    "to": "bob",
+                    "amount": "25.500 HIVE",
+                    "memo": ""
+                }]),
+            ),
+            applied(
+                1,
+                json!(["author_reward", {
+                    "author": "alice",
+                    "permlink": "hello",
+                    "hbd_payout": "1.234 HBD",
+                    "hive_payout": "0.000 HIVE",
+                    "vesting_payout": "2500.123456 VESTS",
+                    "payout_must_be_claimed": true
+                }]),
+            ),
+            applied(
+                0,
+                json!(["claim_reward_balance", {
+                    "account": "alice",
+                    "reward_hive": "0.000 HIVE",
+                    "reward_hbd": "1.234 HBD",
+                    "reward_vests": "2000.000000 VESTS"
+                }]),
+            ),
+            applied(
+                1,
+                json!(["fill_order", {
+                    "current_owner": "bob",
+                    "current_orderid": 1,
+                    "current_pays": "10.000 HBD",
+                    "open_owner": "carol",
+                    "open_orderid": 2,
+                    "open_pays": "30.000 HIVE"
+                }]),
+            ),
+        ];
+
+        let mut balances = BalanceState::new("alice");
+        for op in &history {
+            apply_operation_to_balances(&mut balances, op).expect("op should apply");
+        }
+
+        assert_eq!(balances.hive, Asset::hive(74.5));
+        assert_eq!(balances.hbd, Asset::hbd(1.234));
+        assert_eq!(balances.vests, Asset::vests(2000.0));
+        assert_eq!(balances.reward_hbd, Asset::hbd(0.0));
+        assert_eq!(balances.reward_vests, Asset::vests(500.123456));
+    }
+
+    #[test]
+    fn refunds_cancelled_orders_and_settles_escrow() {
+        let escrow = |escrow_id: u32| {
+            applied(
+                0,
+                json!(["escrow_transfer", {
+                    "from": "alice",
+                    "to": "bob",
+                    "agent": "carol",
+                    "escrow_id": escrow_id,
+                    "hbd_amount": "0.000 HBD",
+                    "hive_amount": "10.000 HIVE",
+                    "fee": "0.100 HIVE",
+                    "ratification_deadline": "2023-11-10T00:00:00",
+                    "escrow_expiration": "2023-11-20T00:00:00",
+                    "json_meta": ""
+                }]),
+            )
+        };
+        let history = [
+            applied(
+                0,
+                json!(["transfer", {
+                    "from": "exchange",
+                    "to": "alice",
+                    "amount": "100.000 HIVE",
+                    "memo": ""
+                }]),
+            ),
+            applied(
+                0,
+                json!(["limit_order_create", {
+                    "owner": "alice",
+                    "orderid": 7,
+                    "amount_to_sell": "5.000 HIVE",
+                    "min_to_receive": "1.000 HBD",
+                    "fill_or_kill": false,
+                    "expiration": "2023-11-10T00:00:00"
+                }]),
+            ),
+            applied(
+                1,
+                json!(["fill_order", {
+                    "current_owner": "bob",
+                    "current_orderid": 1,
+                    "current_pays": "0.400 HBD",
+                    "open_owner": "alice",
+                    "open_orderid": 7,
+                    "open_pays": "2.000 HIVE"
+                }]),
+            ),
+            applied(
+                0,
+                json!(["limit_order_cancel", { "owner": "alice", "orderid": 7 }]),
+            ),
+            applied(
+                1,
+                json!(["limit_order_cancelled", {
+                    "seller": "alice",
+                    "orderid": 7,
+                    "amount_back": "3.000 HIVE"
+                }]),
+            ),
+            escrow(1),
+            applied(
+                1,
+                json!(["escrow_rejected", {
+                    "from": "alice",
+                    "to": "bob",
+                    "agent": "carol",
+                    "escrow_id": 1,
+                    "hbd_amount": "0.000 HBD",
+                    "hive_amount": "10.000 HIVE",
+                    "fee": "0.100 HIVE"
+                }]),
+            ),
+            escrow(2),
+            applied(
+                0,
+                json!(["escrow_release", {
+                    "from": "alice",
+                    "to": "bob",
+                    "agent": "carol",
+                    "who": "bob",
+                    "receiver": "alice",
+                    "escrow_id": 2,
+                    "hbd_amount": "0.000 HBD",
+                    "hive_amount": "4.000 HIVE"
+                }]),
+            ),
+        ];
+
+        let mut balances = BalanceState::new("alice");
+        for op in &history {
+            apply_operation_to_balances(&mut balances, op).expect("op should apply");
+        }
+
+        // 2 HIVE sold for 0.4 HBD; escrow 2 still holds 6 HIVE plus the
+        // 0.1 HIVE agent fee.
+        assert_eq!(balances.hive, Asset::hive(91.9));
+        assert_eq!(balances.hbd, Asset::hbd(0.4));
+        assert!(balances.open_orders.is_empty());
+    }
+
+    #[test]
+    fn collateralized_convert_pays_hbd_before_the_fill() {
+        let history = [
+            applied(
+                0,
+                json!(["transfer", {
+                    "from": "exchange",
+                    "to": "alice",
+                    "amount": "100.000 HIVE",
+                    "memo": ""
+                }]),
+            ),
+            applied(
+                0,
+                json!(["collateralized_convert", {
+                    "owner": "alice",
+                    "requestid": 3,
+                    "amount": "10.000 HIVE"
+                }]),
+            ),
+            applied(
+                1,
+                json!(["collateralized_convert_immediate_conversion", {
+                    "owner": "alice",
+                    "requestid": 3,
+                    "hbd_out": "2.000 HBD"
+                }]),
+            ),
+            applied(
+                0,
+                json!(["transfer", {
+                    "from": "alice",
+                    "to": "bob",
+                    "amount": "2.000 HBD",
+                    "memo": ""
+                }]),
+            ),
+            applied(
+                1,
+                json!(["fill_collateralized_convert_request", {
+                    "owner": "alice",
+                    "requestid": 3,
+                    "amount_in": "4.500 HIVE",
+                    "amount_out": "2.000 HBD",
+                    "excess_collateral": "5.500 HIVE"
+                }]),
+            ),
+        ];
+
+        let mut balances = BalanceState::new("alice");
+        for op in &history {
+            apply_operation_to_balances(&mut balances, op).expect("op should apply");
+        }
+
+        assert_eq!(balances.hive, Asset::hive(95.5));
+        assert_eq!(balances.hbd, Asset::hbd(0.0));
+    }
+
+    #[test]
+    fn rejects_movements_that_overdraw_a_balance() {
+        let mut balances = BalanceState::new("alice");
+        let overdraw = applied(
+            0,
+            json!(["transfer", {
+                "from": "alice",
+                "to": "bob",
+                "amount": "1.000 HIVE",
+                "memo": ""
+            }]),
+        );
+
+        apply_operation_to_balances(&mut balances, &overdraw)
+            .expect_err("a transfer from an empty balance should fail");
+        assert_eq!(balances.hive, Asset::hive(0.0));
+    }
+}
@@ -1,17 +1,32 @@
 mod asset_helpers;
+mod balances;
 mod nonce;
+mod power_down;
+mod vote;
+mod witness;
+
+use std::cmp::Ordering;
 
 use serde_json::Value;
 
+use crate::crypto::KeyRole;
 use crate::error::{HiveError, Result};
 use crate::serialization::types::{
     write_asset, write_price, write_public_key, write_string, write_u16, write_u32,
 };
-use crate::types::OperationName;
-use crate::types::{Asset, Price, WitnessProps, WitnessSetPropertiesOperation};
+use crate::types::{
+    Asset, Operation, OperationName, Price, RequiredAuthorities, WitnessProps,
+    WitnessSetPropertiesOperation,
+};
 
-pub use asset_helpers::{get_vesting_share_price, get_vests};
-pub use nonce::unique_nonce;
+pub use asset_helpers::{get_vesting_share_price, get_vests, vests_to_hp};
+pub use balances::{apply_operation_to_balances, BalanceState};
+pub use nonce::{unique_nonce, NonceGenerator};
+pub use power_down::{power_down_schedule, PowerDownTranche};
+pub use vote::{estimate_payout, vote_rshares, VOTE_DUST_THRESHOLD};
+pub use witness::{
+    WitnessSetPropsBuilder, MAX_WITNESS_URL_LENGTH, MIN_BLOCK_SIZE_LIMIT, SOFT_MAX_BLOCK_SIZE,
+};
 
 pub fn make_bit_mask_filter(operations: &[OperationName]) -> (u64, u64) {
     let mut lower = 0_u64;
@@ -29,6 +44,81 @@ pub fn make_bit_mask_filter(operations: &[OperationName]) -> (u64, u64) {
     (lower, upper)
 }
 
+/// Like [`make_bit_mask_filter`], but takes snake_case operation names such as
+/// `"custom_json"`, failing on the first unknown name.
+pub fn make_bit_mask_filter_from_names(names: &[&str]) -> Result<(u64, u64)> {
+    let operations = names
+        .iter()
+        .map(|name| {
+            OperationName::from_name(name)
+                .ok_or_else(|| HiveError::Other(format!("unknown operation name '{name}'")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(make_bit_mask_filter(&operations))
+}
+
+/// The strictest account role any of `operations` needs: Owner, then Active,
+/// then Posting. `None` means the operations are authorized only by keys or
+/// authorities they name themselves (the signing key of
+/// `witness_set_properties`, the owner authorities of `recover_account`);
+/// those are in [`RequiredAuthorities::other`]. An empty batch needs only
+/// Posting.
+pub fn required_roles(operations: &[Operation]) -> Option<KeyRole> {
+    let required = RequiredAuthorities::for_operations(operations);
+    if !required.owner.is_empty() {
+        Some(KeyRole::Owner)
+    } else if !required.active.is_empty() {
+        Some(KeyRole::Active)
+    } else if !required.posting.is_empty() || required.other.is_empty() {
+        Some(KeyRole::Posting)
+    } else {
+        None
+    }
+}
+
+/// Converts a raw reputation into the log10-based scale shown by Hive front
+/// ends, where new accounts start at 25.
+pub fn reputation_score(raw: i64) -> f64 {
+    if raw == 0 {
+        return 25.0;
+    }
+
+    let magnitude = (raw.unsigned_abs() as f64).log10();
+    let mut score = (magnitude - 9.0).max(0.0);
+    if raw < 0 {
+        score = -score;
+    }
+    score * 9.0 + 25.0
+}
+
+/// Parses an `x.y.z` hardfork or blockchain version into its components.
+pub fn parse_hardfork_version(version: &str) -> Result<(u32, u32, u32)> {
+    let invalid = || HiveError::Other(format!("invalid hardfork version '{version}'"));
+    let mut parts = version.trim().split('.');
+    let mut next = || -> Result<u32> {
+        parts
+            .next()
+            .and_then(|part| part.parse::<u32>().ok())
+            .ok_or_else(invalid)
+    };
+    let parsed = (next()?, next()?, next()?);
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(parsed)
+}
+
+pub fn try_compare_hardfork(a: &str, b: &str) -> Result<Ordering> {
+    Ok(parse_hardfork_version(a)?.cmp(&parse_hardfork_version(b)?))
+}
+
+/// Compares two `x.y.z` versions; malformed versions sort before valid ones.
+pub fn compare_hardfork(a: &str, b: &str) -> Ordering {
+    parse_hardfork_version(a)
+        .ok()
+        .cmp(&parse_hardfork_version(b).ok())
+}
+
 pub fn build_witness_update_op(
     owner: &str,
     props: WitnessProps,
@@ -107,10 +197,44 @@ fn parse_u16(value: &Value, field: &str) -> Result<u16> {
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
     use serde_json::json;
 
-    use crate::types::{OperationName, WitnessProps};
-    use crate::utils::{build_witness_update_op, make_bit_mask_filter};
+    use crate::crypto::KeyRole;
+    use crate::types::{
+        Asset, Operation, OperationName, TransferOperation, VoteOperation, WitnessProps,
+    };
+    use crate::utils::{
+        build_witness_update_op, compare_hardfork, make_bit_mask_filter,
+        make_bit_mask_filter_from_names, reputation_score, required_roles, try_compare_hardfork,
+    };
+
+    #[test]
+    fn required_roles_picks_strictest_role() {
+        let transfer = Operation::Transfer(TransferOperation {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            amount: Asset::hive(1.0),
+            memo: String::new(),
+        });
+        let vote = Operation::Vote(VoteOperation {
+            voter: "alice".to_string(),
+            author: "bob".to_string(),
+            permlink: "post".to_string(),
+            weight: 10000,
+        });
+
+        assert_eq!(
+            required_roles(std::slice::from_ref(&transfer)),
+            Some(KeyRole::Active)
+        );
+        assert_eq!(
+            required_roles(std::slice::from_ref(&vote)),
+            Some(KeyRole::Posting)
+        );
+        assert_eq!(required_roles(&[vote, transfer]), Some(KeyRole::Active));
+    }
 
     #[test]
     fn make_bitmask_filter_sets_expected_bits() {
@@ -125,6 +249,20 @@ mod tests {
         assert_eq!(high, 0);
     }
 
+    #[test]
+    fn bitmask_filter_from_names_matches_enum_filter() {
+        let from_names = make_bit_mask_filter_from_names(&["vote", "custom_json", "fill_order"])
+            .expect("names should resolve");
+        let from_enum = make_bit_mask_filter(&[
+            OperationName::Vote,
+            OperationName::CustomJson,
+            OperationName::FillOrder,
+        ]);
+        assert_eq!(from_names, from_enum);
+
+        assert!(make_bit_mask_filter_from_names(&["vote", "not_an_op"]).is_err());
+    }
+
     #[test]
     fn build_witness_update_op_serializes_and_sorts_props() {
         let mut props = WitnessProps::default();
@@ -141,4 +279,26 @@ mod tests {
         assert_eq!(operation.props[0].0, "hbd_interest_rate");
         assert_eq!(operation.props[1].0, "url");
     }
+
+    #[test]
+    fn compare_hardfork_orders_versions() {
+        assert_eq!(compare_hardfork("0.23.0", "0.24.0"), Ordering::Less);
+        assert_eq!(compare_hardfork("1.27.5", "1.27.5"), Ordering::Equal);
+        assert_eq!(compare_hardfork("0.9.0", "0.10.0"), Ordering::Less);
+        assert_eq!(
+            try_compare_hardfork("0.24.0", "0.23.9").expect("versions should parse"),
+            Ordering::Greater
+        );
+        assert!(try_compare_hardfork("0.24", "0.23.0").is_err());
+        assert!(try_compare_hardfork("0.24.x", "0.23.0").is_err());
+    }
+
+    #[test]
+    fn reputation_score_matches_known_values() {
+        assert_eq!(reputation_score(0), 25.0);
+        assert_eq!(reputation_score(1_000_000_000), 25.0);
+        assert!((reputation_score(95_832_978_796_820) - 69.83).abs() < 0.01);
+        assert!((reputation_score(-10_000_000_000_000) - -11.0).abs() < 1e-9);
+        assert!((reputation_score(2_431_874_930_516) - 55.47).abs() < 0.01);
+    }
 }
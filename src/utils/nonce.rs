@@ -1,27 +1,70 @@
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-static NONCE_ENTROPY: OnceLock<AtomicU32> = OnceLock::new();
+static GLOBAL_NONCES: OnceLock<NonceGenerator> = OnceLock::new();
 
+/// Source of memo nonces: the millisecond timestamp in the high bits and a
+/// counter in the low 16, forced to strictly increase so rapid calls (or a
+/// clock stepping backwards) never repeat a value. [`unique_nonce`] shares one
+/// generator per process; hold your own for explicit control.
+#[derive(Debug)]
+pub struct NonceGenerator {
+    counter: AtomicU32,
+    last: AtomicU64,
+}
+
+impl NonceGenerator {
+    /// A generator whose counter starts at a random offset.
+    pub fn new() -> Self {
+        Self::with_seed(rand::random::<u16>() as u32)
+    }
+
+    /// A generator whose counter starts at `seed`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            counter: AtomicU32::new(seed),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    pub fn next_nonce(&self) -> u64 {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|value| value.as_millis() as u64)
+            .unwrap_or_default();
+
+        let low = (self.counter.fetch_add(1, Ordering::Relaxed).wrapping_add(1)) % 0xFFFF;
+        let candidate = (now_ms << 16) | (low as u64);
+
+        let previous = self
+            .last
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+                Some(candidate.max(last.wrapping_add(1)))
+            })
+            .unwrap_or_default();
+        candidate.max(previous.wrapping_add(1))
+    }
+}
+
+impl Default for NonceGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns a time-based nonce that strictly increases within this process, so
+/// rapid calls (or a clock stepping backwards) can never repeat a value.
 pub fn unique_nonce() -> u64 {
-    let entropy = NONCE_ENTROPY.get_or_init(|| {
-        let seed = rand::random::<u16>() as u32;
-        AtomicU32::new(seed)
-    });
-
-    let now_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|value| value.as_millis() as u64)
-        .unwrap_or_default();
-
-    let low = (entropy.fetch_add(1, Ordering::Relaxed) + 1) % 0xFFFF;
-    (now_ms << 16) | (low as u64)
+    GLOBAL_NONCES.get_or_init(NonceGenerator::new).next_nonce()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::unique_nonce;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use crate::utils::{unique_nonce, NonceGenerator};
 
     #[test]
     fn nonces_are_unique_for_sequential_calls() {
@@ -29,4 +72,33 @@ mod tests {
         let second = unique_nonce();
         assert_ne!(first, second);
     }
+
+    #[test]
+    fn nonces_strictly_increase_under_rapid_calls() {
+        let nonces: Vec<u64> = (0..10_000).map(|_| unique_nonce()).collect();
+        assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn shared_generator_never_repeats_across_threads() {
+        let generator = Arc::new(NonceGenerator::with_seed(0));
+        let handles = (0..4)
+            .map(|_| {
+                let generator = generator.clone();
+                std::thread::spawn(move || {
+                    (0..10_000)
+                        .map(|_| generator.next_nonce())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for nonce in handle.join().expect("thread should finish") {
+                assert!(seen.insert(nonce), "duplicate nonce {nonce}");
+            }
+        }
+        assert_eq!(seen.len(), 40_000);
+    }
 }
@@ -0,0 +1,112 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::serialization::types::parse_hive_time;
+use crate::types::{Asset, DynamicGlobalProperties, ExtendedAccount};
+use crate::utils::vests_to_hp;
+
+const VESTING_WITHDRAW_INTERVAL_SECONDS: i64 = 60 * 60 * 24 * 7;
+const VESTING_WITHDRAW_INTERVALS: i64 = 13;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerDownTranche {
+    pub date: DateTime<Utc>,
+    pub vests: Asset,
+    pub hive: Asset,
+}
+
+/// Returns the withdrawals still pending for an account that is powering down,
+/// or an empty schedule when no power down is active.
+pub fn power_down_schedule(
+    account: &ExtendedAccount,
+    props: &DynamicGlobalProperties,
+) -> Vec<PowerDownTranche> {
+    let Some(rate) = account
+        .vesting_withdraw_rate
+        .as_ref()
+        .filter(|rate| rate.amount > 0)
+    else {
+        return Vec::new();
+    };
+    let to_withdraw = parse_raw_amount(account.to_withdraw.as_deref());
+    let withdrawn = parse_raw_amount(account.withdrawn.as_deref());
+    let Some(next) = account
+        .next_vesting_withdrawal
+        .as_deref()
+        .and_then(|value| parse_hive_time(value).ok())
+        .filter(|next| next.timestamp() > 0)
+    else {
+        return Vec::new();
+    };
+
+    let mut remaining = to_withdraw.saturating_sub(withdrawn);
+    let mut schedule = Vec::new();
+    let mut date = next;
+    while remaining > 0 && (schedule.len() as i64) < VESTING_WITHDRAW_INTERVALS {
+        let amount = remaining.min(rate.amount);
+        let vests = Asset {
+            amount,
+            precision: rate.precision,
+            symbol: rate.symbol.clone(),
+            display_symbol: None,
+        };
+        let hive = vests_to_hp(props, &vests);
+        schedule.push(PowerDownTranche { date, vests, hive });
+        remaining -= amount;
+        date += Duration::seconds(VESTING_WITHDRAW_INTERVAL_SECONDS);
+    }
+    schedule
+}
+
+fn parse_raw_amount(value: Option<&str>) -> i64 {
+    value
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::serialization::types::format_hive_time;
+    use crate::types::{Asset, DynamicGlobalProperties, ExtendedAccount};
+    use crate::utils::power_down_schedule;
+
+    #[test]
+    fn power_down_schedule_lists_remaining_tranches() {
+        let account: ExtendedAccount = serde_json::from_value(json!({
+            "name": "alice",
+            "vesting_withdraw_rate": "1000.000000 VESTS",
+            "to_withdraw": 13000000000_i64,
+            "withdrawn": "4000000000",
+            "next_vesting_withdrawal": "2024-01-08T00:00:00"
+        }))
+        .expect("account should deserialize");
+        let props = DynamicGlobalProperties {
+            total_vesting_fund_hive: Some(Asset::hive(500.0)),
+            total_vesting_shares: Some(Asset::vests(1000.0)),
+            ..Default::default()
+        };
+
+        let schedule = power_down_schedule(&account, &props);
+        assert_eq!(schedule.len(), 9);
+        assert!(schedule
+            .iter()
+            .all(|tranche| tranche.vests == Asset::vests(1000.0)
+                && tranche.hive == Asset::hive(500.0)));
+        assert_eq!(format_hive_time(schedule[0].date), "2024-01-08T00:00:00");
+        assert_eq!(format_hive_time(schedule[8].date), "2024-03-04T00:00:00");
+    }
+
+    #[test]
+    fn power_down_schedule_is_empty_without_active_withdrawal() {
+        let account: ExtendedAccount = serde_json::from_value(json!({
+            "name": "bob",
+            "vesting_withdraw_rate": "0.000000 VESTS",
+            "next_vesting_withdrawal": "1969-12-31T23:59:59"
+        }))
+        .expect("account should deserialize");
+        let props = DynamicGlobalProperties::default();
+
+        assert!(power_down_schedule(&account, &props).is_empty());
+    }
+}
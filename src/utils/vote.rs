@@ -0,0 +1,65 @@
+use crate::error::{HiveError, Result};
+use crate::types::{Asset, AssetSymbol, Price, RewardFund};
+
+/// Rshares below this are dropped from every vote (`HIVE_VOTE_DUST_THRESHOLD`).
+pub const VOTE_DUST_THRESHOLD: i64 = 50_000_000;
+
+/// A full-weight vote spends this fraction (1/50) of the voter's mana.
+const VOTE_MANA_DENOMINATOR: i128 = 50;
+
+/// Rshares a vote of `weight` basis points (-10000..=10000) would add, given
+/// the voter's current voting mana. Negative weights give negative rshares.
+pub fn vote_rshares(current_mana: i64, weight: i16) -> i64 {
+    let weight = i128::from(weight.clamp(-10_000, 10_000));
+    let used = i128::from(current_mana.max(0)) * weight.abs() / 10_000 / VOTE_MANA_DENOMINATOR;
+    let rshares = (used - i128::from(VOTE_DUST_THRESHOLD)).max(0) as i64;
+    if weight < 0 {
+        -rshares
+    } else {
+        rshares
+    }
+}
+
+/// HBD value of `rshares` against a reward fund, priced with the median feed
+/// (HBD per HIVE, either orientation).
+pub fn estimate_payout(rshares: i64, fund: &RewardFund, median_price: &Price) -> Result<Asset> {
+    let recent_claims = fund
+        .recent_claims
+        .as_deref()
+        .and_then(|claims| claims.parse::<i128>().ok())
+        .filter(|claims| *claims > 0)
+        .ok_or_else(|| HiveError::Other("reward fund has no recent_claims".to_string()))?;
+    let reward_balance = fund
+        .reward_balance
+        .as_ref()
+        .ok_or_else(|| HiveError::Other("reward fund has no reward_balance".to_string()))?;
+
+    let price = median_price.hbd_per_hive().ok_or_else(|| {
+        HiveError::Other("median price must be a nonzero HBD/HIVE price".to_string())
+    })?;
+
+    let hive = i128::from(rshares) * i128::from(reward_balance.amount) / recent_claims;
+    let hbd = hive * i128::from(price.base.amount) / i128::from(price.quote.amount);
+    Ok(Asset {
+        amount: hbd as i64,
+        precision: 3,
+        symbol: AssetSymbol::Hbd,
+        display_symbol: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{vote_rshares, VOTE_DUST_THRESHOLD};
+
+    #[test]
+    fn vote_rshares_applies_weight_and_dust() {
+        let mana = 1_000_000_000_000_i64;
+        assert_eq!(vote_rshares(mana, 10_000), mana / 50 - VOTE_DUST_THRESHOLD);
+        assert_eq!(
+            vote_rshares(mana, -5_000),
+            -(mana / 100 - VOTE_DUST_THRESHOLD)
+        );
+        assert_eq!(vote_rshares(1_000, 10_000), 0);
+    }
+}
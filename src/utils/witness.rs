@@ -0,0 +1,229 @@
+use crate::crypto::PublicKey;
+use crate::error::{HiveError, Result};
+use crate::serialization::types::{
+    write_asset, write_price, write_public_key, write_string, write_u16, write_u32,
+};
+use crate::types::{Asset, AssetSymbol, Price, WitnessSetPropertiesOperation};
+
+/// Smallest `maximum_block_size` witnesses may vote for
+/// (`HIVE_MIN_BLOCK_SIZE_LIMIT`, twice the maximum transaction size).
+pub const MIN_BLOCK_SIZE_LIMIT: u32 = 2 * 64 * 1024;
+
+/// Largest `maximum_block_size` witnesses may vote for (`HIVE_SOFT_MAX_BLOCK_SIZE`).
+pub const SOFT_MAX_BLOCK_SIZE: u32 = 2 * 1024 * 1024;
+
+/// Longest witness URL hived accepts (`HIVE_MAX_WITNESS_URL_LENGTH`).
+pub const MAX_WITNESS_URL_LENGTH: usize = 2048;
+
+/// Typed alternative to [`build_witness_update_op`](crate::utils::build_witness_update_op):
+/// each prop is range-checked the way hived checks it, then serialized into a
+/// `witness_set_properties` operation with props sorted by key.
+#[derive(Debug, Clone)]
+pub struct WitnessSetPropsBuilder {
+    owner: String,
+    key: String,
+    account_creation_fee: Option<Asset>,
+    maximum_block_size: Option<u32>,
+    hbd_interest_rate: Option<u16>,
+    hbd_exchange_rate: Option<Price>,
+    new_signing_key: Option<String>,
+    url: Option<String>,
+    account_subsidy_budget: Option<u32>,
+}
+
+impl WitnessSetPropsBuilder {
+    /// `signing_key` is the witness's current signing key, sent as the `key`
+    /// prop the node checks the signature against.
+    pub fn new(owner: impl Into<String>, signing_key: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            key: signing_key.into(),
+            account_creation_fee: None,
+            maximum_block_size: None,
+            hbd_interest_rate: None,
+            hbd_exchange_rate: None,
+            new_signing_key: None,
+            url: None,
+            account_subsidy_budget: None,
+        }
+    }
+
+    pub fn account_creation_fee(mut self, fee: Asset) -> Self {
+        self.account_creation_fee = Some(fee);
+        self
+    }
+
+    pub fn maximum_block_size(mut self, size: u32) -> Self {
+        self.maximum_block_size = Some(size);
+        self
+    }
+
+    /// Interest on HBD savings in basis points (0..=10000).
+    pub fn hbd_interest_rate(mut self, rate: u16) -> Self {
+        self.hbd_interest_rate = Some(rate);
+        self
+    }
+
+    /// Price feed as HBD per HIVE.
+    pub fn hbd_exchange_rate(mut self, price: Price) -> Self {
+        self.hbd_exchange_rate = Some(price);
+        self
+    }
+
+    pub fn new_signing_key(mut self, key: &str) -> Self {
+        self.new_signing_key = Some(key.to_string());
+        self
+    }
+
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    pub fn account_subsidy_budget(mut self, budget: u32) -> Self {
+        self.account_subsidy_budget = Some(budget);
+        self
+    }
+
+    pub fn build(self) -> Result<WitnessSetPropertiesOperation> {
+        let invalid = |message: String| Err(HiveError::Other(message));
+        let mut props: Vec<(String, Vec<u8>)> = Vec::new();
+        let mut push = |key: &str, write: &dyn Fn(&mut Vec<u8>) -> Result<()>| -> Result<()> {
+            let mut buf = Vec::new();
+            write(&mut buf)?;
+            props.push((key.to_string(), buf));
+            Ok(())
+        };
+
+        PublicKey::from_string(&self.key)?;
+        push("key", &|buf| write_public_key(buf, &self.key))?;
+
+        if let Some(fee) = &self.account_creation_fee {
+            if fee.symbol != AssetSymbol::Hive || fee.amount < 0 {
+                return invalid(format!(
+                    "account_creation_fee must be a non-negative HIVE amount, got {fee}"
+                ));
+            }
+            push("account_creation_fee", &|buf| write_asset(buf, fee))?;
+        }
+        if let Some(size) = self.maximum_block_size {
+            if !(MIN_BLOCK_SIZE_LIMIT..=SOFT_MAX_BLOCK_SIZE).contains(&size) {
+                return invalid(format!(
+                    "maximum_block_size must be between {MIN_BLOCK_SIZE_LIMIT} and {SOFT_MAX_BLOCK_SIZE}, got {size}"
+                ));
+            }
+            push("maximum_block_size", &|buf| {
+                write_u32(buf, size);
+                Ok(())
+            })?;
+        }
+        if let Some(rate) = self.hbd_interest_rate {
+            if rate > 10_000 {
+                return invalid(format!(
+                    "hbd_interest_rate must be at most 10000 basis points, got {rate}"
+                ));
+            }
+            push("hbd_interest_rate", &|buf| {
+                write_u16(buf, rate);
+                Ok(())
+            })?;
+        }
+        if let Some(price) = &self.hbd_exchange_rate {
+            if price.base.symbol != AssetSymbol::Hbd || price.quote.symbol != AssetSymbol::Hive {
+                return invalid(format!(
+                    "hbd_exchange_rate must be quoted as HBD per HIVE, got {} / {}",
+                    price.base, price.quote
+                ));
+            }
+            push("hbd_exchange_rate", &|buf| write_price(buf, price))?;
+        }
+        if let Some(key) = &self.new_signing_key {
+            PublicKey::from_string(key)?;
+            push("new_signing_key", &|buf| write_public_key(buf, key))?;
+        }
+        if let Some(url) = &self.url {
+            if url.is_empty() || url.len() > MAX_WITNESS_URL_LENGTH {
+                return invalid(format!(
+                    "url must be 1 to {MAX_WITNESS_URL_LENGTH} bytes, got {}",
+                    url.len()
+                ));
+            }
+            push("url", &|buf| {
+                write_string(buf, url);
+                Ok(())
+            })?;
+        }
+        if let Some(budget) = self.account_subsidy_budget {
+            if budget == 0 {
+                return invalid("account_subsidy_budget must be positive".to_string());
+            }
+            push("account_subsidy_budget", &|buf| {
+                write_u32(buf, budget);
+                Ok(())
+            })?;
+        }
+
+        props.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(WitnessSetPropertiesOperation {
+            owner: self.owner,
+            props,
+            extensions: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::types::{Asset, Price, WitnessProps};
+    use crate::utils::{build_witness_update_op, WitnessSetPropsBuilder};
+
+    const KEY: &str = "STM87F7tN56tAUL2C6J9Gzi9HzgNpZdi6M2cLQo7TjDU5v178QsYA";
+
+    #[test]
+    fn builder_matches_map_path_and_checks_ranges() {
+        let price = Price {
+            base: Asset::hbd(0.25),
+            quote: Asset::hive(1.0),
+        };
+        let built = WitnessSetPropsBuilder::new("alice", KEY)
+            .url("https://example.com")
+            .hbd_interest_rate(1500)
+            .maximum_block_size(65536 * 2)
+            .account_creation_fee(Asset::hive(3.0))
+            .hbd_exchange_rate(price.clone())
+            .new_signing_key(KEY)
+            .account_subsidy_budget(797)
+            .build()
+            .expect("props should build");
+
+        let mut props = WitnessProps::default();
+        for (key, value) in [
+            ("key", json!(KEY)),
+            ("url", json!("https://example.com")),
+            ("hbd_interest_rate", json!(1500)),
+            ("maximum_block_size", json!(131072)),
+            ("account_creation_fee", json!(Asset::hive(3.0))),
+            ("hbd_exchange_rate", json!(price)),
+            ("new_signing_key", json!(KEY)),
+            ("account_subsidy_budget", json!(797)),
+        ] {
+            props.extra.insert(key.to_string(), value);
+        }
+        let mapped = build_witness_update_op("alice", props).expect("map should build");
+        assert_eq!(built, mapped);
+
+        let too_small = WitnessSetPropsBuilder::new("alice", KEY)
+            .maximum_block_size(65536)
+            .build();
+        assert!(too_small.is_err());
+        assert!(WitnessSetPropsBuilder::new("alice", KEY)
+            .hbd_interest_rate(10_001)
+            .build()
+            .is_err());
+        assert!(WitnessSetPropsBuilder::new("alice", "not-a-key")
+            .build()
+            .is_err());
+    }
+}